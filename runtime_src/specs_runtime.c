@@ -20,7 +20,7 @@ inline _Specs_NumericIterator _Specs_NumericIterator__New( int start, int end, i
 inline int _Specs_NumericIterator__Next( _Specs_NumericIterator* iterator )
 {
     int result = iterator->n;
-    iterator->n += 1;
+    iterator->n += iterator->step;
     return result;
 }
 inline bool _Specs_NumericIterator__HasNext( _Specs_NumericIterator* iterator )
@@ -4,29 +4,141 @@ use super::common::*;
 /// ## C Convert Pass
 ///
 /// - Try and collapse the whole node tree into a CNode with the final C output program
+/// - Struct and function declarations are collected and placed at the start of the program
+/// - A struct and functions are generated for each type of array used in the program
 ///
-pub struct Convert {}
+pub struct Convert
+{
+    declarations: Vec<String>,
+    array_types:  Vec<(String, String)>,
+}
 
 impl Convert
 {
     pub fn new() -> Convert
     {
-        Convert {}
+        Convert {
+            declarations: Vec::new(),
+            array_types:  Vec::new(),
+        }
+    }
+
+    ///
+    /// Keep track of any array types used in a type (as the name of the array type and the C
+    ///     version of its element type), so their structs and functions can be generated
+    ///
+    fn add_array_types(&mut self, t: &Type)
+    {
+        match t
+        {
+            Type::Array(array) =>
+            {
+                // Element arrays are generated first, since this array is made of them
+
+                let element_type = array.get_element();
+                self.add_array_types(&element_type.get());
+
+                let name = get_array_name(array);
+                if !self.array_types.iter().any(|(existing, _)| existing == &name)
+                {
+                    let element_text = element_type.get().get_c_text();
+                    self.array_types.push((name, element_text));
+                }
+            }
+            Type::Reference(reference) => self.add_array_types(&reference.get_target().get()),
+            Type::Function(function) =>
+            {
+                for argument in function.get_arguments()
+                {
+                    self.add_array_types(&argument.get());
+                }
+                self.add_array_types(&function.get_return_type().get());
+            }
+            Type::Class(class) =>
+            {
+                for member in class.get_members()
+                {
+                    self.add_array_types(&member.get_type().get());
+                }
+            }
+            _ => (),
+        }
+    }
+
+    ///
+    /// Get the text for all generated array types
+    ///
+    /// - Returns declarations (which go before anything else), struct definitions and function
+    ///     prototypes (which go before other definitions), and function definitions (which go
+    ///     after everything, once their element types are complete)
+    ///
+    fn take_array_texts(&mut self) -> (String, String, String)
+    {
+        let mut declarations = Vec::new();
+        let mut structs = Vec::new();
+        let mut functions = Vec::new();
+
+        for (name, element) in std::mem::take(&mut self.array_types)
+        {
+            let new_name = format!("{}__{}", name, runtime::names::ARRAY_NEW_FUNCTION);
+            let from_name = format!("{}__{}", name, runtime::names::ARRAY_FROM_FUNCTION);
+            let at_name = format!("{}__{}", name, runtime::names::ARRAY_AT_FUNCTION);
+
+            let new_signature = format!("{} {}(long length, {} value)", name, new_name, element);
+            let from_signature =
+                format!("{} {}(long length, {}* values)", name, from_name, element);
+            let at_signature = format!("{}* {}({} array, long index)", element, at_name, name);
+
+            declarations.push(format!("typedef struct {} {};", name, name));
+
+            structs.push(format!(
+                "struct {} {{ long length; {}* data; }}; {}; {}; {};",
+                name, element, new_signature, from_signature, at_signature
+            ));
+
+            let allocate_text = format!(
+                "{{ length, ({}*){}(sizeof({}) * length) }}",
+                element,
+                runtime::names::ALLOCATE_FUNCTION,
+                element
+            );
+            functions.push(format!(
+                "{}{{ {}(length); {} array = {}; for (long i = 0; i < length; i++) {{ array.data[i] = value; }} return array; }}",
+                new_signature,
+                runtime::names::CHECK_LENGTH_FUNCTION,
+                name,
+                allocate_text
+            ));
+            functions.push(format!(
+                "{}{{ {} array = {}; for (long i = 0; i < length; i++) {{ array.data[i] = values[i]; }} return array; }}",
+                from_signature, name, allocate_text
+            ));
+            functions.push(format!(
+                "{}{{ {}(index, array.length); return &array.data[index]; }}",
+                at_signature,
+                runtime::names::CHECK_BOUNDS_FUNCTION
+            ));
+        }
+
+        (declarations.join(" "), structs.join(" "), functions.join(" "))
     }
 }
 
 ///
 /// Pass state for Convert
 ///
-/// - ...
+/// - Track if we should dump the accumulated declarations at this node
 ///
-pub struct PassState {}
+pub struct PassState
+{
+    pub is_root: bool,
+}
 
 impl PassState
 {
-    pub fn new() -> PassState
+    pub fn new(is_root: bool) -> PassState
     {
-        PassState {}
+        PassState { is_root }
     }
 }
 
@@ -34,11 +146,77 @@ impl RecurTransform<Node, PassState, Error> for Convert
 {
     fn get_root_state(&mut self, _node: &Node) -> PassState
     {
-        PassState::new()
+        PassState::new(true)
+    }
+
+    fn get_child_states(&mut self, state: &PassState, _node: &Node) -> Vec<ChildState<PassState>>
+    {
+        if state.is_root
+        {
+            vec![ChildState::New(PassState::new(false))]
+        }
+        else
+        {
+            vec![ChildState::Inherit]
+        }
     }
 
-    fn exit(&mut self, node: &mut Node, _state: &mut PassState) -> ResultLog<(), Error>
+    fn enter(&mut self, node: &mut Node, _state: &mut PassState) -> ResultLog<(), Error>
     {
+        // Create operators are converted before their operands, since the operand is a type
+        //  rather than a value
+
+        let operator = match node
+        {
+            Node::Call(call) => match call.get_operator()
+            {
+                Node::PrimitiveOperator(operator) => operator.get_value(),
+                _ => return ResultLog::Ok(()),
+            },
+            _ => return ResultLog::Ok(()),
+        };
+
+        let call_type = node.get_type();
+
+        let (cnode_type, text) = match operator
+        {
+            primitive::Operator::Create =>
+            {
+                // Instances are created zero-initialized on the stack
+                let text = format!("(({}){{ 0 }})", call_type.get().get_c_text());
+                (CNodeType::Atomic, text)
+            }
+            primitive::Operator::HeapAllocate =>
+            {
+                let instance_type = match call_type.get().dereference()
+                {
+                    Some(t) => t,
+                    None =>
+                    {
+                        return ResultLog::new_error(Error::Internal(format!(
+                            "Expected heap allocation to have a reference type: {}",
+                            node
+                        )))
+                    }
+                };
+
+                let text = format!(
+                    "_specs__Allocate(sizeof({}))",
+                    instance_type.get().get_c_text()
+                );
+                (CNodeType::Call, text)
+            }
+            _ => return ResultLog::Ok(()),
+        };
+
+        *node = CNode::new(cnode_type, text, call_type, node.get_source()).to_node();
+        ResultLog::Ok(())
+    }
+
+    fn exit(&mut self, node: &mut Node, state: &mut PassState) -> ResultLog<(), Error>
+    {
+        self.add_array_types(&node.get_type().get());
+
         match node
         {
             Node::Comment(comment) =>
@@ -63,6 +241,15 @@ impl RecurTransform<Node, PassState, Error> for Convert
                 )
                 .to_node();
             }
+            Node::Float(float) =>
+            {
+                // Debug formatting gives the shortest text that reads back as the same value, and
+                //  always includes a decimal point or exponent
+
+                let text = format!("{:?}", float.get_value());
+                *node = CNode::new(CNodeType::Atomic, text, float.get_type(), float.get_source())
+                    .to_node();
+            }
             Node::Boolean(boolean) =>
             {
                 let text = format!("{}", boolean.get_value());
@@ -74,6 +261,19 @@ impl RecurTransform<Node, PassState, Error> for Convert
                 )
                 .to_node();
             }
+            Node::Text(text) =>
+            {
+                // Strings are stored with their length, so they can contain null characters
+
+                let c_text = format!(
+                    "(({}){{ {}, \"{}\" }})",
+                    runtime::names::STRING_TYPE,
+                    text.get_value().len(),
+                    escape_c_string(text.get_value())
+                );
+                *node = CNode::new(CNodeType::Atomic, c_text, text.get_type(), text.get_source())
+                    .to_node();
+            }
             Node::Variable(variable) =>
             {
                 let text = format!("{}", variable.get_name());
@@ -124,7 +324,31 @@ impl RecurTransform<Node, PassState, Error> for Convert
                     // primitive::Operator::HeapAllocate => (CNodeType::Atomic, ""),
                     // primitive::Operator::HeapFree => (CNodeType::Atomic, ""),
 
+                    // Closure operators
+                    primitive::Operator::MakeClosure => (CNodeType::MakeClosure, ""),
+                    primitive::Operator::ApplyClosure => (CNodeType::ApplyClosure, ""),
+
+                    // Conversion operators
+                    primitive::Operator::ToInteger => (CNodeType::PrefixOperator, "(int)"),
+                    primitive::Operator::ToFloat => (CNodeType::PrefixOperator, "(double)"),
+
+                    // Iterator operators are named for their iterable along with their calls
+                    primitive::Operator::Range => (CNodeType::Range, ""),
+                    primitive::Operator::MakeIterator => (CNodeType::MakeIterator, ""),
+                    primitive::Operator::IteratorHasNext =>
+                    {
+                        (CNodeType::Atomic, runtime::names::RANGE_HAS_NEXT_FUNCTION)
+                    }
+                    primitive::Operator::IteratorNext => (CNodeType::IteratorNext, ""),
+
+                    // Array operators are named for their array type along with their calls
+                    primitive::Operator::ArrayLiteral => (CNodeType::ArrayLiteral, ""),
+                    primitive::Operator::MakeArray => (CNodeType::MakeArray, ""),
+                    primitive::Operator::ArrayAt => (CNodeType::ArrayAt, ""),
+                    primitive::Operator::ArrayLength => (CNodeType::ArrayLength, ""),
+
                     // Other operators
+                    primitive::Operator::Print => (CNodeType::Atomic, runtime::names::PRINT_FUNCTION),
                     primitive::Operator::Return => (CNodeType::PrefixOperator, "return"),
 
                     // Anything else should have been converted already
@@ -214,6 +438,143 @@ impl RecurTransform<Node, PassState, Error> for Convert
                                 .to_node();
                             }
 
+                            CNodeType::MakeClosure =>
+                            {
+                                // Closures pair a function with its environment (if it has one)
+
+                                let operand_texts = match get_operand_texts(call)
+                                {
+                                    Ok(texts) => texts,
+                                    Err(error) => return ResultLog::new_error(error),
+                                };
+
+                                let environment_text = match operand_texts.get(1)
+                                {
+                                    Some(text) => text.clone(),
+                                    None => String::from("0"),
+                                };
+
+                                let text = format!(
+                                    "(({}){{ (void*){}, (void*){} }})",
+                                    runtime::names::CLOSURE_TYPE,
+                                    operand_texts[0],
+                                    environment_text
+                                );
+                                *node = CNode::new(
+                                    CNodeType::Atomic,
+                                    text,
+                                    call.get_type(),
+                                    call.get_source(),
+                                )
+                                .to_node();
+                            }
+
+                            CNodeType::ApplyClosure =>
+                            {
+                                // Closures are applied by casting their function to its real type
+                                //  and passing the environment as the first argument
+                                //  ie. ((R (*)(void*, A1, ...))f.apply)(f.environment, a1, ...)
+
+                                let operand_texts = match get_operand_texts(call)
+                                {
+                                    Ok(texts) => texts,
+                                    Err(error) => return ResultLog::new_error(error),
+                                };
+
+                                let operator_type = call.get_operator().get_type();
+                                let (argument_types, return_type) = match &operator_type.get()
+                                {
+                                    Type::Function(function) => (
+                                        function.get_arguments().clone(),
+                                        function.get_return_type(),
+                                    ),
+                                    t =>
+                                    {
+                                        return ResultLog::new_error(Error::Internal(format!(
+                                            "Expected closure application to have a function type: {}",
+                                            t,
+                                        )));
+                                    }
+                                };
+
+                                let mut pointer_text = format!(
+                                    "{} (*)(void*",
+                                    return_type.get().get_c_text()
+                                );
+                                for argument_type in argument_types.iter().skip(1)
+                                {
+                                    pointer_text = format!(
+                                        "{}, {}",
+                                        pointer_text,
+                                        argument_type.get().get_c_text()
+                                    );
+                                }
+                                pointer_text = format!("{})", pointer_text);
+
+                                let closure_text = match &call.get_operands()[0]
+                                {
+                                    Node::CNode(cnode) => cnode.get_text_non_bare(),
+                                    _ => operand_texts[0].clone(),
+                                };
+
+                                let mut text = format!(
+                                    "(({}){}.apply)({}.environment",
+                                    pointer_text, closure_text, closure_text
+                                );
+                                for operand_text in operand_texts.iter().skip(1)
+                                {
+                                    text = format!("{}, {}", text, operand_text);
+                                }
+                                text = format!("{})", text);
+
+                                *node = CNode::new(
+                                    CNodeType::Call,
+                                    text,
+                                    call.get_type(),
+                                    call.get_source(),
+                                )
+                                .to_node();
+                            }
+
+                            array_operator @ (CNodeType::ArrayLiteral
+                            | CNodeType::MakeArray
+                            | CNodeType::ArrayAt
+                            | CNodeType::ArrayLength) =>
+                            {
+                                let text = match convert_array_call(array_operator, call)
+                                {
+                                    Ok(text) => text,
+                                    Err(error) => return ResultLog::new_error(error),
+                                };
+
+                                *node = CNode::new(
+                                    CNodeType::Atomic,
+                                    text,
+                                    call.get_type(),
+                                    call.get_source(),
+                                )
+                                .to_node();
+                            }
+
+                            iterator_operator @ (CNodeType::Range
+                            | CNodeType::MakeIterator
+                            | CNodeType::IteratorNext) =>
+                            {
+                                let text = match convert_iterator_call(iterator_operator, call)
+                                {
+                                    Ok(text) => text,
+                                    Err(error) => return ResultLog::new_error(error),
+                                };
+
+                                *node = CNode::new(
+                                    CNodeType::Call,
+                                    text,
+                                    call.get_type(),
+                                    call.get_source(),
+                                )
+                                .to_node();
+                            }
+
                             _ =>
                             {
                                 let mut text = format!("{}(", call.get_operator());
@@ -348,9 +709,18 @@ impl RecurTransform<Node, PassState, Error> for Convert
                     }
                 };
 
+                // Members are accessed through pointers when the target is a reference
+
+                let operator = match target_cnode.get_type().get().is_reference()
+                {
+                    true => "->",
+                    false => ".",
+                };
+
                 let text = format!(
-                    "{}.{}",
+                    "{}{}{}",
                     target_cnode.get_text_non_bare(),
+                    operator,
                     access.get_property(),
                 );
                 *node = CNode::new(
@@ -378,7 +748,7 @@ impl RecurTransform<Node, PassState, Error> for Convert
 
                 let lhs_text = format!(
                     "{} {}",
-                    &binding_cnode.borrow_type().get_c_text(),
+                    &binding_cnode.get_type().get().get_c_text(),
                     binding.get_name()
                 );
 
@@ -424,16 +794,26 @@ impl RecurTransform<Node, PassState, Error> for Convert
                     text = match cnode.get_node_type()
                     {
                         CNodeType::Nothing => text,
-                        CNodeType::Block
-                        | CNodeType::BlockTransparent
-                        | CNodeType::Conditional
-                        | CNodeType::Function
-                        | CNodeType::Comment => format!("{} {}", text, cnode),
-                        _ => format!("{} {};", text, cnode),
+                        _ => format!("{} {}", text, cnode.get_text_statement()),
                     };
                 }
 
                 text = format!("{} {}", text, end_brace);
+
+                if state.is_root
+                {
+                    // Declare all structs and functions before any definitions
+
+                    let declarations = std::mem::take(&mut self.declarations).join(" ");
+                    let (array_declarations, array_structs, array_functions) =
+                        self.take_array_texts();
+
+                    text = format!(
+                        "{} {} {} {} {}",
+                        array_declarations, declarations, array_structs, text, array_functions
+                    );
+                }
+
                 *node = CNode::new(cnode_type, text, sequence.get_type(), sequence.get_source())
                     .to_node();
             }
@@ -478,11 +858,82 @@ impl RecurTransform<Node, PassState, Error> for Convert
                 .to_node();
             }
 
+            Node::WhileLoop(while_loop) =>
+            {
+                let (prelude_cnode, condition_cnode, body_cnode) = match (
+                    while_loop.get_prelude(),
+                    while_loop.get_condition(),
+                    while_loop.get_body(),
+                )
+                {
+                    (
+                        Node::CNode(prelude_cnode),
+                        Node::CNode(condition_cnode),
+                        Node::CNode(body_cnode),
+                    ) => (prelude_cnode, condition_cnode, body_cnode),
+                    (prelude_node, condition_node, body_node) =>
+                    {
+                        return ResultLog::new_error(Error::Internal(format!(
+                            "Expected loop nodes to be CNodes: {}, {}, {}",
+                            prelude_node, condition_node, body_node,
+                        )));
+                    }
+                };
+
+                let condition_text = condition_cnode.get_text_wrapped();
+                let body_text = body_cnode.get_text_block();
+
+                let text = match prelude_cnode.get_node_type()
+                {
+                    CNodeType::Nothing => format!("while {} {}", condition_text, body_text),
+
+                    // The prelude has to run before the condition is checked on each iteration
+                    _ => format!(
+                        "while (1) {{ {} if (!{}) break; {} }}",
+                        prelude_cnode.get_text_statement(),
+                        condition_text,
+                        body_text
+                    ),
+                };
+
+                *node = CNode::new(
+                    CNodeType::Loop,
+                    text,
+                    while_loop.get_type(),
+                    while_loop.get_source(),
+                )
+                .to_node();
+            }
+
+            Node::Jump(jump) =>
+            {
+                let value_cnode = match jump.get_value()
+                {
+                    Node::CNode(cnode) => cnode,
+                    value_node =>
+                    {
+                        return ResultLog::new_error(Error::Internal(format!(
+                            "Expected jump value to be a CNode: {}",
+                            value_node,
+                        )));
+                    }
+                };
+
+                let text = match value_cnode.get_node_type()
+                {
+                    CNodeType::Nothing => format!("{}", jump.get_mode()),
+                    _ => format!("{} {}", jump.get_mode(), value_cnode),
+                };
+
+                *node = CNode::new(CNodeType::Bare, text, jump.get_type(), jump.get_source())
+                    .to_node();
+            }
+
             Node::Function(function) =>
             {
                 let name_text = format!(
                     "{} {}",
-                    function.get_return_type().borrow().get_c_text(),
+                    function.get_return_type().get().get_c_text(),
                     function.get_name()
                 );
 
@@ -493,7 +944,7 @@ impl RecurTransform<Node, PassState, Error> for Convert
                         "{}{}{} {}",
                         arguments_text,
                         if i == 0 { "" } else { ", " },
-                        argument.get_type().borrow().get_c_text(),
+                        argument.get_type().get().get_c_text(),
                         argument.get_name()
                     );
                 }
@@ -513,6 +964,9 @@ impl RecurTransform<Node, PassState, Error> for Convert
 
                 let body_text = body_cnode.get_text_block();
 
+                self.declarations
+                    .push(format!("{}{};", name_text, arguments_text));
+
                 let text = format!("{}{}{}", name_text, arguments_text, body_text);
                 *node = CNode::new(
                     CNodeType::Function,
@@ -523,6 +977,90 @@ impl RecurTransform<Node, PassState, Error> for Convert
                 .to_node();
             }
 
+            Node::Class(class) =>
+            {
+                // Instance data members become fields of a struct, static data members are globals
+
+                let mut fields_text = String::new();
+                let mut globals_text = String::new();
+
+                for member in class.get_members()
+                {
+                    // Declare the tag enum of an enumerated type before its struct
+
+                    if let Type::Tag(tag) = &member.get_type().get()
+                    {
+                        self.declarations.push(format!(
+                            "typedef enum {{ {} }} {};",
+                            tag.get_values().join(", "),
+                            tag.get_name()
+                        ));
+                    }
+
+                    let member_text = format!(
+                        "{} {};",
+                        member.get_type().get().get_c_text(),
+                        member.get_name()
+                    );
+
+                    match member.get_scope()
+                    {
+                        MemberScope::Instance => fields_text = format!("{} {}", fields_text, member_text),
+                        MemberScope::Static => globals_text = format!("{} {}", globals_text, member_text),
+                    }
+                }
+
+                // Declare the struct type up front, so it can be used before it is defined
+                //  - Variants of enumerated types are stored as their enumerated type
+
+                let variant_of = match &class.get_type().get()
+                {
+                    Type::Class(class_type) => class_type.get_variant_of(),
+                    _ => None,
+                };
+
+                let text = match variant_of
+                {
+                    Some(enumerated_type) =>
+                    {
+                        self.declarations.push(format!(
+                            "typedef {} {};",
+                            enumerated_type.get().get_c_text(),
+                            class.get_name()
+                        ));
+
+                        globals_text
+                    }
+                    None =>
+                    {
+                        self.declarations.push(format!(
+                            "typedef struct {} {};",
+                            class.get_name(),
+                            class.get_name()
+                        ));
+
+                        format!(
+                            "struct {} {{{} }};{}",
+                            class.get_name(),
+                            fields_text,
+                            globals_text
+                        )
+                    }
+                };
+                *node = CNode::new(
+                    CNodeType::Struct,
+                    text,
+                    class.get_type(),
+                    class.get_source(),
+                )
+                .to_node();
+            }
+
+            Node::CNode(_) =>
+            {
+                // Already converted on enter
+            }
+
             _ =>
             {
                 return ResultLog::new_error(Error::Internal(format!("Expected node: {}", node,)));
@@ -533,6 +1071,227 @@ impl RecurTransform<Node, PassState, Error> for Convert
     }
 }
 
+///
+/// Get the C text for a call to an array operator
+///
+/// - Array literals copy their elements from a temporary C array
+/// - Arrays accessed through a reference are dereferenced, since the array functions take arrays
+///     by value (which only copies the length and pointer to the elements)
+///
+fn convert_array_call(operator: CNodeType, call: &Call) -> Result<String, Error>
+{
+    let operand_texts = get_operand_texts(call)?;
+
+    let array_type = match operator
+    {
+        CNodeType::ArrayLiteral | CNodeType::MakeArray => call.get_type(),
+        _ => call.get_operands()[0].get_type(),
+    };
+    let is_reference = array_type.get().is_reference();
+    let target_type = array_type.get().dereference();
+    let array_type = target_type.unwrap_or(array_type);
+
+    let (name, element_text) = match &array_type.get()
+    {
+        Type::Array(array) => (get_array_name(array), array.get_element().get().get_c_text()),
+        t =>
+        {
+            return Err(Error::Internal(format!(
+                "Expected array operator to be used on an array: {}",
+                t
+            )))
+        }
+    };
+
+    // note: Only `at` and `length` have an array operand
+    let get_array_text = || match is_reference
+    {
+        true => format!("(*{})", operand_texts[0]),
+        false => operand_texts[0].clone(),
+    };
+
+    let text = match operator
+    {
+        CNodeType::ArrayLiteral if operand_texts.is_empty() =>
+        {
+            format!("{}__{}(0, NULL)", name, runtime::names::ARRAY_FROM_FUNCTION)
+        }
+        CNodeType::ArrayLiteral => format!(
+            "{}__{}({}, ({}[]){{ {} }})",
+            name,
+            runtime::names::ARRAY_FROM_FUNCTION,
+            operand_texts.len(),
+            element_text,
+            operand_texts.join(", ")
+        ),
+        CNodeType::MakeArray => format!(
+            "{}__{}({}, {})",
+            name,
+            runtime::names::ARRAY_NEW_FUNCTION,
+            operand_texts[0],
+            operand_texts[1]
+        ),
+        CNodeType::ArrayAt => format!(
+            "(*{}__{}({}, {}))",
+            name,
+            runtime::names::ARRAY_AT_FUNCTION,
+            get_array_text(),
+            operand_texts[1]
+        ),
+        _ => format!("((int){}.length)", get_array_text()),
+    };
+
+    Ok(text)
+}
+
+///
+/// Get the text for a call to an iterator operator
+///
+/// - Ranges step by one through their numbers
+/// - Arrays are iterated over with a range of their indices, and each element is taken from the
+///     array with the index the range gives
+///
+fn convert_iterator_call(operator: CNodeType, call: &Call) -> Result<String, Error>
+{
+    let operand_texts = get_operand_texts(call)?;
+
+    let text = match operator
+    {
+        CNodeType::Range => format!(
+            "{}({}, {}, 1)",
+            runtime::names::RANGE_FUNCTION,
+            operand_texts[0],
+            operand_texts[1]
+        ),
+        CNodeType::MakeIterator =>
+        {
+            let (array_name, iterable_text) =
+                get_iterable_text(&call.get_operands()[0], &operand_texts[0])?;
+            match array_name
+            {
+                Some(_) => format!(
+                    "{}(0, {}.length, 1)",
+                    runtime::names::RANGE_FUNCTION,
+                    iterable_text
+                ),
+                None => iterable_text,
+            }
+        }
+        _ =>
+        {
+            let next_text = format!(
+                "{}({})",
+                runtime::names::RANGE_NEXT_FUNCTION,
+                operand_texts[0]
+            );
+            let (array_name, iterable_text) =
+                get_iterable_text(&call.get_operands()[1], &operand_texts[1])?;
+            match array_name
+            {
+                Some(name) => format!(
+                    "(*{}__{}({}, {}))",
+                    name,
+                    runtime::names::ARRAY_AT_FUNCTION,
+                    iterable_text,
+                    next_text
+                ),
+                None => next_text,
+            }
+        }
+    };
+
+    Ok(text)
+}
+
+///
+/// Get the text of an iterable (dereferenced if it's a reference), along with the name of its
+///     generated C struct if it's an array
+///
+fn get_iterable_text(iterable: &Node, text: &String) -> Result<(Option<String>, String), Error>
+{
+    let iterable_type = iterable.get_type();
+    let (target_type, target_text) = match iterable_type.get().dereference()
+    {
+        Some(t) => (t, format!("(*{})", text)),
+        None => (iterable_type, text.clone()),
+    };
+
+    match target_type.get()
+    {
+        Type::Array(array) => Ok((Some(get_array_name(&array)), target_text)),
+        Type::Range => Ok((None, target_text)),
+        t => Err(Error::Internal(format!(
+            "Expected iterable to be a range or array: {}",
+            t
+        ))),
+    }
+}
+
+///
+/// Get the name of the generated C struct for an array type, from the C version of its element type
+///
+/// - Arrays of any length use the same struct
+///
+fn get_array_name(array: &ArrayType) -> String
+{
+    let element_text = array
+        .get_element()
+        .get()
+        .get_c_text()
+        .replace("*", "_ref")
+        .replace(" ", "_");
+
+    format!("{}__{}", runtime::names::ARRAY_TYPE, element_text)
+}
+
+///
+/// Escape the characters of a string that can't appear directly in a C string literal
+///
+fn escape_c_string(value: &String) -> String
+{
+    let mut result = String::with_capacity(value.len());
+    for byte in value.bytes()
+    {
+        match byte
+        {
+            b'"' => result.push_str("\\\""),
+            b'\\' => result.push_str("\\\\"),
+            b'\n' => result.push_str("\\n"),
+            b'\t' => result.push_str("\\t"),
+            b'\r' => result.push_str("\\r"),
+            b' '..=b'~' => result.push(byte as char),
+
+            // Octal escapes always use 3 digits, so they can't run into the next character
+            _ => result.push_str(&format!("\\{:03o}", byte)),
+        }
+    }
+    result
+}
+
+///
+/// Get the text of each operand of a call, which should all be CNodes
+///
+fn get_operand_texts(call: &Call) -> Result<Vec<String>, Error>
+{
+    let mut texts = Vec::new();
+    for operand in call.get_operands()
+    {
+        match operand
+        {
+            Node::CNode(cnode) => texts.push(format!("{}", cnode)),
+            operand =>
+            {
+                return Err(Error::Internal(format!(
+                    "Expected call operand to be a CNode: {}",
+                    operand,
+                )));
+            }
+        }
+    }
+
+    Ok(texts)
+}
+
 impl CNode
 {
     ///
@@ -559,6 +1318,24 @@ impl CNode
         }
     }
 
+    ///
+    /// Get the text of a CNode as a statement, terminated with a semicolon if it needs one
+    ///
+    pub fn get_text_statement(&self) -> String
+    {
+        match self.get_node_type()
+        {
+            CNodeType::Block
+            | CNodeType::BlockTransparent
+            | CNodeType::Conditional
+            | CNodeType::Loop
+            | CNodeType::Function
+            | CNodeType::Struct
+            | CNodeType::Comment => self.get_text().clone(),
+            _ => format!("{};", self.get_text()),
+        }
+    }
+
     ///
     /// Wrap a CNode's text in braces if it isn't already a block
     ///
@@ -579,6 +1356,40 @@ impl Type
     ///
     pub fn get_c_text(&self) -> String
     {
-        format!("{}", self)
+        match self
+        {
+            Type::Reference(reference) =>
+            {
+                format!("{}*", reference.get_target().get().get_c_text())
+            }
+            Type::Instance(instance) => match instance.get_class()
+            {
+                // Use the (flattened) name of the class, rather than the name it was referred to by
+                Some(class) => class.get_name().clone(),
+                None => instance.get_name().clone(),
+            },
+            Type::Class(class) => format!("struct {}", class.get_name()),
+            Type::Tag(tag) => tag.get_name().clone(),
+            Type::Function(_) => String::from(runtime::names::CLOSURE_TYPE),
+            Type::String => String::from(runtime::names::STRING_TYPE),
+            Type::Range => String::from(runtime::names::RANGE_TYPE),
+            Type::Array(array) => get_array_name(array),
+            Type::Float => String::from("double"),
+            Type::Union(union) =>
+            {
+                let mut fields_text = String::new();
+                for (name, field_type) in union.get_fields()
+                {
+                    fields_text = format!(
+                        "{} {} {};",
+                        fields_text,
+                        field_type.get().get_c_text(),
+                        name
+                    );
+                }
+                format!("union {{{} }}", fields_text)
+            }
+            _ => format!("{}", self),
+        }
     }
 }
@@ -30,6 +30,40 @@ impl ConvertNames
 
         result
     }
+
+    ///
+    /// Convert the names in a type that are named in their C definitions (ie. the tags and unions of
+    ///     enumerated types)
+    ///
+    fn convert_type(&self, t: TypeId) -> TypeId
+    {
+        match t.get()
+        {
+            Type::Tag(tag) =>
+            {
+                let values = tag
+                    .get_values()
+                    .iter()
+                    .map(|value| self.convert_name(value.clone()))
+                    .collect();
+
+                TagType::new(self.convert_name(tag.get_name().clone()), values)
+                    .to_type()
+                    .intern()
+            }
+            Type::Union(union) =>
+            {
+                let fields = union
+                    .get_fields()
+                    .iter()
+                    .map(|(name, field_type)| (self.convert_name(name.clone()), *field_type))
+                    .collect();
+
+                UnionType::new(fields).to_type().intern()
+            }
+            t => t.map_inner(&mut |inner| self.convert_type(inner)).intern(),
+        }
+    }
 }
 
 pub struct PassState {}
@@ -51,6 +85,10 @@ impl RecurTransform<Node, PassState, Error> for ConvertNames
 
     fn exit(&mut self, node: &mut Node, _state: &mut PassState) -> ResultLog<(), Error>
     {
+        // Tags and unions are converted wherever they appear, not just in their class
+
+        node.map_types(&mut |t| self.convert_type(t));
+
         match node
         {
             Node::Variable(variable) =>
@@ -73,6 +111,23 @@ impl RecurTransform<Node, PassState, Error> for ConvertNames
                 let name = function.get_name_mut();
                 *name = self.convert_name(std::mem::take(name));
             }
+            Node::Class(class) =>
+            {
+                let name = class.get_name_mut();
+                *name = self.convert_name(std::mem::take(name));
+
+                if let Type::Class(class_type) = class.get_type().get()
+                {
+                    let type_name = self.convert_name(class_type.get_name());
+                    class_type.set_name(type_name);
+                }
+
+                for member in class.get_members_mut()
+                {
+                    let name = member.get_name_mut();
+                    *name = self.convert_name(std::mem::take(name));
+                }
+            }
             _ => (),
         }
         ResultLog::Ok(())
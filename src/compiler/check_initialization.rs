@@ -0,0 +1,378 @@
+use super::common::*;
+
+///
+/// ## Check Initialization Pass
+///
+/// - Verifies that all instance data members of a created instance are initialized before the
+///     instance escapes (is passed, referenced, returned, or has methods called on it)
+///     - ex. `let new = (create Foo)` must be followed by `new.a <- ...` for each member of Foo
+///     - Members assigned in only one branch of a conditional are not considered initialized
+/// - Created instances that aren't immediately bound must not have any instance data members
+///
+pub struct CheckInitialization {}
+
+impl CheckInitialization
+{
+    pub fn new() -> CheckInitialization
+    {
+        CheckInitialization {}
+    }
+}
+
+///
+/// Pass state for CheckInitialization
+///
+/// - Track if the current node is the value of a binding
+///
+pub struct PassState
+{
+    pub is_bound: bool,
+}
+
+impl PassState
+{
+    pub fn new(is_bound: bool) -> PassState
+    {
+        PassState { is_bound }
+    }
+}
+
+impl RecurTransform<Node, PassState, Error> for CheckInitialization
+{
+    fn get_root_state(&mut self, _node: &Node) -> PassState
+    {
+        PassState::new(false)
+    }
+
+    fn get_child_states(&mut self, _state: &PassState, node: &Node) -> Vec<ChildState<PassState>>
+    {
+        match node
+        {
+            Node::Binding(_) => vec![ChildState::New(PassState::new(true))],
+            _ => vec![ChildState::New(PassState::new(false))],
+        }
+    }
+
+    fn exit(&mut self, node: &mut Node, state: &mut PassState) -> ResultLog<(), Error>
+    {
+        match node
+        {
+            Node::Call(_) if !state.is_bound =>
+            {
+                // Unbound instances can never be initialized
+
+                if let Some(members) = get_created_members(node)
+                {
+                    if !members.is_empty()
+                    {
+                        let error = Error::UninitializedMembers(
+                            format!("{}", node.get_type().get()),
+                            members,
+                            node.get_source(),
+                        );
+                        return ResultLog::new_error(error);
+                    }
+                }
+            }
+            Node::Sequence(sequence) =>
+            {
+                // Check the nodes following each created instance binding
+
+                let nodes = sequence.get_nodes();
+                for (i, node) in nodes.iter().enumerate()
+                {
+                    let binding = match node
+                    {
+                        Node::Binding(binding) => binding,
+                        _ => continue,
+                    };
+
+                    let members = match get_created_members(binding.get_binding())
+                    {
+                        Some(members) => members,
+                        None => continue,
+                    };
+
+                    let mut tracker = Tracker {
+                        name:          binding.get_name(),
+                        members:       &members,
+                        uninitialized: members.clone(),
+                    };
+
+                    for following_node in &nodes[i + 1..]
+                    {
+                        match tracker.scan(following_node)
+                        {
+                            Ok(Scan::Continue) => (),
+                            Ok(Scan::Initialized) | Ok(Scan::Shadowed) => break,
+                            Err(error) => return ResultLog::new_error(error),
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        ResultLog::Ok(())
+    }
+}
+
+///
+/// Get the names of all instance data members if a node is a create operator application
+///
+fn get_created_members(node: &Node) -> Option<Vec<String>>
+{
+    match node
+    {
+        Node::Call(call) => match call.get_operator()
+        {
+            Node::PrimitiveOperator(operator) => match operator.get_value()
+            {
+                Operator::Create | Operator::HeapAllocate => (),
+                _ => return None,
+            },
+            _ => return None,
+        },
+        _ => return None,
+    }
+
+    // Heap allocations create a reference to an instance
+
+    let created_type = node.get_type();
+    let instance_type = match created_type.get().dereference()
+    {
+        Some(t) => t,
+        None => created_type,
+    };
+
+    let class = match instance_type.get()
+    {
+        Type::Instance(instance) => instance.get_class()?,
+        _ => return None,
+    };
+
+    let members = class
+        .get_members()
+        .iter()
+        .filter(|member| member.get_scope() == MemberScope::Instance)
+        .map(|member| member.get_name().clone())
+        .collect();
+
+    Some(members)
+}
+
+///
+/// Result of scanning a node for uses of a created instance
+///
+enum Scan
+{
+    Continue,
+    Initialized,
+    Shadowed,
+}
+
+///
+/// Track which members of a bound instance have not been initialized
+///
+struct Tracker<'a>
+{
+    name:          &'a String,
+    members:       &'a Vec<String>,
+    uninitialized: Vec<String>,
+}
+
+impl<'a> Tracker<'a>
+{
+    ///
+    /// Walk a node in evaluation order, marking assigned members as initialized and erroring if
+    ///     the instance is used before all members are initialized
+    ///
+    fn scan(&mut self, node: &Node) -> Result<Scan, Error>
+    {
+        if self.uninitialized.is_empty()
+        {
+            return Ok(Scan::Initialized);
+        }
+
+        match node
+        {
+            Node::Assign(assign) =>
+            {
+                let result = self.scan(assign.get_rhs())?;
+                if !matches_continue(&result)
+                {
+                    return Ok(result);
+                }
+
+                match self.get_member_access(assign.get_lhs())
+                {
+                    Some(member_name) =>
+                    {
+                        // Assigning to a member initializes it
+
+                        self.uninitialized.retain(|name| name != member_name);
+                        Ok(self.get_status())
+                    }
+                    None => self.scan(assign.get_lhs()),
+                }
+            }
+            Node::Access(access) => match self.get_member_access(node)
+            {
+                Some(member_name) if self.uninitialized.contains(member_name) =>
+                {
+                    Err(Error::UninitializedMemberRead(
+                        self.name.clone(),
+                        member_name.clone(),
+                        access.get_source(),
+                    ))
+                }
+                Some(_) => Ok(Scan::Continue),
+                None => self.scan(access.get_target()),
+            },
+            Node::Variable(variable) if variable.get_name() == self.name =>
+            {
+                // Any other use of the instance lets it escape
+
+                Err(Error::UninitializedMembers(
+                    self.name.clone(),
+                    self.uninitialized.clone(),
+                    variable.get_source(),
+                ))
+            }
+            Node::Binding(binding) =>
+            {
+                let result = self.scan(binding.get_binding())?;
+                if !matches_continue(&result)
+                {
+                    return Ok(result);
+                }
+
+                match binding.get_name() == self.name
+                {
+                    true => Ok(Scan::Shadowed),
+                    false => Ok(Scan::Continue),
+                }
+            }
+            Node::Sequence(sequence) =>
+            {
+                for child in sequence.get_nodes()
+                {
+                    match self.scan(child)?
+                    {
+                        Scan::Continue => (),
+                        Scan::Initialized => return Ok(Scan::Initialized),
+                        Scan::Shadowed => match sequence.get_mode()
+                        {
+                            // Shadowing only lasts until the end of a scope
+                            SequenceMode::Scope => return Ok(Scan::Continue),
+                            SequenceMode::Transparent => return Ok(Scan::Shadowed),
+                        },
+                    }
+                }
+
+                Ok(Scan::Continue)
+            }
+            Node::Conditional(conditional) =>
+            {
+                let result = self.scan(conditional.get_condition())?;
+                if !matches_continue(&result)
+                {
+                    return Ok(result);
+                }
+
+                // Only members initialized in both branches are initialized after the conditional
+
+                let original = self.uninitialized.clone();
+
+                self.scan(conditional.get_then())?;
+                let then_uninitialized = std::mem::replace(&mut self.uninitialized, original);
+
+                self.scan(conditional.get_else())?;
+
+                for name in then_uninitialized
+                {
+                    if !self.uninitialized.contains(&name)
+                    {
+                        self.uninitialized.push(name);
+                    }
+                }
+
+                Ok(self.get_status())
+            }
+            Node::WhileLoop(while_loop) =>
+            {
+                let result = self.scan(while_loop.get_condition())?;
+                if !matches_continue(&result)
+                {
+                    return Ok(result);
+                }
+
+                // The body might never run, so members it initializes are still uninitialized
+                //  after the loop
+
+                let original = self.uninitialized.clone();
+                self.scan(while_loop.get_body())?;
+                self.uninitialized = original;
+
+                Ok(Scan::Continue)
+            }
+            Node::Function(_) | Node::Class(_) =>
+            {
+                // Definitions can't refer to local bindings
+                Ok(Scan::Continue)
+            }
+            node =>
+            {
+                for child in node.get_children()
+                {
+                    let result = self.scan(child)?;
+                    if !matches_continue(&result)
+                    {
+                        return Ok(result);
+                    }
+                }
+
+                Ok(Scan::Continue)
+            }
+        }
+    }
+
+    ///
+    /// Get the name of the data member a node accesses on the tracked instance, if any
+    ///
+    fn get_member_access<'b>(&self, node: &'b Node) -> Option<&'b String>
+    {
+        match node
+        {
+            Node::Access(access) => match access.get_target()
+            {
+                Node::Variable(variable)
+                    if variable.get_name() == self.name
+                        && self.members.contains(access.get_property()) =>
+                {
+                    Some(access.get_property())
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn get_status(&self) -> Scan
+    {
+        match self.uninitialized.is_empty()
+        {
+            true => Scan::Initialized,
+            false => Scan::Continue,
+        }
+    }
+}
+
+fn matches_continue(scan: &Scan) -> bool
+{
+    match scan
+    {
+        Scan::Continue => true,
+        _ => false,
+    }
+}
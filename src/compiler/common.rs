@@ -1,5 +1,6 @@
 pub use crate::errors::compile_error::*;
 pub use crate::language::node::*;
+pub use crate::language::{runtime, symbols, MemberScope, Visibility};
 pub use crate::utilities::*;
 
 use std::collections::HashMap;
@@ -146,6 +147,25 @@ impl<TBinding: Clone> BindingState<TBinding>
             }));
     }
 
+    ///
+    /// Add definition bindings from `Class` nodes, using the given `Class` to `TBinding` map
+    ///
+    pub fn add_definitions_from_classes<TGetBinding>(
+        &mut self,
+        nodes: &Vec<Node>,
+        get_binding: TGetBinding,
+    ) where
+        TGetBinding: Fn(&Class) -> TBinding,
+    {
+        self.definitions
+            .local
+            .extend(nodes.iter().filter_map(|node| match node
+            {
+                Node::Class(class) => Some((class.get_name().clone(), get_binding(class))),
+                _ => None,
+            }));
+    }
+
     ///
     /// Add bindings from a function's arguments, using the given `Argument` to `TBinding` map
     ///
@@ -227,6 +247,7 @@ impl<TBinding: Clone> BindingState<TBinding>
 /// ## Wrap Pass
 ///
 /// - Wraps the result of a node tree with the given wrap function
+/// - Results that jump away (ie. `return`) are left alone, since they never produce a value
 ///
 pub struct WrapPass<TWrap>
 where
@@ -333,7 +354,7 @@ where
 
     fn exit(&mut self, node: &mut Node, state: &mut WrapPassState) -> ResultLog<(), Error>
     {
-        if !node.is_complex() && state.is_result_context
+        if !node.is_complex() && state.is_result_context && !node.get_type().get().is_never()
         {
             // We only want to wrap non-complex nodes in result contexts (otherwise we'd want to
             //  recur into the node's children)
@@ -0,0 +1,648 @@
+use super::common::*;
+
+///
+/// ## Convert Closures Pass
+///
+/// - Lifts anonymous functions (ie. `fn [x int] -> int { (x + y) }`) to named functions that take
+///     an environment holding the bindings they capture as their first argument
+///     - ex. the closure above becomes `fn _closure_1 [_environment (mut _closure_1/Environment)]
+///         [x int] -> int { (x + _environment.y) }`
+///     - Captured bindings are copied into a heap-allocated environment type when the closure is
+///         created, so assigning to them inside the closure only changes the closure's copy
+///     - Closures without any captured bindings take an empty `(ref void)` environment
+/// - Closure values are created with `(make-closure F environment)`, which pairs the lifted
+///     function with its environment
+/// - Named functions used as values (ie. `let f = Foo`) are wrapped in a closure that calls them
+/// - Calls to function values (ie. `(f 1)` where f is an argument or binding) are replaced by
+///     `(apply-closure f 1)`, which passes the environment to the lifted function
+/// - Lifted functions and environment types are placed in the innermost scope around the closure,
+///     leaving FlattenNames and FlattenDefinitions to move them to the top level
+///
+pub struct ConvertClosures
+{
+    closure_names:     TempNameGenerator,
+    environment_names: TempNameGenerator,
+    value_names:       TempNameGenerator,
+    lifted:            Vec<Vec<Node>>,
+}
+
+impl ConvertClosures
+{
+    pub fn new() -> ConvertClosures
+    {
+        ConvertClosures {
+            closure_names:     TempNameGenerator::new("closure"),
+            environment_names: TempNameGenerator::new("environment"),
+            value_names:       TempNameGenerator::new("closure_value"),
+            lifted:            Vec::new(),
+        }
+    }
+}
+
+///
+/// What a name refers to at some point in the tree
+///
+#[derive(Clone, PartialEq)]
+pub enum Symbol
+{
+    Binding,
+    Definition,
+}
+
+///
+/// Pass state for ConvertClosures
+///
+/// - Track which names are bindings (which closures need to capture) and which are definitions
+/// - Track if a node is the operator of a call (named functions aren't values when they're called)
+///
+pub struct PassState
+{
+    symbols:     BindingState<Symbol>,
+    is_operator: bool,
+}
+
+impl PassState
+{
+    pub fn new(symbols: BindingState<Symbol>, is_operator: bool) -> PassState
+    {
+        PassState {
+            symbols,
+            is_operator,
+        }
+    }
+}
+
+const ENVIRONMENT_ARGUMENT: &str = "_environment";
+
+impl RecurTransform<Node, PassState, Error> for ConvertClosures
+{
+    fn get_root_state(&mut self, _node: &Node) -> PassState
+    {
+        PassState::new(BindingState::root(), false)
+    }
+
+    fn get_child_states(&mut self, state: &PassState, node: &Node) -> Vec<ChildState<PassState>>
+    {
+        match node
+        {
+            Node::Sequence(sequence) if !sequence.is_transparent() =>
+            {
+                let mut symbols = BindingState::empty(&state.symbols, true, true);
+                symbols
+                    .add_definitions_from_functions(sequence.get_nodes(), |_| Symbol::Definition);
+                symbols.add_definitions_from_classes(sequence.get_nodes(), |_| Symbol::Definition);
+
+                vec![ChildState::New(PassState::new(symbols, false))]
+            }
+            Node::Function(function) =>
+            {
+                // Functions can't use bindings from the parent scope
+
+                let mut symbols = BindingState::empty(&state.symbols, true, false);
+                symbols.add_bindings_from_arguments(function.get_arguments(), |_| Symbol::Binding);
+
+                vec![ChildState::New(PassState::new(symbols, false))]
+            }
+            Node::Closure(closure) =>
+            {
+                let mut symbols = BindingState::empty(&state.symbols, true, true);
+                symbols.add_bindings_from_arguments(closure.get_arguments(), |_| Symbol::Binding);
+
+                vec![ChildState::New(PassState::new(symbols, false))]
+            }
+            Node::Call(call) =>
+            {
+                let operator_symbols = BindingState::empty(&state.symbols, true, true);
+                let mut child_states =
+                    vec![ChildState::New(PassState::new(operator_symbols, true))];
+
+                for _ in call.get_operands()
+                {
+                    let operand_symbols = BindingState::empty(&state.symbols, true, true);
+                    child_states.push(ChildState::New(PassState::new(operand_symbols, false)));
+                }
+
+                child_states
+            }
+            _ if state.is_operator =>
+            {
+                let symbols = BindingState::empty(&state.symbols, true, true);
+                vec![ChildState::New(PassState::new(symbols, false))]
+            }
+            _ => vec![ChildState::Inherit],
+        }
+    }
+
+    fn enter(&mut self, node: &mut Node, _state: &mut PassState) -> ResultLog<(), Error>
+    {
+        match node
+        {
+            Node::Sequence(sequence) if !sequence.is_transparent() =>
+            {
+                // Collect definitions lifted from closures inside this scope
+
+                self.lifted.push(Vec::new());
+            }
+            _ => (),
+        }
+
+        ResultLog::Ok(())
+    }
+
+    fn exit(&mut self, node: &mut Node, state: &mut PassState) -> ResultLog<(), Error>
+    {
+        match node
+        {
+            Node::Binding(binding) =>
+            {
+                state
+                    .symbols
+                    .add_binding(binding.get_name(), Symbol::Binding);
+            }
+            Node::Variable(variable) =>
+            {
+                // Named functions used as values are wrapped in a closure
+
+                let is_function = match &variable.get_type().get()
+                {
+                    Type::Function(_) => true,
+                    _ => false,
+                };
+                let is_definition =
+                    state.symbols.lookup(variable.get_name()) == Some(Symbol::Definition);
+
+                if is_function && is_definition && !state.is_operator
+                {
+                    return self.wrap_function(node);
+                }
+            }
+            Node::Closure(_) =>
+            {
+                return self.convert_closure(node, state);
+            }
+            Node::Call(call) =>
+            {
+                if is_function_value(call.get_operator(), state)
+                {
+                    self.apply_closure(node);
+                }
+            }
+            Node::Sequence(sequence) if !sequence.is_transparent() =>
+            {
+                // Lifted definitions don't change the result of the sequence
+
+                if let Some(mut lifted) = self.lifted.pop()
+                {
+                    sequence.get_nodes_mut().append(&mut lifted);
+                }
+            }
+            _ => (),
+        }
+
+        ResultLog::Ok(())
+    }
+}
+
+impl ConvertClosures
+{
+    ///
+    /// Lift a closure to a named function, replacing it with the creation of its environment and
+    ///     closure value
+    ///
+    fn convert_closure(&mut self, node: &mut Node, state: &PassState) -> ResultLog<(), Error>
+    {
+        let closure = match node
+        {
+            Node::Closure(closure) => closure,
+            _ => return ResultLog::Ok(()),
+        };
+
+        let source = closure.get_source();
+        let closure_type = closure.get_type();
+        let function_name = self.closure_names.next();
+
+        // Replace captured bindings in the body with accesses to the environment
+        //  note: the environment type is only known once all the captured bindings are, so the
+        //      accesses are given a placeholder type that is replaced afterwards
+
+        let placeholder_type = TypeVariable::new().to_type().intern();
+        let mut captures = Vec::new();
+
+        let mut body = closure.get_body_mut().extract_temp();
+        capture_bindings(
+            &mut body,
+            &mut Vec::new(),
+            &state.symbols,
+            placeholder_type,
+            &mut captures,
+        );
+
+        let mut definitions = Vec::new();
+        let mut environment = Vec::new();
+
+        let environment_type = if captures.is_empty()
+        {
+            Type::Void.into_reference(ReferenceMode::Immutable).intern()
+        }
+        else
+        {
+            let class_name = format!("{}/Environment", function_name);
+            let members = captures
+                .iter()
+                .map(|(name, t)| {
+                    Member::from(
+                        name.clone(),
+                        *t,
+                        MemberScope::Instance,
+                        Visibility::Public,
+                        Visibility::Public,
+                        source.clone(),
+                    )
+                })
+                .collect();
+
+            let class = Class::new(
+                class_name.clone(),
+                Vec::new(),
+                members,
+                Vec::new(),
+                Vec::new(),
+                source.clone(),
+            );
+
+            let environment_type = class
+                .get_instance_type()
+                .get()
+                .into_reference(ReferenceMode::Mutable)
+                .intern();
+
+            // let _environment_N = (heap-alloc _closure_N/Environment)
+
+            let mut allocate_operator =
+                PrimitiveOperator::new(Operator::HeapAllocate, source.clone());
+            allocate_operator.set_type(
+                FunctionType::from(vec![class.get_type()], environment_type)
+                    .to_type()
+                    .intern(),
+            );
+
+            let class_variable = Variable::new_typed(class_name, class.get_type(), source.clone());
+
+            let mut allocate = Call::new(
+                allocate_operator.to_node(),
+                vec![class_variable.to_node()],
+                source.clone(),
+            );
+            allocate.set_type(environment_type);
+
+            let environment_name = self.environment_names.next();
+            environment.push(
+                Binding::new(environment_name.clone(), allocate.to_node(), source.clone())
+                    .to_node(),
+            );
+
+            // _environment_N.x <- x
+
+            for (name, t) in captures.iter()
+            {
+                let environment_variable =
+                    Variable::new_typed(environment_name.clone(), environment_type, source.clone());
+
+                let mut access =
+                    Access::new(environment_variable.to_node(), name.clone(), source.clone());
+                access.set_type(*t);
+
+                let value = Variable::new_typed(name.clone(), *t, source.clone());
+                environment
+                    .push(Assign::new(access.to_node(), value.to_node(), source.clone()).to_node());
+            }
+
+            environment.push(
+                Variable::new_typed(environment_name, environment_type, source.clone()).to_node(),
+            );
+
+            definitions.push(class.to_node());
+            environment_type
+        };
+
+        body.map_all_types(&mut |t| match t == placeholder_type
+        {
+            true => environment_type,
+            false => t,
+        });
+
+        // Lift the closure to a function taking its environment as the first argument
+
+        let mut arguments = vec![Argument::from(
+            String::from(ENVIRONMENT_ARGUMENT),
+            environment_type,
+        )];
+        arguments.extend(closure.get_arguments().iter().cloned());
+
+        let function = Function::from(
+            function_name,
+            arguments,
+            closure.get_return_type(),
+            body,
+            source.clone(),
+        );
+
+        // Create the environment (if there is one) and closure value in place of the closure
+
+        let environment_variable = environment.pop();
+        let make_closure = make_closure(&function, environment_variable, closure_type);
+        definitions.push(function.to_node());
+
+        let result = match environment.is_empty()
+        {
+            true => make_closure,
+            false =>
+            {
+                environment.push(make_closure);
+
+                let mut sequence = Sequence::new(SequenceMode::Scope, environment, source);
+                sequence.set_type(closure_type);
+                sequence.to_node()
+            }
+        };
+
+        *node = result;
+        self.lift(definitions)
+    }
+
+    ///
+    /// Wrap a variable naming a function in a closure that calls the function
+    ///
+    fn wrap_function(&mut self, node: &mut Node) -> ResultLog<(), Error>
+    {
+        let variable = match node
+        {
+            Node::Variable(variable) => variable,
+            _ => return ResultLog::Ok(()),
+        };
+
+        let source = variable.get_source();
+        let function_type = variable.get_type();
+
+        let (argument_types, return_type) = match &function_type.get()
+        {
+            Type::Function(function_type) => (
+                function_type.get_arguments().clone(),
+                function_type.get_return_type(),
+            ),
+            _ => return ResultLog::Ok(()),
+        };
+
+        // fn _closure_N [_environment (ref void)] [_1 T1] ... -> Tr { (F _1 ...) }
+
+        let mut arguments = vec![Argument::new(
+            String::from(ENVIRONMENT_ARGUMENT),
+            Type::Void.into_reference(ReferenceMode::Immutable),
+        )];
+        let mut operands = Vec::new();
+
+        for (i, t) in argument_types.iter().enumerate()
+        {
+            let name = format!("_{}", i + 1);
+            arguments.push(Argument::from(name.clone(), *t));
+            operands.push(Variable::new_typed(name, *t, source.clone()).to_node());
+        }
+
+        let mut call = Call::new(variable.clone().to_node(), operands, source.clone());
+        call.set_type(return_type);
+
+        let function = Function::from(
+            self.closure_names.next(),
+            arguments,
+            return_type,
+            call.to_node(),
+            source,
+        );
+
+        *node = make_closure(&function, None, function_type);
+        self.lift(vec![function.to_node()])
+    }
+
+    ///
+    /// Replace a call to a function value with an application of the closure
+    ///     (f x y) => (apply-closure f x y)
+    ///
+    /// - Closures that aren't named are bound first, since they are used twice when applied
+    ///     (g x) y => { let _closure_value_N = (g x) (apply-closure _closure_value_N y) }
+    ///
+    fn apply_closure(&mut self, node: &mut Node)
+    {
+        let call = match node
+        {
+            Node::Call(call) => call,
+            _ => return,
+        };
+
+        let source = call.get_source();
+        let closure_type = call.get_operator().get_type();
+
+        let (argument_types, return_type) = match &closure_type.get()
+        {
+            Type::Function(function_type) => (
+                function_type.get_arguments().clone(),
+                function_type.get_return_type(),
+            ),
+            _ => return,
+        };
+
+        let mut operator_arguments = vec![closure_type];
+        operator_arguments.extend(argument_types);
+
+        let mut operator = PrimitiveOperator::new(Operator::ApplyClosure, source.clone());
+        operator.set_type(
+            FunctionType::from(operator_arguments, return_type)
+                .to_type()
+                .intern(),
+        );
+
+        let closure = call.get_operator_mut().extract(operator.to_node());
+
+        let binding = match closure
+        {
+            Node::Variable(_) =>
+            {
+                call.get_operands_mut().insert(0, closure);
+                None
+            }
+            closure =>
+            {
+                let value_name = self.value_names.next();
+                let value = Variable::new_typed(value_name.clone(), closure_type, source.clone());
+
+                call.get_operands_mut().insert(0, value.to_node());
+                Some(Binding::new(value_name, closure, source.clone()).to_node())
+            }
+        };
+
+        if let Some(binding) = binding
+        {
+            let call_type = call.get_type();
+            let apply = node.extract_temp();
+
+            let mut sequence = Sequence::new(SequenceMode::Scope, vec![binding, apply], source);
+            sequence.set_type(call_type);
+
+            *node = sequence.to_node();
+        }
+    }
+
+    ///
+    /// Add lifted definitions to the innermost scope
+    ///
+    fn lift(&mut self, mut definitions: Vec<Node>) -> ResultLog<(), Error>
+    {
+        match self.lifted.last_mut()
+        {
+            Some(lifted) =>
+            {
+                lifted.append(&mut definitions);
+                ResultLog::Ok(())
+            }
+            None => ResultLog::new_error(Error::Internal(format!(
+                "Expected a scope to lift closures to"
+            ))),
+        }
+    }
+}
+
+///
+/// Replace bindings from outside a closure body with accesses to the closure's environment,
+///     keeping track of the name and type of each binding that is captured
+///
+fn capture_bindings(
+    node: &mut Node,
+    bound: &mut Vec<String>,
+    symbols: &BindingState<Symbol>,
+    environment_type: TypeId,
+    captures: &mut Vec<(String, TypeId)>,
+)
+{
+    match node
+    {
+        Node::Variable(variable) =>
+        {
+            let name = variable.get_name();
+            let is_captured =
+                !bound.contains(name) && symbols.lookup(name) == Some(Symbol::Binding);
+
+            if !is_captured
+            {
+                return;
+            }
+
+            let t = variable.get_type();
+            let source = variable.get_source();
+
+            if !captures.iter().any(|(captured, _)| captured == name)
+            {
+                captures.push((name.clone(), t));
+            }
+
+            let environment_variable = Variable::new_typed(
+                String::from(ENVIRONMENT_ARGUMENT),
+                environment_type,
+                source.clone(),
+            );
+
+            let mut access = Access::new(environment_variable.to_node(), name.clone(), source);
+            access.set_type(t);
+
+            *node = access.to_node();
+        }
+        Node::Binding(binding) =>
+        {
+            capture_bindings(
+                binding.get_binding_mut(),
+                bound,
+                symbols,
+                environment_type,
+                captures,
+            );
+            bound.push(binding.get_name().clone());
+        }
+        Node::Sequence(sequence) =>
+        {
+            // Bindings are only visible until the end of their sequence
+
+            let bound_count = bound.len();
+            for child in sequence.get_nodes_mut()
+            {
+                capture_bindings(child, bound, symbols, environment_type, captures);
+            }
+
+            if !sequence.is_transparent()
+            {
+                bound.truncate(bound_count);
+            }
+        }
+        Node::Function(_) | Node::Class(_) =>
+        {
+            // Nested definitions can't use bindings from the closure body
+        }
+        _ =>
+        {
+            for child in node.get_children_mut()
+            {
+                capture_bindings(child, bound, symbols, environment_type, captures);
+            }
+        }
+    }
+}
+
+///
+/// Create a closure value from a lifted function and its environment
+///
+fn make_closure(function: &Function, environment: Option<Node>, closure_type: TypeId) -> Node
+{
+    let source = function.get_source();
+
+    let mut operands = vec![Variable::new_typed(
+        function.get_name().clone(),
+        function.get_type(),
+        source.clone(),
+    )
+    .to_node()];
+    operands.extend(environment);
+
+    let operand_types = operands.iter().map(|operand| operand.get_type()).collect();
+
+    let mut operator = PrimitiveOperator::new(Operator::MakeClosure, source.clone());
+    operator.set_type(
+        FunctionType::from(operand_types, closure_type)
+            .to_type()
+            .intern(),
+    );
+
+    let mut call = Call::new(operator.to_node(), operands, source);
+    call.set_type(closure_type);
+    call.to_node()
+}
+
+///
+/// Check if the operator of a call is a function value rather than a named function, method, or
+///     primitive operator
+///
+fn is_function_value(operator: &Node, state: &PassState) -> bool
+{
+    match operator
+    {
+        Node::PrimitiveOperator(_) => false,
+        Node::Variable(variable) => match state.symbols.lookup(variable.get_name())
+        {
+            Some(symbol) => symbol == Symbol::Binding,
+            None => false,
+        },
+        Node::Access(access) => match &access.get_target().get_type().get()
+        {
+            // Static methods are named functions
+            Type::Class(_) => false,
+            _ => true,
+        },
+        _ => true,
+    }
+}
@@ -0,0 +1,692 @@
+use super::common::*;
+
+///
+/// ## Explicate Enumerations Pass
+///
+/// - Lowers enumerated types (ie. `type Expression { is Enumerated }`) and their variants (ie.
+///     `type Number { is Expression {...} }`) to tagged unions
+///     - Instance data members of each variant are moved into a data type
+///         (ie. `Data/Expression/Number`)
+///     - The enumerated type gets a tag member `__K` naming the variant it holds, and a union member
+///         `__V` with a field for each variant's data type
+///     - Variants share the layout of their enumerated type, so they can be used in its place
+/// - Data members of variants are accessed through their field of the union
+///     - ex. `n.value` to `n.__V.Number.value`
+/// - Creating a variant creates its enumerated type and sets the tag
+///     - ex. `(create Number)` to `{ let x = (create Expression) x.__K <- Type/Expression/Number x }`
+/// - Instance methods of the enumerated type dispatch on the tag to the variants that implement
+///     them, and use their original body for any other variant
+/// - Variants must be defined in the same sequence as their enumerated type
+///
+pub struct ExplicateEnumerations
+{
+    temp_names: TempNameGenerator,
+}
+
+impl ExplicateEnumerations
+{
+    pub fn new() -> ExplicateEnumerations
+    {
+        ExplicateEnumerations {
+            temp_names: TempNameGenerator::new("variant"),
+        }
+    }
+}
+
+pub struct PassState {}
+
+impl RecurTransform<Node, PassState, Error> for ExplicateEnumerations
+{
+    fn get_root_state(&mut self, _node: &Node) -> PassState
+    {
+        PassState {}
+    }
+
+    fn enter(&mut self, node: &mut Node, _state: &mut PassState) -> ResultLog<(), Error>
+    {
+        let sequence = match node
+        {
+            Node::Sequence(sequence) => sequence,
+            _ => return ResultLog::Ok(()),
+        };
+
+        let enumerated_names: Vec<String> = sequence
+            .get_nodes()
+            .iter()
+            .filter_map(|node| match node
+            {
+                Node::Class(class) if is_enumerated(class) => Some(class.get_name().clone()),
+                _ => None,
+            })
+            .collect();
+
+        let mut errors = Vec::new();
+        for name in enumerated_names
+        {
+            if let Err(error) = explicate(sequence.get_nodes_mut(), &name, &mut self.temp_names)
+            {
+                errors.push(error);
+            }
+        }
+
+        ResultLog::maybe_error((), Vec::new(), errors)
+    }
+}
+
+fn is_enumerated(class: &Class) -> bool
+{
+    class
+        .get_traits()
+        .iter()
+        .any(|name| name == symbols::traits::ENUMERATED)
+}
+
+///
+/// Information about an enumerated type gathered before its variants are changed
+///
+struct Enumeration
+{
+    name:          String,
+    class_type:    TypeId,
+    instance_type: TypeId,
+    tag_type:      TypeId,
+    union_type:    TypeId,
+    variants:      Vec<Variant>,
+}
+
+///
+/// Information about a variant of an enumerated type (variants without any instance data don't
+///     have a data type)
+///
+struct Variant
+{
+    name:       String,
+    class_type: TypeId,
+    data_type:  Option<TypeId>,
+    members:    Vec<String>,
+    tag:        String,
+}
+
+///
+/// Lower the enumerated type with the given name and all of its variants in a list of nodes
+///
+fn explicate(
+    nodes: &mut Vec<Node>,
+    name: &String,
+    temp_names: &mut TempNameGenerator,
+) -> Result<(), Error>
+{
+    let index = find_class(nodes, name)?;
+    let (class_type, instance_type, base_members, source) = match &nodes[index]
+    {
+        Node::Class(class) => (
+            class.get_type(),
+            class.get_instance_type(),
+            get_instance_member_names(class),
+            class.get_source(),
+        ),
+        _ => unreachable!(),
+    };
+
+    // Move the instance data of each variant into its own data type
+
+    let mut variants = Vec::new();
+    let mut data_classes = Vec::new();
+
+    for node in nodes.iter_mut()
+    {
+        let class = match node
+        {
+            Node::Class(class) if class.get_name() != name && class.get_traits().contains(name) =>
+            {
+                class
+            }
+            _ => continue,
+        };
+
+        let variant_members: Vec<Member> = class
+            .get_members()
+            .iter()
+            .filter(|member| {
+                member.get_scope() == MemberScope::Instance
+                    && !base_members.contains(member.get_name())
+            })
+            .cloned()
+            .collect();
+
+        let members = variant_members
+            .iter()
+            .map(|member| member.get_name().clone())
+            .collect();
+
+        let data_type = match variant_members.is_empty()
+        {
+            true => None,
+            false =>
+            {
+                let data_class = Class::new(
+                    format!("Data/{}/{}", name, class.get_name()),
+                    Vec::new(),
+                    variant_members,
+                    Vec::new(),
+                    Vec::new(),
+                    class.get_source(),
+                );
+
+                let data_type = data_class.get_instance_type();
+
+                data_classes.push(data_class.to_node());
+                Some(data_type)
+            }
+        };
+
+        // The variant's own instance members are stored in the enumerated type
+
+        class
+            .get_members_mut()
+            .retain(|member| member.get_scope() == MemberScope::Static);
+
+        if let Type::Class(variant_class_type) = class.get_type().get()
+        {
+            variant_class_type.set_variant_of(class_type);
+        }
+
+        variants.push(Variant {
+            name: class.get_name().clone(),
+            class_type: class.get_type(),
+            data_type,
+            members,
+            tag: format!("Type/{}/{}", name, class.get_name()),
+        });
+    }
+
+    if variants.is_empty()
+    {
+        return Err(Error::NoVariants(name.clone(), source));
+    }
+
+    // Add the tag and union members to the enumerated type
+
+    let tag_member = Member::new(
+        String::from("__K"),
+        TagType::new(
+            format!("Type/{}", name),
+            variants.iter().map(|variant| variant.tag.clone()).collect(),
+        )
+        .to_type(),
+        MemberScope::Instance,
+        Visibility::Private,
+        Visibility::Private,
+        source.clone(),
+    );
+    let union_member = Member::new(
+        String::from("__V"),
+        UnionType::new(
+            variants
+                .iter()
+                .filter_map(|variant| match &variant.data_type
+                {
+                    Some(data_type) => Some((variant.name.clone(), *data_type)),
+                    None => None,
+                })
+                .collect(),
+        )
+        .to_type(),
+        MemberScope::Instance,
+        Visibility::Private,
+        Visibility::Private,
+        source.clone(),
+    );
+
+    let enumeration = Enumeration {
+        name: name.clone(),
+        class_type,
+        instance_type,
+        tag_type: tag_member.get_type(),
+        union_type: union_member.get_type(),
+        variants,
+    };
+
+    if let Node::Class(class) = &mut nodes[index]
+    {
+        class.get_members_mut().push(tag_member);
+        class.get_members_mut().push(union_member);
+    }
+
+    // Update all uses of variants, then make methods of the enumerated type dispatch to variants
+
+    for node in nodes.iter_mut()
+    {
+        explicate_variant_uses(node, &enumeration, temp_names);
+    }
+
+    let dispatches: Vec<(String, Vec<usize>)> = match &nodes[index]
+    {
+        Node::Class(class) => class
+            .get_methods()
+            .iter()
+            .filter(|method| method.get_scope() == MemberScope::Instance)
+            .map(|method| {
+                let implementors =
+                    get_implementing_variants(nodes, &enumeration, method.get_name());
+                (method.get_name().clone(), implementors)
+            })
+            .collect(),
+        _ => unreachable!(),
+    };
+
+    for (method_name, implementors) in dispatches
+    {
+        let variant_methods: Vec<(String, TypeId)> = implementors
+            .iter()
+            .map(|i| match &nodes[*i]
+            {
+                Node::Class(class) => (class.get_name().clone(), get_method_type(class, &method_name)),
+                _ => unreachable!(),
+            })
+            .collect();
+
+        if let Node::Class(class) = &mut nodes[index]
+        {
+            for function in class.get_children_mut()
+            {
+                if let Node::Function(function) = function
+                {
+                    if *function.get_name() == method_name
+                    {
+                        make_dispatch(function, &enumeration, &variant_methods);
+                    }
+                }
+            }
+        }
+    }
+
+    // Data types must be defined before the enumerated type that holds them
+
+    let index = find_class(nodes, name)?;
+    for data_class in data_classes.into_iter().rev()
+    {
+        nodes.insert(index, data_class);
+    }
+
+    Ok(())
+}
+
+fn find_class(nodes: &Vec<Node>, name: &String) -> Result<usize, Error>
+{
+    nodes
+        .iter()
+        .position(|node| match node
+        {
+            Node::Class(class) => class.get_name() == name,
+            _ => false,
+        })
+        .ok_or_else(|| Error::Internal(format!("Failed to find enumerated type: {}", name)))
+}
+
+fn get_instance_member_names(class: &Class) -> Vec<String>
+{
+    class
+        .get_members()
+        .iter()
+        .filter(|member| member.get_scope() == MemberScope::Instance)
+        .map(|member| member.get_name().clone())
+        .collect()
+}
+
+///
+/// Get the indices of the variants that implement (rather than inherit) an enumerated type's method
+///
+fn get_implementing_variants(
+    nodes: &Vec<Node>,
+    enumeration: &Enumeration,
+    method_name: &String,
+) -> Vec<usize>
+{
+    let mut indices = Vec::new();
+
+    for variant in enumeration.variants.iter()
+    {
+        if let Ok(i) = find_class(nodes, &variant.name)
+        {
+            if let Node::Class(class) = &nodes[i]
+            {
+                let is_implemented = class.get_methods().iter().any(|method| {
+                    method.get_name() == method_name
+                        && method.get_implements().as_ref() == Some(&enumeration.name)
+                });
+
+                if is_implemented
+                {
+                    indices.push(i);
+                }
+            }
+        }
+    }
+
+    indices
+}
+
+fn get_method_type(class: &Class, method_name: &String) -> TypeId
+{
+    match &class.get_type().get()
+    {
+        Type::Class(class_type) => match class_type.get_method(method_name)
+        {
+            Some(method) => method.get_type(),
+            None => basic_types::interned::unknown(),
+        },
+        _ => basic_types::interned::unknown(),
+    }
+}
+
+///
+/// Rewrite accesses to the data members of variants and creation of variants
+///
+fn explicate_variant_uses(
+    node: &mut Node,
+    enumeration: &Enumeration,
+    temp_names: &mut TempNameGenerator,
+)
+{
+    for child in node.get_children_mut()
+    {
+        explicate_variant_uses(child, enumeration, temp_names);
+    }
+
+    match node
+    {
+        Node::Access(access) =>
+        {
+            // x.member => x.__V.Variant.member
+
+            let target_type = access.get_target().get_type();
+            let (variant, data_type) = match get_variant(&target_type, enumeration)
+            {
+                Some(variant) if variant.members.contains(access.get_property()) =>
+                {
+                    match &variant.data_type
+                    {
+                        Some(data_type) => (variant, *data_type),
+                        None => return,
+                    }
+                }
+                _ => return,
+            };
+
+            let source = access.get_source();
+            let target = access.get_target_mut().extract_temp();
+
+            let mut union_access = Access::new(target, String::from("__V"), source.clone());
+            union_access.set_type(enumeration.union_type);
+
+            let mut data_access = Access::new(union_access.to_node(), variant.name.clone(), source);
+            data_access.set_type(data_type);
+
+            *access.get_target_mut() = data_access.to_node();
+        }
+        Node::Call(call) =>
+        {
+            // (create Variant) => { let x = (create Enumerated) x.__K <- Type/Enumerated/Variant x }
+
+            let operator = match call.get_operator()
+            {
+                Node::PrimitiveOperator(operator) => match operator.get_value()
+                {
+                    Operator::Create | Operator::HeapAllocate => operator.get_value(),
+                    _ => return,
+                },
+                _ => return,
+            };
+
+            let variant = match call.get_operands().as_slice()
+            {
+                [operand] => match enumeration
+                    .variants
+                    .iter()
+                    .find(|variant| variant.class_type == operand.get_type())
+                {
+                    Some(variant) => variant,
+                    None => return,
+                },
+                _ => return,
+            };
+
+            let source = call.get_source();
+            let created_type = match operator
+            {
+                Operator::HeapAllocate =>
+                {
+                    ReferenceType::from(ReferenceMode::Mutable, enumeration.instance_type)
+                        .to_type()
+                        .intern()
+                }
+                _ => enumeration.instance_type,
+            };
+
+            let mut create_operator = PrimitiveOperator::new(operator, source.clone());
+            create_operator.set_type(
+                FunctionType::from(vec![enumeration.class_type], created_type)
+                    .to_type()
+                    .intern(),
+            );
+
+            let class_variable = Variable::new_typed(
+                enumeration.name.clone(),
+                enumeration.class_type,
+                source.clone(),
+            );
+
+            let mut create = Call::new(
+                create_operator.to_node(),
+                vec![class_variable.to_node()],
+                source.clone(),
+            );
+            create.set_type(created_type);
+
+            let temp_name = temp_names.next();
+            let temp_variable =
+                || Variable::new_typed(temp_name.clone(), created_type, source.clone());
+
+            let mut tag_access =
+                Access::new(temp_variable().to_node(), String::from("__K"), source.clone());
+            tag_access.set_type(enumeration.tag_type);
+
+            let tag_value = Variable::new_typed(
+                variant.tag.clone(),
+                enumeration.tag_type,
+                source.clone(),
+            );
+
+            let mut sequence = Sequence::new(
+                SequenceMode::Scope,
+                vec![
+                    Binding::new(temp_name.clone(), create.to_node(), source.clone()).to_node(),
+                    Assign::new(tag_access.to_node(), tag_value.to_node(), source.clone())
+                        .to_node(),
+                    temp_variable().to_node(),
+                ],
+                source,
+            );
+            sequence.set_type(call.get_type());
+
+            *node = sequence.to_node();
+        }
+        _ => (),
+    }
+}
+
+///
+/// Get the variant a (reference to an) instance type is an instance of
+///
+fn get_variant<'a>(t: &TypeId, enumeration: &'a Enumeration) -> Option<&'a Variant>
+{
+    let instance_type = match t.get().dereference()
+    {
+        Some(target) => target,
+        None => *t,
+    };
+
+    let class_type = match &instance_type.get()
+    {
+        Type::Instance(instance) => instance.get_class_type(),
+        _ => return None,
+    };
+
+    enumeration
+        .variants
+        .iter()
+        .find(|variant| variant.class_type == class_type)
+}
+
+///
+/// Replace the body of a method of an enumerated type with a dispatch on the tag to the same method
+///     of each implementing variant, falling back to the original body
+///
+fn make_dispatch(
+    function: &mut Function,
+    enumeration: &Enumeration,
+    variant_methods: &Vec<(String, TypeId)>,
+)
+{
+    let source = function.get_source();
+    let return_type = function.get_return_type();
+
+    let arguments: Vec<(String, TypeId)> = function
+        .get_arguments()
+        .iter()
+        .map(|argument| (argument.get_name().clone(), argument.get_type()))
+        .collect();
+
+    let argument_variable = |i: usize| {
+        let (name, t) = &arguments[i];
+        Variable::new_typed(name.clone(), *t, source.clone()).to_node()
+    };
+
+    let mut result = function.get_body_mut().extract_temp();
+
+    for (variant_name, method_type) in variant_methods.iter().rev()
+    {
+        let variant = match enumeration
+            .variants
+            .iter()
+            .find(|variant| variant.name == *variant_name)
+        {
+            Some(variant) => variant,
+            None => continue,
+        };
+
+        // self.__K == Type/Enumerated/Variant
+
+        let mut tag_access = Access::new(argument_variable(0), String::from("__K"), source.clone());
+        tag_access.set_type(enumeration.tag_type);
+
+        let tag_value = Variable::new_typed(
+            variant.tag.clone(),
+            enumeration.tag_type,
+            source.clone(),
+        );
+
+        let mut equal = PrimitiveOperator::new(Operator::Equal, source.clone());
+        equal.set_type(
+            FunctionType::from(
+                vec![enumeration.tag_type, enumeration.tag_type],
+                basic_types::interned::boolean(),
+            )
+            .to_type()
+            .intern(),
+        );
+
+        let mut condition = Call::new(
+            equal.to_node(),
+            vec![tag_access.to_node(), tag_value.to_node()],
+            source.clone(),
+        );
+        condition.set_type(basic_types::interned::boolean());
+
+        // (Variant.Method self args...)
+
+        let variant_self_type = match &method_type.get()
+        {
+            Type::Function(function_type) => function_type.get_arguments()[0],
+            _ => arguments[0].1,
+        };
+
+        let mut operands = vec![adapt_self(argument_variable(0), &variant_self_type)];
+        for i in 1..arguments.len()
+        {
+            operands.push(argument_variable(i));
+        }
+
+        let class_variable = Variable::new_typed(
+            variant.name.clone(),
+            variant.class_type,
+            source.clone(),
+        );
+
+        let mut method_access =
+            Access::new(class_variable.to_node(), function.get_name().clone(), source.clone());
+        method_access.set_type(*method_type);
+
+        let mut call = Call::new(method_access.to_node(), operands, source.clone());
+        call.set_type(return_type);
+
+        let mut conditional = Conditional::new(
+            condition.to_node(),
+            call.to_node(),
+            result,
+            source.clone(),
+        );
+        conditional.set_type(return_type);
+
+        result = conditional.to_node();
+    }
+
+    let mut body = Sequence::new(SequenceMode::Scope, vec![result], source);
+    body.set_type(return_type);
+
+    *function.get_body_mut() = body.to_node();
+}
+
+///
+/// Reference or dereference the self argument of an enumerated type's method to match the self
+///     argument of a variant's method
+///
+fn adapt_self(self_node: Node, variant_self_type: &TypeId) -> Node
+{
+    let self_type = self_node.get_type();
+    let source = self_node.get_source();
+
+    let self_mode = match &self_type.get()
+    {
+        Type::Reference(reference) => Some(reference.get_mode()),
+        _ => None,
+    };
+    let variant_mode = match &variant_self_type.get()
+    {
+        Type::Reference(reference) => Some(reference.get_mode()),
+        _ => None,
+    };
+
+    match (self_mode, variant_mode)
+    {
+        (Some(_), None) =>
+        {
+            let target_type = self_type.get().dereference();
+
+            let mut dereference = Dereference::new(self_node, source);
+            if let Some(t) = target_type
+            {
+                dereference.set_type(t);
+            }
+            dereference.to_node()
+        }
+        (None, Some(mode)) =>
+        {
+            let mut reference = Reference::new(mode, self_node, source);
+            reference.set_type(ReferenceType::from(mode, self_type).to_type().intern());
+            reference.to_node()
+        }
+        _ => self_node,
+    }
+}
@@ -65,7 +65,7 @@ impl RecurTransform<Node, PassState, Error> for FlattenBindings
                 let temporary_name = self.temp_names.next();
                 let bind_temporary = Binding::new(
                     temporary_name.clone(),
-                    Node::nothing_typed(binding_type.clone(), binding_source_inner),
+                    Node::nothing_typed(binding_type, binding_source_inner),
                     binding_source_outer.clone(),
                 )
                 .to_node();
@@ -74,7 +74,7 @@ impl RecurTransform<Node, PassState, Error> for FlattenBindings
 
                 let temporary_variable = Variable::new_typed(
                     temporary_name.clone(),
-                    binding_type.clone(),
+                    binding_type,
                     binding_source_outer.clone(),
                 )
                 .to_node();
@@ -87,7 +87,7 @@ impl RecurTransform<Node, PassState, Error> for FlattenBindings
                     let source = result_node.get_source();
                     let temporary_variable = Variable::new_typed(
                         temporary_name.clone(),
-                        binding_type.clone(),
+                        binding_type,
                         source.clone(),
                     )
                     .to_node();
@@ -4,10 +4,15 @@ use super::common::*;
 /// ## Flatten Definitions Pass
 ///
 /// - Moves all definitions to the top-level sequence
+///     - Classes are placed before functions, so their types are declared before any use
+///     - Classes are placed after the classes they contain by value, so their structs are defined
+///         before they are used as fields (classes can't contain themselves, see CheckTypes)
+///     - Methods are extracted from their classes like any other function
 /// - Definition names should have been made unique (and variable references updated) in FlattenNames
 ///
 pub struct FlattenDefinitions
 {
+    classes:   Vec<Node>,
     functions: Vec<Node>,
 }
 
@@ -16,6 +21,7 @@ impl FlattenDefinitions
     pub fn new() -> FlattenDefinitions
     {
         FlattenDefinitions {
+            classes:   Vec::new(),
             functions: Vec::new(),
         }
     }
@@ -69,6 +75,7 @@ impl RecurTransform<Node, PassState, Error> for FlattenDefinitions
                 let mut new_nodes = Vec::new();
                 let mut original_nodes = std::mem::take(sequence.get_nodes_mut());
 
+                new_nodes.append(&mut order_classes(std::mem::take(&mut self.classes)));
                 new_nodes.append(&mut self.functions);
                 new_nodes.append(&mut original_nodes);
 
@@ -83,9 +90,126 @@ impl RecurTransform<Node, PassState, Error> for FlattenDefinitions
 
                 self.functions.push(original_node);
             }
+            Node::Class(class) =>
+            {
+                // Extract classes and replace with a placeholder comment
+
+                let comment = format!("type {}", class.get_name());
+                let original_node = node.extract_comment(comment);
+
+                self.classes.push(original_node);
+            }
             _ => (),
         }
 
         ResultLog::Ok(())
     }
 }
+
+///
+/// Order class definitions so that each class comes after the classes it contains by value,
+///     keeping the original order otherwise
+///
+fn order_classes(classes: Vec<Node>) -> Vec<Node>
+{
+    let class_types: Vec<Option<ClassType>> = classes
+        .iter()
+        .map(|node| match node
+        {
+            Node::Class(class) => match class.get_type().get()
+            {
+                Type::Class(class_type) => Some(class_type),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+
+    let mut order = Vec::new();
+    let mut is_visited = vec![false; classes.len()];
+
+    for i in 0..classes.len()
+    {
+        visit_class(i, &classes, &class_types, &mut is_visited, &mut order);
+    }
+
+    let mut classes: Vec<Option<Node>> = classes.into_iter().map(Some).collect();
+    order
+        .into_iter()
+        .filter_map(|i| classes[i].take())
+        .collect()
+}
+
+fn visit_class(
+    i: usize,
+    classes: &Vec<Node>,
+    class_types: &Vec<Option<ClassType>>,
+    is_visited: &mut Vec<bool>,
+    order: &mut Vec<usize>,
+)
+{
+    if is_visited[i]
+    {
+        return;
+    }
+    is_visited[i] = true;
+
+    let mut contained = Vec::new();
+    if let Node::Class(class) = &classes[i]
+    {
+        // Variants are stored as their enumerated type
+
+        if let Some(Some(enumerated)) = class_types[i].map(|class_type| class_type.get_variant_of())
+        {
+            get_contained_classes(enumerated, &mut contained);
+        }
+
+        for member in class.get_members()
+        {
+            if member.get_scope() == MemberScope::Instance
+            {
+                get_contained_classes(member.get_type(), &mut contained);
+            }
+        }
+    }
+
+    for class_type in contained
+    {
+        let index = class_types
+            .iter()
+            .position(|other| *other == Some(class_type));
+
+        if let Some(j) = index
+        {
+            visit_class(j, classes, class_types, is_visited, order);
+        }
+    }
+
+    order.push(i);
+}
+
+///
+/// Get the classes a value of some type contains (ie. not through a reference)
+///
+fn get_contained_classes(t: TypeId, classes: &mut Vec<ClassType>)
+{
+    match t.get()
+    {
+        Type::Instance(instance) =>
+        {
+            if let Some(class_type) = instance.get_class()
+            {
+                classes.push(class_type);
+            }
+        }
+        Type::Class(class_type) => classes.push(class_type),
+        Type::Union(union) =>
+        {
+            for (_, field_type) in union.get_fields()
+            {
+                get_contained_classes(*field_type, classes);
+            }
+        }
+        _ => (),
+    }
+}
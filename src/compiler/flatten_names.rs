@@ -5,8 +5,12 @@ use super::common::*;
 ///
 /// - Makes all definition names unique (such that they can appear as top-level definitions in C)
 ///     - ex. nested `fn Foo { fn Bar }` to `fn Foo { fn Foo/Bar }`
+///     - ex. methods `type Foo { fn Bar }` to `type Foo { fn Foo/Bar }`
+///     - ex. static data members `type Foo { Bar : int }` to `type Foo { Foo/Bar : int }`
 ///     - Generates unique names for anonymous sequence scopes
 /// - Updates variable references to renamed definitions (respecting shadowing / scope binding rules)
+/// - Replaces static accesses with variable references to the flattened member name
+///     - ex. `Foo.Bar` to `Foo/Bar`
 /// - Leaves definition nodes in-place for `FlattenDefinitions` to extract
 ///
 pub struct FlattenNames
@@ -30,6 +34,7 @@ pub enum ParentScope
     Root,
     Function(String),
     Sequence(String),
+    Class(String),
 }
 
 impl ParentScope
@@ -39,7 +44,9 @@ impl ParentScope
         match self
         {
             ParentScope::None | ParentScope::Root => "",
-            ParentScope::Function(name) | ParentScope::Sequence(name) => name.as_str(),
+            ParentScope::Function(name)
+            | ParentScope::Sequence(name)
+            | ParentScope::Class(name) => name.as_str(),
         }
     }
 
@@ -48,10 +55,9 @@ impl ParentScope
         match self
         {
             ParentScope::None | ParentScope::Root => child_name.clone(),
-            ParentScope::Function(name) | ParentScope::Sequence(name) =>
-            {
-                format!("{}/{}", name, child_name)
-            }
+            ParentScope::Function(name)
+            | ParentScope::Sequence(name)
+            | ParentScope::Class(name) => format!("{}/{}", name, child_name),
         }
     }
 }
@@ -66,9 +72,10 @@ impl RecurTransform<Node, State, Error> for FlattenNames
 
         if let Node::Sequence(sequence) = node
         {
-            // Don't give new names to root-level functions
+            // Don't give new names to root-level functions or classes
 
             binding_state.add_definitions_from_functions(sequence.get_nodes(), |_function| None);
+            binding_state.add_definitions_from_classes(sequence.get_nodes(), |_class| None);
         }
 
         (ParentScope::None, binding_state)
@@ -91,7 +98,7 @@ impl RecurTransform<Node, State, Error> for FlattenNames
 
                         ParentScope::Sequence(function_name.clone())
                     }
-                    ParentScope::Root | ParentScope::Sequence(_) =>
+                    ParentScope::Root | ParentScope::Sequence(_) | ParentScope::Class(_) =>
                     {
                         let anonymous_name = self.anonymous_scope_names.next();
                         ParentScope::Sequence(parent_scope.get_child_name(&anonymous_name))
@@ -105,6 +112,9 @@ impl RecurTransform<Node, State, Error> for FlattenNames
                     .add_definitions_from_functions(sequence.get_nodes(), |function| {
                         Some(new_parent_scope.get_child_name(function.get_name()))
                     });
+                new_binding_state.add_definitions_from_classes(sequence.get_nodes(), |class| {
+                    Some(new_parent_scope.get_child_name(class.get_name()))
+                });
 
                 let new_state = (new_parent_scope, new_binding_state);
                 vec![ChildState::New(new_state)]
@@ -125,6 +135,18 @@ impl RecurTransform<Node, State, Error> for FlattenNames
                 let new_state = (new_parent_scope, new_binding_state);
                 vec![ChildState::New(new_state)]
             }
+            Node::Class(class) =>
+            {
+                // Methods are named under the new name of their class
+
+                let scope_name = parent_scope.get_child_name(class.get_name());
+                let new_parent_scope = ParentScope::Class(scope_name);
+
+                let new_binding_state = BindingState::empty(binding_state, true, true);
+
+                let new_state = (new_parent_scope, new_binding_state);
+                vec![ChildState::New(new_state)]
+            }
             _ => vec![ChildState::Inherit],
         }
     }
@@ -148,6 +170,31 @@ impl RecurTransform<Node, State, Error> for FlattenNames
                     _ => (),
                 }
             }
+            Node::Access(access) =>
+            {
+                // Static members and methods are top-level definitions, so access them by name
+
+                let static_name = match access.get_target()
+                {
+                    Node::Variable(variable) => match &variable.get_type().get()
+                    {
+                        Type::Class(_) => Some(format!(
+                            "{}/{}",
+                            variable.get_name(),
+                            access.get_property()
+                        )),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+
+                if let Some(name) = static_name
+                {
+                    let new_variable =
+                        Variable::new_typed(name, access.get_type(), access.get_source());
+                    *node = new_variable.to_node();
+                }
+            }
             Node::Binding(binding) =>
             {
                 // Shadow previous names so they are no longer changed after this variable is bound
@@ -160,6 +207,30 @@ impl RecurTransform<Node, State, Error> for FlattenNames
 
                 *function.get_name_mut() = scope.get_child_name(function.get_name());
             }
+            Node::Class(class) =>
+            {
+                // Rename the class and its type
+
+                let new_name = scope.get_child_name(class.get_name());
+
+                if let Type::Class(class_type) = class.get_type().get()
+                {
+                    class_type.set_name(new_name.clone());
+                }
+
+                // Static data members are stored as globals, so they need unique names as well
+
+                let static_scope = ParentScope::Class(new_name.clone());
+                for member in class.get_members_mut()
+                {
+                    if member.get_scope() == MemberScope::Static
+                    {
+                        *member.get_name_mut() = static_scope.get_child_name(member.get_name());
+                    }
+                }
+
+                *class.get_name_mut() = new_name;
+            }
 
             _ => (),
         }
@@ -5,6 +5,8 @@ use super::common::*;
 ///
 /// - Substitutes complex nodes used in operand context with a temporary binding such that all
 ///     operands are simple (either atomic or a call on simple operands)
+/// - Replaces nodes with an operand that jumps away (ie. `(f a (return b))`) with their operands up
+///     to and including the jump, since the node itself is never reached
 ///
 pub struct FlattenOperands
 {
@@ -59,6 +61,73 @@ impl FlattenOperands
         bindings
     }
 
+    ///
+    /// Get the children of a node that must be simple
+    ///
+    fn get_operands_mut(node: &mut Node) -> Vec<&mut Node>
+    {
+        match node
+        {
+            Node::Call(_)
+            | Node::Reference(_)
+            | Node::Dereference(_)
+            | Node::Assign(_)
+            | Node::Access(_)
+            | Node::Jump(_) => node.get_children_mut(),
+            Node::Conditional(conditional) => vec![conditional.get_condition_mut()],
+            Node::WhileLoop(while_loop) => vec![while_loop.get_condition_mut()],
+            _ => Vec::new(),
+        }
+    }
+
+    ///
+    /// Replace a node with the operands it evaluates before one of them jumps away, if any do
+    ///
+    /// - Returns true if the node was replaced
+    ///
+    fn replace_with_jump(node: &mut Node) -> bool
+    {
+        let operands = Self::get_operands_mut(node);
+        let jump_index = match operands
+            .iter()
+            .position(|operand| operand.get_type().get().is_never())
+        {
+            Some(index) => index,
+            None => return false,
+        };
+
+        // Operands before the jump are still evaluated for their side effects, but atomic values
+        //  can be dropped
+
+        let mut nodes = Vec::new();
+        for (i, operand) in operands.into_iter().enumerate().take(jump_index + 1)
+        {
+            let is_atomic = match operand
+            {
+                Node::Nothing(_)
+                | Node::Integer(_)
+                | Node::Float(_)
+                | Node::Boolean(_)
+                | Node::Text(_)
+                | Node::Variable(_)
+                | Node::PrimitiveOperator(_) => true,
+                _ => false,
+            };
+
+            if i == jump_index || !is_atomic
+            {
+                nodes.push(operand.extract_temp());
+            }
+        }
+
+        let source = node.get_source();
+        let mut sequence = Sequence::new(SequenceMode::Transparent, nodes, source);
+        sequence.set_type(basic_types::interned::never());
+
+        *node = sequence.to_node();
+        true
+    }
+
     ///
     /// Takes a node and a prepends it with list of bindings for all temporary complex operand variables
     ///
@@ -76,12 +145,15 @@ impl FlattenOperands
         // Get the original node, add it after the bindings, and create an enclosing sequence
 
         let original_node = node.extract_temp();
+        let original_type = original_node.get_type();
         bindings.push(original_node);
-        let sequence = Sequence::new(SequenceMode::Transparent, bindings, source).to_node();
+
+        let mut sequence = Sequence::new(SequenceMode::Transparent, bindings, source);
+        sequence.set_type(original_type);
 
         // Put the new sequence back in place
 
-        *node = sequence;
+        *node = sequence.to_node();
     }
 }
 
@@ -94,13 +166,21 @@ impl RecurTransform<Node, PassState, Error> for FlattenOperands
 
     fn exit(&mut self, node: &mut Node, _state: &mut PassState) -> ResultLog<(), Error>
     {
+        if Self::replace_with_jump(node)
+        {
+            // The node itself is never reached, so its operands don't need to be simple
+
+            return ResultLog::Ok(());
+        }
+
         match node
         {
             Node::Call(_)
             | Node::Reference(_)
             | Node::Dereference(_)
             | Node::Assign(_)
-            | Node::Access(_) =>
+            | Node::Access(_)
+            | Node::Jump(_) =>
             {
                 // All children of call-like nodes must be simple
 
@@ -114,6 +194,19 @@ impl RecurTransform<Node, PassState, Error> for FlattenOperands
                 let bindings = self.bind_complex(vec![conditional.get_condition_mut()]);
                 Self::prepend_bindings(node, bindings);
             }
+            Node::WhileLoop(while_loop) =>
+            {
+                // The condition of a loop must be simple, but is checked on every iteration so its
+                //  bindings go in the loop's prelude instead of before the loop
+
+                let bindings = self.bind_complex(vec![while_loop.get_condition_mut()]);
+                if !bindings.is_empty()
+                {
+                    let source = while_loop.get_source();
+                    *while_loop.get_prelude_mut() =
+                        Sequence::new(SequenceMode::Transparent, bindings, source).to_node();
+                }
+            }
             _ => (),
         }
 
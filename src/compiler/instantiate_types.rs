@@ -0,0 +1,880 @@
+use super::common::*;
+
+use std::collections::HashMap;
+
+///
+/// ## Instantiate Types Pass
+///
+/// - Creates a concrete copy of each parameterized type (ie. `type Numbered [T is Type] {...}`) for
+///     each unique set of type arguments it is applied to
+///     - ex. `x : (Numbered Sum3)` becomes `x : Numbered/Sum3`, where `Numbered/Sum3` is a copy of
+///         Numbered with `T` replaced by Sum3
+///     - Copies are named after the parameterized type and their type arguments, with references
+///         named by their mode (ie. `(Pair (ref int) bool)` is `Pair/ref/int/bool`)
+///     - Function types are named by their argument count, argument types and return type (ie.
+///         `(Box (int int -> bool))` is `Box/fn/2/int/int/bool`)
+///     - Copies are placed after the parameterized type and the types used as its arguments
+///     - Copies can apply other parameterized types, which are instantiated in turn
+/// - Type aliases (ie. `type NumberedSum3 = (Numbered Sum3)`) are replaced by the types they name
+///     - Variables naming an alias (ie. `(create NumberedSum3)`) are renamed to the aliased type
+/// - Parameterized types and aliases are removed once all copies have been made
+/// - Parameterized types and aliases can be used in the sequence that defines them and in any
+///     sequence inside it, and copies are placed in the sequence that defines the parameterized type
+///     - Signatures that apply a parameterized type that isn't defined are reported, even if the
+///         sequence doesn't define any
+/// - Aliases can't name themselves, and parameterized types can't apply themselves to larger type
+///     arguments (ie. `(Box (Box T))` inside Box), since either would never finish instantiating
+/// - Type arguments aren't checked against the bounds of their parameters, uses of the copies are
+///     checked like any other type
+///
+pub struct InstantiateTypes
+{
+    // The parameterized types visible in each enclosing sequence
+    scopes: Vec<HashMap<String, Class>>,
+}
+
+impl InstantiateTypes
+{
+    pub fn new() -> InstantiateTypes
+    {
+        InstantiateTypes { scopes: Vec::new() }
+    }
+}
+
+pub struct PassState {}
+
+impl RecurTransform<Node, PassState, Error> for InstantiateTypes
+{
+    fn get_root_state(&mut self, _node: &Node) -> PassState
+    {
+        PassState {}
+    }
+
+    fn enter(&mut self, node: &mut Node, _state: &mut PassState) -> ResultLog<(), Error>
+    {
+        let sequence = match node
+        {
+            Node::Sequence(sequence) => sequence,
+            _ => return ResultLog::Ok(()),
+        };
+
+        let has_definitions = sequence.get_nodes().iter().any(|node| match node
+        {
+            Node::Class(class) => class.is_parameterized(),
+            Node::Alias(_) => true,
+            _ => false,
+        });
+
+        let mut generics = self.scopes.last().cloned().unwrap_or_default();
+        for node in sequence.get_nodes()
+        {
+            if let Node::Class(class) = node
+            {
+                if class.is_parameterized()
+                {
+                    generics.insert(class.get_name().clone(), class.clone());
+                }
+            }
+        }
+
+        // Sequences inside a sequence that defines parameterized types may apply them to their own
+        //  types, which were left for them to instantiate
+
+        let is_inside_definitions = !self.scopes.last().map_or(true, HashMap::is_empty);
+        let errors = match has_definitions || is_inside_definitions
+        {
+            true => instantiate_all(sequence.get_nodes_mut(), generics.clone()),
+            false => Vec::new(),
+        };
+
+        self.scopes.push(generics);
+        ResultLog::maybe_error((), Vec::new(), errors)
+    }
+
+    fn exit(&mut self, node: &mut Node, _state: &mut PassState) -> ResultLog<(), Error>
+    {
+        if let Node::Sequence(_) = node
+        {
+            self.scopes.pop();
+            return ResultLog::Ok(());
+        }
+
+        // Applications left in a signature once every enclosing sequence has been instantiated
+        //  name a parameterized type that isn't defined (types that failed to instantiate are
+        //  already unknown)
+
+        let signature_types = match node
+        {
+            Node::Function(function) => vec![function.get_type()],
+            Node::Closure(closure) => vec![closure.get_type()],
+            Node::Class(class) => class
+                .get_members()
+                .iter()
+                .map(|member| member.get_type())
+                .collect(),
+            _ => return ResultLog::Ok(()),
+        };
+
+        match signature_types.into_iter().find_map(find_application)
+        {
+            Some(name) => ResultLog::new_error(Error::UnknownType(name, node.get_source())),
+            None => ResultLog::Ok(()),
+        }
+    }
+}
+
+///
+/// A copy of a parameterized type waiting to be made
+///
+struct Instance
+{
+    name:      String,
+    generic:   String,
+    arguments: Vec<TypeId>,
+}
+
+///
+/// Parameterized types and aliases defined in a sequence, along with the copies made so far
+///
+///
+/// - Types that fail to instantiate are replaced with the unknown type after their error is kept,
+///     so that every error in the sequence is found
+///
+struct Context
+{
+    generics:     HashMap<String, Class>,
+    local_names:  Vec<String>,
+    aliases:      HashMap<String, TypeId>,
+    pending:      Vec<Instance>,
+    instantiated: Vec<String>,
+    expanding:    Vec<String>,
+    errors:       Vec<Error>,
+}
+
+///
+/// Replace all type applications and aliases in a list of nodes, then make copies of parameterized
+///     types and remove the original definitions
+///
+/// - Parameterized types from enclosing sequences are given along with the ones from this sequence
+/// - Applications are left for a nested sequence if they apply a parameterized type it defines, or
+///     name one of its types or aliases, and copies made for them are placed in that sequence
+///
+fn instantiate_all(nodes: &mut Vec<Node>, generics: HashMap<String, Class>) -> Vec<Error>
+{
+    let mut context = Context {
+        generics,
+        local_names:  Vec::new(),
+        aliases:      HashMap::new(),
+        pending:      Vec::new(),
+        instantiated: Vec::new(),
+        expanding:    Vec::new(),
+        errors:       Vec::new(),
+    };
+
+    // Copies that apply their own type to larger arguments would need copies forever, so
+    //  applications of these types become unknown once the error is kept
+
+    context.expanding = find_expanding_generics(&context.generics);
+    for node in nodes.iter()
+    {
+        match node
+        {
+            Node::Class(class) if context.expanding.contains(class.get_name()) =>
+            {
+                let error =
+                    Error::ExpandingTypeArguments(class.get_name().clone(), class.get_source());
+                context.errors.push(error);
+            }
+            _ => (),
+        }
+    }
+
+    // Aliases can name aliases defined before them, but not themselves
+
+    let recursive_aliases = find_recursive_aliases(nodes);
+
+    for node in nodes.iter()
+    {
+        if let Node::Alias(alias) = node
+        {
+            let source = alias.get_source();
+            let aliased_type = match recursive_aliases.contains(alias.get_name())
+            {
+                true =>
+                {
+                    let error = Error::RecursiveAlias(alias.get_name().clone(), source);
+                    context.errors.push(error);
+                    basic_types::interned::unknown()
+                }
+                false => context.instantiate_or_unknown(alias.get_aliased_type(), &source),
+            };
+            context.aliases.insert(alias.get_name().clone(), aliased_type);
+        }
+    }
+
+    for node in nodes.iter_mut()
+    {
+        if let Node::Alias(_) = node
+        {
+            continue;
+        }
+        context.instantiate_definitions(node);
+    }
+
+    // Make copies of parameterized types, which may apply other parameterized types
+
+    let mut instances = Vec::new();
+    while !context.pending.is_empty()
+    {
+        let instance = context.pending.remove(0);
+        let generic = &context.generics[&instance.generic];
+
+        let parameters: HashMap<String, TypeId> = generic
+            .get_parameters()
+            .iter()
+            .map(|parameter| parameter.get_name().clone())
+            .zip(instance.arguments.iter().cloned())
+            .collect();
+
+        let mut substitute = |t: TypeId| substitute_type(t, &parameters);
+        let mut copy = generic
+            .instantiate(instance.name.clone(), &mut substitute)
+            .to_node();
+
+        context.instantiate_definitions(&mut copy);
+        instances.push((instance, copy));
+    }
+
+    for node in nodes.iter_mut()
+    {
+        rename_alias_variables(node, &context.aliases);
+    }
+
+    // Replace the original definitions with their copies
+
+    for (instance, copy) in instances
+    {
+        let index = get_instance_index(nodes, &instance);
+        nodes.insert(index, copy);
+    }
+
+    nodes.retain(|node| match node
+    {
+        Node::Class(class) => !class.is_parameterized(),
+        Node::Alias(_) => false,
+        _ => true,
+    });
+
+    context.errors
+}
+
+impl Context
+{
+    ///
+    /// Replace type applications and aliases in the signatures of all functions, classes and
+    ///     aliases in a node and its children (parameterized types are only changed once they're
+    ///     copied)
+    ///
+    /// - Types and aliases defined in a nested sequence hide the ones from this sequence with the
+    ///     same name
+    ///
+    fn instantiate_definitions(&mut self, node: &mut Node)
+    {
+        match node
+        {
+            Node::Class(class) if class.is_parameterized() => return,
+            Node::Class(_) | Node::Function(_) | Node::Closure(_) | Node::Alias(_) =>
+            {
+                // Only the first error is kept, since a type can appear more than once in a
+                //  signature (ie. an argument and the function type)
+
+                let source = node.get_source();
+                let mut error = None;
+
+                node.map_types(&mut |t| match self.instantiate_type(t, &source)
+                {
+                    Ok(new_type) => new_type,
+                    Err(e) =>
+                    {
+                        error.get_or_insert(e);
+                        basic_types::interned::unknown()
+                    }
+                });
+
+                self.errors.extend(error);
+            }
+            Node::Sequence(sequence) =>
+            {
+                let local_count = self.local_names.len();
+                for node in sequence.get_nodes()
+                {
+                    match node
+                    {
+                        Node::Class(class) => self.local_names.push(class.get_name().clone()),
+                        Node::Alias(alias) => self.local_names.push(alias.get_name().clone()),
+                        _ => (),
+                    }
+                }
+
+                let hidden: Vec<String> = sequence
+                    .get_nodes()
+                    .iter()
+                    .filter_map(|node| match node
+                    {
+                        Node::Class(class) if class.is_parameterized() => Some(class.get_name()),
+                        Node::Alias(alias) => Some(alias.get_name()),
+                        _ => None,
+                    })
+                    .filter(|name| {
+                        self.generics.contains_key(*name) || self.aliases.contains_key(*name)
+                    })
+                    .cloned()
+                    .collect();
+
+                let hidden_generics: Vec<_> = hidden
+                    .iter()
+                    .filter_map(|name| self.generics.remove_entry(name))
+                    .collect();
+                let hidden_aliases: Vec<_> = hidden
+                    .iter()
+                    .filter_map(|name| self.aliases.remove_entry(name))
+                    .collect();
+
+                for child in node.get_children_mut()
+                {
+                    self.instantiate_definitions(child);
+                }
+
+                self.generics.extend(hidden_generics);
+                self.aliases.extend(hidden_aliases);
+                self.local_names.truncate(local_count);
+                return;
+            }
+            _ => (),
+        }
+
+        for child in node.get_children_mut()
+        {
+            self.instantiate_definitions(child);
+        }
+    }
+
+    ///
+    /// Instantiate a type (see instantiate_type), keeping the error and giving the unknown type if
+    ///     it fails
+    ///
+    fn instantiate_or_unknown(&mut self, t: TypeId, source: &Source) -> TypeId
+    {
+        match self.instantiate_type(t, source)
+        {
+            Ok(new_type) => new_type,
+            Err(error) =>
+            {
+                self.errors.push(error);
+                basic_types::interned::unknown()
+            }
+        }
+    }
+
+    ///
+    /// Replace type applications and aliases in a type, keeping track of copies that need to be
+    ///     made for each application
+    ///
+    fn instantiate_type(&mut self, t: TypeId, source: &Source) -> Result<TypeId, Error>
+    {
+        match t.get()
+        {
+            Type::Instance(instance) => match self.aliases.get(&instance.get_name())
+            {
+                Some(aliased_type) => Ok(copy_type(*aliased_type)),
+                None => Ok(t),
+            },
+            Type::Reference(_) | Type::Array(_) | Type::Function(_) =>
+            {
+                let mut error = None;
+                let mut instantiate_inner = |inner| match self.instantiate_type(inner, source)
+                {
+                    Ok(new_inner) => new_inner,
+                    Err(e) =>
+                    {
+                        error.get_or_insert(e);
+                        inner
+                    }
+                };
+                let new_type = t.get().map_inner(&mut instantiate_inner);
+
+                match error
+                {
+                    Some(error) => Err(error),
+                    None => Ok(new_type.intern()),
+                }
+            }
+            Type::Application(application) =>
+            {
+                let mut arguments = Vec::new();
+                for argument in application.get_arguments()
+                {
+                    arguments.push(self.instantiate_type(*argument, source)?);
+                }
+
+                let mut names = Vec::new();
+                for argument in arguments.iter()
+                {
+                    get_named_types(*argument, &mut names);
+                }
+
+                let application = ApplicationType::from(application.get_name().clone(), arguments);
+                let is_local = names.iter().any(|name| self.local_names.contains(name));
+
+                if !self.generics.contains_key(application.get_name()) || is_local
+                {
+                    // Left for the sequence that defines the types it names, if there is one
+                    return Ok(application.to_type().intern());
+                }
+                if self.expanding.contains(application.get_name())
+                {
+                    return Ok(basic_types::interned::unknown());
+                }
+
+                let name = self.get_instance_name(&application, source)?;
+                Ok(InstanceType::new(name).to_type().intern())
+            }
+            _ => Ok(t),
+        }
+    }
+
+    ///
+    /// Get the name of the copy of a parameterized type for an application, making sure it will be
+    ///     copied
+    ///
+    fn get_instance_name(
+        &mut self,
+        application: &ApplicationType,
+        source: &Source,
+    ) -> Result<String, Error>
+    {
+        let generic_name = application.get_name();
+        let parameter_count = self.generics[generic_name].get_parameters().len();
+
+        let arguments = application.get_arguments();
+        if arguments.len() != parameter_count
+        {
+            return Err(Error::BadTypeArguments(
+                generic_name.clone(),
+                parameter_count,
+                arguments.len(),
+                source.clone(),
+            ));
+        }
+
+        let mut name = generic_name.clone();
+        for argument in arguments
+        {
+            match get_type_name(&argument.get())
+            {
+                Some(argument_name) => name = format!("{}/{}", name, argument_name),
+                None =>
+                {
+                    return Err(Error::BadTypeArgument(
+                        generic_name.clone(),
+                        *argument,
+                        source.clone(),
+                    ));
+                }
+            }
+        }
+
+        if !self.instantiated.contains(&name)
+        {
+            self.instantiated.push(name.clone());
+            self.pending.push(Instance {
+                name: name.clone(),
+                generic: generic_name.clone(),
+                arguments: arguments.clone(),
+            });
+        }
+
+        Ok(name)
+    }
+}
+
+///
+/// Get the name of a type argument to use in the name of a copy, if it is a concrete type
+///
+fn get_type_name(t: &Type) -> Option<String>
+{
+    match t
+    {
+        Type::Integer | Type::Boolean | Type::Float | Type::String | Type::Range | Type::Void =>
+        {
+            Some(format!("{}", t))
+        }
+        Type::Instance(instance) => Some(instance.get_name()),
+        Type::Reference(reference) =>
+        {
+            let mode = match reference.get_mode()
+            {
+                ReferenceMode::Immutable => symbols::operators::REFERENCE,
+                ReferenceMode::Mutable => symbols::operators::MUTABLE_REFERENCE,
+            };
+
+            let target_name = get_type_name(&reference.get_target().get())?;
+            Some(format!("{}/{}", mode, target_name))
+        }
+        Type::Array(array) =>
+        {
+            let element_name = get_type_name(&array.get_element().get())?;
+            match array.get_length()
+            {
+                Some(length) => Some(format!("{}/{}/{}", symbols::keywords::ARRAY, element_name, length)),
+                None => Some(format!("{}/{}", symbols::keywords::ARRAY, element_name)),
+            }
+        }
+        Type::Function(function) =>
+        {
+            // note: The argument count comes first, so names of nested function types are unambiguous
+            let mut name = format!(
+                "{}/{}",
+                symbols::keywords::FUNCTION,
+                function.get_arguments().len()
+            );
+            for argument in function.get_arguments()
+            {
+                name = format!("{}/{}", name, get_type_name(&argument.get())?);
+            }
+
+            let return_name = get_type_name(&function.get_return_type().get())?;
+            Some(format!("{}/{}", name, return_name))
+        }
+        _ => None,
+    }
+}
+
+///
+/// Make a new type with the same structure as some other type, replacing instances of type
+///     parameters with their arguments
+///
+fn substitute_type(t: TypeId, parameters: &HashMap<String, TypeId>) -> TypeId
+{
+    match t.get()
+    {
+        Type::Instance(instance) => match parameters.get(&instance.get_name())
+        {
+            Some(argument) => copy_type(*argument),
+            None => InstanceType::new(instance.get_name()).to_type().intern(),
+        },
+        Type::Reference(_) | Type::Array(_) | Type::Function(_) | Type::Application(_) => t
+            .get()
+            .map_inner(&mut |inner| substitute_type(inner, parameters))
+            .intern(),
+        Type::Bounded(bounded) =>
+        {
+            let names = bounded
+                .get_bounds()
+                .iter()
+                .filter_map(|bound| match bound.get()
+                {
+                    Type::Instance(instance) => Some(instance.get_name()),
+                    _ => None,
+                })
+                .collect();
+
+            BoundedType::new(names).to_type().intern()
+        }
+
+        Type::Integer | Type::Boolean | Type::Float | Type::String | Type::Range | Type::Void => t,
+
+        // Each copy of an inferred type is inferred separately
+        Type::Variable(_) => TypeVariable::new().to_type().intern(),
+
+        // note: other types are only created once types are inferred
+        _ => basic_types::interned::unknown(),
+    }
+}
+
+fn copy_type(t: TypeId) -> TypeId
+{
+    substitute_type(t, &HashMap::new())
+}
+
+///
+/// Get the name of the first parameterized type applied in a type, if there is one
+///
+fn find_application(t: TypeId) -> Option<String>
+{
+    match t.get()
+    {
+        Type::Application(application) => Some(application.get_name().clone()),
+        Type::Reference(reference) => find_application(reference.get_target()),
+        Type::Array(array) => find_application(array.get_element()),
+        Type::Function(function) => function
+            .get_arguments()
+            .iter()
+            .copied()
+            .chain(std::iter::once(function.get_return_type()))
+            .find_map(find_application),
+        _ => None,
+    }
+}
+
+///
+/// Get the names of aliases defined in a list of nodes that name themselves, directly or through
+///     other aliases
+///
+fn find_recursive_aliases(nodes: &Vec<Node>) -> Vec<String>
+{
+    let mut named: HashMap<String, Vec<String>> = HashMap::new();
+    for node in nodes.iter()
+    {
+        if let Node::Alias(alias) = node
+        {
+            let mut names = Vec::new();
+            get_named_types(alias.get_aliased_type(), &mut names);
+            named.insert(alias.get_name().clone(), names);
+        }
+    }
+
+    named
+        .keys()
+        .filter(|alias| {
+            let mut visited = Vec::new();
+            let mut pending = named[*alias].clone();
+
+            while let Some(name) = pending.pop()
+            {
+                if name == **alias
+                {
+                    return true;
+                }
+                if !visited.contains(&name)
+                {
+                    pending.extend(named.get(&name).cloned().unwrap_or_default());
+                    visited.push(name);
+                }
+            }
+
+            false
+        })
+        .cloned()
+        .collect()
+}
+
+///
+/// Get the names of all types and parameterized types named in a type
+///
+fn get_named_types(t: TypeId, names: &mut Vec<String>)
+{
+    match t.get()
+    {
+        Type::Instance(instance) => names.push(instance.get_name()),
+        Type::Application(application) =>
+        {
+            names.push(application.get_name().clone());
+            for argument in application.get_arguments()
+            {
+                get_named_types(*argument, names);
+            }
+        }
+        Type::Reference(reference) => get_named_types(reference.get_target(), names),
+        Type::Array(array) => get_named_types(array.get_element(), names),
+        Type::Function(function) =>
+        {
+            for argument in function.get_arguments()
+            {
+                get_named_types(*argument, names);
+            }
+            get_named_types(function.get_return_type(), names);
+        }
+        _ => (),
+    }
+}
+
+///
+/// Get the names of parameterized types that would need a copy for ever larger type arguments
+///
+/// - Each parameter of a parameterized type flows into the parameters of the types it applies, and
+///     grows when it's only part of a type argument (ie. `T` into `(Box (ref T))`)
+/// - Copies never stop if a parameter can flow back into itself after growing
+///
+fn find_expanding_generics(generics: &HashMap<String, Class>) -> Vec<String>
+{
+    // Edges from (type, parameter index) to (type, parameter index), and if the parameter grows
+
+    let mut edges: Vec<((String, usize), (String, usize), bool)> = Vec::new();
+
+    for (name, generic) in generics.iter()
+    {
+        let parameters: Vec<String> = generic
+            .get_parameters()
+            .iter()
+            .map(|parameter| parameter.get_name().clone())
+            .collect();
+
+        let mut applications = Vec::new();
+        generic.clone().to_node().map_all_types(&mut |t| {
+            get_applications(t, &mut applications);
+            t
+        });
+
+        for application in applications
+        {
+            if !generics.contains_key(application.get_name())
+            {
+                continue;
+            }
+
+            for (j, argument) in application.get_arguments().iter().enumerate()
+            {
+                let mut names = Vec::new();
+                get_named_types(*argument, &mut names);
+
+                for (i, parameter) in parameters.iter().enumerate()
+                {
+                    if !names.contains(parameter)
+                    {
+                        continue;
+                    }
+
+                    let is_growing = match argument.get()
+                    {
+                        Type::Instance(instance) => instance.get_name() != *parameter,
+                        _ => true,
+                    };
+
+                    let to = (application.get_name().clone(), j);
+                    edges.push(((name.clone(), i), to, is_growing));
+                }
+            }
+        }
+    }
+
+    let is_reachable = |from: &(String, usize), to: &(String, usize)| {
+        let mut visited = Vec::new();
+        let mut pending = vec![from.clone()];
+
+        while let Some(current) = pending.pop()
+        {
+            if current == *to
+            {
+                return true;
+            }
+            if !visited.contains(&current)
+            {
+                for (edge_from, edge_to, _) in edges.iter()
+                {
+                    if *edge_from == current
+                    {
+                        pending.push(edge_to.clone());
+                    }
+                }
+                visited.push(current);
+            }
+        }
+
+        false
+    };
+
+    let mut names = Vec::new();
+    for (from, to, is_growing) in edges.iter()
+    {
+        if *is_growing && is_reachable(to, from) && !names.contains(&from.0)
+        {
+            names.push(from.0.clone());
+        }
+    }
+
+    names
+}
+
+///
+/// Get every type application in a type, including those nested in other applications
+///
+fn get_applications(t: TypeId, applications: &mut Vec<ApplicationType>)
+{
+    match t.get()
+    {
+        Type::Application(application) =>
+        {
+            for argument in application.get_arguments()
+            {
+                get_applications(*argument, applications);
+            }
+            applications.push(application);
+        }
+        Type::Reference(reference) => get_applications(reference.get_target(), applications),
+        Type::Array(array) => get_applications(array.get_element(), applications),
+        Type::Function(function) =>
+        {
+            for argument in function.get_arguments()
+            {
+                get_applications(*argument, applications);
+            }
+            get_applications(function.get_return_type(), applications);
+        }
+        _ => (),
+    }
+}
+
+///
+/// Rename variables that name an alias of an instance type to the aliased type
+///
+fn rename_alias_variables(node: &mut Node, aliases: &HashMap<String, TypeId>)
+{
+    if let Node::Variable(variable) = node
+    {
+        let aliased_name = match aliases.get(variable.get_name())
+        {
+            Some(aliased_type) => match &aliased_type.get()
+            {
+                Type::Instance(instance) => Some(instance.get_name()),
+                _ => None,
+            },
+            None => None,
+        };
+
+        if let Some(aliased_name) = aliased_name
+        {
+            *variable.get_name_mut() = aliased_name;
+        }
+    }
+
+    for child in node.get_children_mut()
+    {
+        rename_alias_variables(child, aliases);
+    }
+}
+
+///
+/// Get the index to insert a copy of a parameterized type at, after the parameterized type and any
+///     types named in its arguments
+///
+fn get_instance_index(nodes: &Vec<Node>, instance: &Instance) -> usize
+{
+    let mut names = vec![instance.generic.clone()];
+    for argument in instance.arguments.iter()
+    {
+        get_instance_names(&argument.get(), &mut names);
+    }
+
+    nodes
+        .iter()
+        .enumerate()
+        .filter_map(|(i, node)| match node
+        {
+            Node::Class(class) if names.contains(class.get_name()) => Some(i + 1),
+            _ => None,
+        })
+        .max()
+        .unwrap_or(nodes.len())
+}
+
+fn get_instance_names(t: &Type, names: &mut Vec<String>)
+{
+    match t
+    {
+        Type::Instance(instance) => names.push(instance.get_name()),
+        Type::Reference(reference) => get_instance_names(&reference.get_target().get(), names),
+        Type::Array(array) => get_instance_names(&array.get_element().get(), names),
+        _ => (),
+    }
+}
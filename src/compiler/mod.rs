@@ -7,7 +7,13 @@ mod common;
 
 // Passes
 
+mod instantiate_types;
+mod resolve_traits;
 mod type_system;
+mod check_initialization;
+mod monomorphize;
+mod explicate_enumerations;
+mod convert_closures;
 
 mod flatten_bindings;
 mod flatten_definitions;
@@ -20,61 +26,144 @@ mod explicate_returns;
 mod c_convert;
 mod c_convert_names;
 
+///
+/// Apply a list of passes to a node, returning any errors from the enclosing function
+///
+macro_rules! passes
+{
+    {
+        ($node:ident, $errors:ident, $warnings:ident, $print_passes:expr)
+        {
+            $($name:expr => $pass:expr,)+
+        }
+    } =>
+    {
+        $(
+            match $pass.apply(&mut $node)
+            {
+                ResultLog::Ok(()) => (),
+                ResultLog::Warn((), mut new_warnings) => $warnings.append(&mut new_warnings),
+                ResultLog::Error(mut new_errors, mut new_warnings) =>
+                {
+                    $errors.append(&mut new_errors);
+                    $warnings.append(&mut new_warnings);
+                    return ResultLog::Error($errors, $warnings);
+                }
+            }
+
+            if $print_passes
+            {
+                println!("# Pass {}\n\t{}\n", $name, $node);
+            }
+        )+
+    };
+}
+
 ///
 /// A compiler instance with associated configuration, etc.
 ///
-pub struct Compiler {}
+/// - The result of each pass is only printed if `print_passes` is turned on
+/// - Programs must result in an int or bool, which is returned from main in C, unless
+///     `allow_any_result` is turned on (ie. for inputs that are only interpreted)
+/// - Types are made in the type context entered on the current thread, which should be the same
+///     one the nodes were parsed in (see TypeContext::enter)
+///
+pub struct Compiler
+{
+    print_passes:     bool,
+    allow_any_result: bool,
+}
 
 impl Compiler
 {
     pub fn new() -> Compiler
     {
-        Compiler {}
+        Compiler {
+            print_passes:     false,
+            allow_any_result: false,
+        }
+    }
+
+    set!(set_print_passes -> print_passes : bool);
+    set!(set_allow_any_result -> allow_any_result : bool);
+
+    ///
+    /// Compile a node to C, running all passes
+    ///
+    pub fn compile_c(&self, node: Node) -> ResultLog<CNode, Error>
+    {
+        self.check(node)
+            .and_then(|node| self.flatten(node))
+            .and_then(|node| self.convert_c(node))
     }
 
-    pub fn compile_c(&self, mut node: Node) -> ResultLog<CNode, Error>
+    ///
+    /// Infer and check the types of a node, without flattening it
+    ///
+    /// - The resulting node can be evaluated by the interpreter, or converted to C
+    ///
+    pub fn check(&self, mut node: Node) -> ResultLog<Node, Error>
     {
         let mut errors = Vec::new();
         let mut warnings = Vec::new();
 
-        macro_rules! passes
-        {
-            {
-                $($name:expr => $pass:expr,)+
-            } =>
+        passes! {
+            (node, errors, warnings, self.print_passes)
             {
-                $(
-                    match $pass.apply(&mut node)
-                    {
-                        ResultLog::Ok(()) => (),
-                        ResultLog::Warn((), mut new_warnings) => warnings.append(&mut new_warnings),
-                        ResultLog::Error(mut new_errors, mut new_warnings) =>
-                        {
-                            errors.append(&mut new_errors);
-                            warnings.append(&mut new_warnings);
-                            return ResultLog::Error(errors, warnings);
-                        }
-                    }
-
-                    println!("# Pass {}\n\t{}\n", $name, node);
-                )+
-            };
+                "InstantiateTypes"      => instantiate_types::InstantiateTypes::new(),
+                "ResolveTraits"         => resolve_traits::ResolveTraits::new(),
+                "InferTypes"            => type_system::Infer::new(),
+                "CheckTypes"            => type_system::Check::new(self.allow_any_result),
+                "CheckVisibility"       => type_system::CheckVisibility::new(),
+                "CheckInitialization"   => check_initialization::CheckInitialization::new(),
+            }
         }
 
+        ResultLog::maybe_warn(node, warnings)
+    }
+
+    ///
+    /// Lower a checked node (see `check`) to the flat subset of nodes that can be converted to C
+    ///
+    pub fn flatten(&self, mut node: Node) -> ResultLog<Node, Error>
+    {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+
         passes! {
-            "InferTypes"         => type_system::Infer::new(),
-            "CheckTypes"         => type_system::Check::new(),
+            (node, errors, warnings, self.print_passes)
+            {
+                "Monomorphize"          => monomorphize::Monomorphize::new(),
+                "ExplicateEnumerations" => explicate_enumerations::ExplicateEnumerations::new(),
+                "ConvertClosures"       => convert_closures::ConvertClosures::new(),
 
-            "FlattenNames"       => flatten_names::FlattenNames::new(),
-            "FlattenDefinitions" => flatten_definitions::FlattenDefinitions::new(),
-            "FlattenOperands"    => flatten_operands::FlattenOperands::new(),
-            "FlattenBindings"    => flatten_bindings::FlattenBindings::new(),
+                "FlattenNames"          => flatten_names::FlattenNames::new(),
+                "FlattenDefinitions"    => flatten_definitions::FlattenDefinitions::new(),
+                "FlattenOperands"       => flatten_operands::FlattenOperands::new(),
+                "FlattenBindings"       => flatten_bindings::FlattenBindings::new(),
 
-            "ExplicateMain"      => explicate_main::ExplicateMain::new("__SpecsMain__"),
-            "ExplicateReturns"   => explicate_returns::ExplicateReturns::new(),
+                "ExplicateMain"         => explicate_main::ExplicateMain::new("__SpecsMain__"),
+                "ExplicateReturns"      => explicate_returns::ExplicateReturns::new(),
+            }
+        }
+
+        ResultLog::maybe_warn(node, warnings)
+    }
 
-            "CConvertNames"      => c_convert_names::ConvertNames::new(),
-            "CConvert"           => c_convert::Convert::new(),
+    ///
+    /// Convert a flattened node (see `flatten`) to C
+    ///
+    pub fn convert_c(&self, mut node: Node) -> ResultLog<CNode, Error>
+    {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+
+        passes! {
+            (node, errors, warnings, self.print_passes)
+            {
+                "CConvertNames"         => c_convert_names::ConvertNames::new(),
+                "CConvert"              => c_convert::Convert::new(),
+            }
         }
 
         match node
@@ -0,0 +1,346 @@
+use super::common::*;
+use super::type_system::{Check, Infer};
+
+///
+/// ## Monomorphize Pass
+///
+/// - Creates a copy of each generic function (ie. one with `is Trait` arguments) for each set of
+///     concrete argument types it is called with
+///     - ex. `(PrintSum (ref a))` with `a : Sum3` calls `PrintSum/Sum3`, a copy of PrintSum with
+///         `x : (ref Sum3)`
+///     - Copies are named after the generic function and the concrete types of its generic arguments
+///     - Copies are placed next to the generic function, so they're visible in the same scope
+/// - Copies are re-inferred, so methods called on generic arguments call the concrete type's method
+///     - Copies can call other generic functions, which are instantiated in turn
+/// - Generic functions are removed once all copies have been made
+///
+pub struct Monomorphize {}
+
+impl Monomorphize
+{
+    pub fn new() -> Monomorphize
+    {
+        Monomorphize {}
+    }
+}
+
+///
+/// Pass state for Monomorphize
+///
+/// - Track if the current node is the root node, which is where all the work is done
+///
+pub struct PassState
+{
+    pub is_root: bool,
+}
+
+impl RecurTransform<Node, PassState, Error> for Monomorphize
+{
+    fn get_root_state(&mut self, _node: &Node) -> PassState
+    {
+        PassState { is_root: true }
+    }
+
+    fn get_child_states(&mut self, _state: &PassState, _node: &Node) -> Vec<ChildState<PassState>>
+    {
+        vec![ChildState::New(PassState { is_root: false })]
+    }
+
+    fn enter(&mut self, node: &mut Node, state: &mut PassState) -> ResultLog<(), Error>
+    {
+        if !state.is_root
+        {
+            return ResultLog::Ok(());
+        }
+
+        let mut warnings = Vec::new();
+        let generics = get_generic_functions(node);
+
+        if generics.is_empty()
+        {
+            return ResultLog::Ok(());
+        }
+
+        // Keep instantiating until copies stop calling new instances
+
+        let mut instantiated: Vec<String> = Vec::new();
+        loop
+        {
+            let mut calls = Vec::new();
+            rename_generic_calls(node, &generics, &mut calls);
+
+            let mut instances: Vec<Instance> = Vec::new();
+            for instance in calls
+            {
+                let is_new = !instantiated.contains(&instance.name)
+                    && !instances.iter().any(|other| other.name == instance.name);
+
+                if is_new
+                {
+                    instances.push(instance);
+                }
+            }
+
+            if instances.is_empty()
+            {
+                break;
+            }
+
+            for instance in instances
+            {
+                if !instantiate(node, &instance)
+                {
+                    return ResultLog::new_error(Error::Internal(format!(
+                        "Failed to find generic function for instance: {}",
+                        instance.name
+                    )));
+                }
+
+                instantiated.push(instance.name);
+            }
+
+            // Infer the types of the new copies
+
+            match Infer::new().apply(node)
+            {
+                ResultLog::Ok(()) => (),
+                ResultLog::Warn((), mut new_warnings) => warnings.append(&mut new_warnings),
+                ResultLog::Error(errors, mut new_warnings) =>
+                {
+                    warnings.append(&mut new_warnings);
+                    return ResultLog::Error(errors, warnings);
+                }
+            }
+        }
+
+        remove_generic_functions(node, &generics);
+
+        // Check the types of the new copies (the result of the program was already checked)
+
+        match Check::new(true).apply(node)
+        {
+            ResultLog::Ok(()) => (),
+            ResultLog::Warn((), mut new_warnings) => warnings.append(&mut new_warnings),
+            ResultLog::Error(errors, mut new_warnings) =>
+            {
+                warnings.append(&mut new_warnings);
+                return ResultLog::Error(errors, warnings);
+            }
+        }
+
+        ResultLog::maybe_error((), warnings, Vec::new())
+    }
+}
+
+///
+/// A concrete copy of a generic function
+///
+struct Instance
+{
+    name:           String,
+    generic:        Generic,
+    argument_types: Vec<TypeId>,
+}
+
+///
+/// The name and type of a generic function definition
+///
+/// - Functions with the same signature share a type, so generic functions are told apart by name
+///
+type Generic = (String, TypeId);
+
+///
+/// Get the names and types of all generic function definitions
+///
+fn get_generic_functions(node: &Node) -> Vec<Generic>
+{
+    let mut generics = Vec::new();
+
+    if let Node::Function(function) = node
+    {
+        if function.get_type().get().is_generic()
+        {
+            generics.push((function.get_name().clone(), function.get_type()));
+        }
+    }
+
+    for child in node.get_children()
+    {
+        generics.append(&mut get_generic_functions(child));
+    }
+
+    generics
+}
+
+///
+/// Rename all calls to generic functions with concrete operands to the name of the matching
+///     instance, tracking each instance that is needed
+///
+fn rename_generic_calls(node: &mut Node, generics: &Vec<Generic>, instances: &mut Vec<Instance>)
+{
+    for child in node.get_children_mut()
+    {
+        rename_generic_calls(child, generics, instances);
+    }
+
+    let call = match node
+    {
+        Node::Call(call) => call,
+        _ => return,
+    };
+
+    let operand_types: Vec<TypeId> = call
+        .get_operands()
+        .iter()
+        .map(|operand| operand.get_type())
+        .collect();
+
+    if operand_types.iter().any(|t| t.get().is_generic())
+    {
+        // Calls from inside generic functions are instantiated along with their function
+        return;
+    }
+
+    let variable = match call.get_operator_mut()
+    {
+        Node::Variable(variable) => variable,
+        _ => return,
+    };
+
+    let generic = (variable.get_name().clone(), variable.get_type());
+    if !generics.contains(&generic)
+    {
+        return;
+    }
+
+    // Use the operand type in place of each generic argument type
+
+    let mut argument_types = Vec::new();
+    let mut name = variable.get_name().clone();
+
+    if let Type::Function(function) = generic.1.get()
+    {
+        for (argument_type, operand_type) in function.get_arguments().iter().zip(operand_types)
+        {
+            match argument_type.get().is_generic()
+            {
+                true =>
+                {
+                    name = format!("{}/{}", name, get_type_name(&operand_type.get()));
+                    argument_types.push(operand_type);
+                }
+                false => argument_types.push(*argument_type),
+            }
+        }
+    }
+
+    *variable.get_name_mut() = name.clone();
+
+    instances.push(Instance {
+        name,
+        generic,
+        argument_types,
+    });
+}
+
+///
+/// Get the name of a type to use in the name of an instance
+///
+fn get_type_name(t: &Type) -> String
+{
+    match t
+    {
+        Type::Reference(reference) => get_type_name(&reference.get_target().get()),
+        Type::Array(array) => format!(
+            "{}-{}",
+            symbols::keywords::ARRAY,
+            get_type_name(&array.get_element().get())
+        ),
+        Type::Instance(instance) => instance.get_name(),
+        t => format!("{}", t),
+    }
+}
+
+///
+/// Copy the generic function of an instance and place it after the original, returning true if the
+///     generic function was found
+///
+fn instantiate(node: &mut Node, instance: &Instance) -> bool
+{
+    if let Node::Sequence(sequence) = node
+    {
+        let nodes = sequence.get_nodes_mut();
+        let generic_index = nodes.iter().position(|node| match node
+        {
+            Node::Function(function) =>
+            {
+                *function.get_name() == instance.generic.0
+                    && function.get_type() == instance.generic.1
+            }
+            _ => false,
+        });
+
+        if let Some(index) = generic_index
+        {
+            let mut copy = nodes[index].clone();
+            if let Node::Function(function) = &mut copy
+            {
+                *function.get_name_mut() = instance.name.clone();
+                function.set_argument_types(instance.argument_types.clone());
+                reset_operator_types(function.get_body_mut());
+            }
+
+            nodes.insert(index + 1, copy);
+            return true;
+        }
+    }
+
+    node.get_children_mut()
+        .into_iter()
+        .any(|child| instantiate(child, instance))
+}
+
+///
+/// Clear the inferred types of primitive operators so they are inferred again from the new operand
+///     types (other node types are always re-inferred)
+///
+fn reset_operator_types(node: &mut Node)
+{
+    if let Node::PrimitiveOperator(operator) = node
+    {
+        match operator.get_value()
+        {
+            Operator::And | Operator::Or | Operator::ExclusiveOr | Operator::Not => (),
+            _ => operator.set_type(basic_types::interned::unknown()),
+        }
+    }
+
+    for child in node.get_children_mut()
+    {
+        reset_operator_types(child);
+    }
+}
+
+///
+/// Remove all generic function definitions
+///
+fn remove_generic_functions(node: &mut Node, generics: &Vec<Generic>)
+{
+    if let Node::Sequence(sequence) = node
+    {
+        sequence.get_nodes_mut().retain(|node| match node
+        {
+            Node::Function(function) =>
+            {
+                let function = (function.get_name().clone(), function.get_type());
+                !generics.contains(&function)
+            }
+            _ => true,
+        });
+    }
+
+    for child in node.get_children_mut()
+    {
+        remove_generic_functions(child, generics);
+    }
+}
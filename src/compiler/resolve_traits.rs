@@ -0,0 +1,155 @@
+use super::common::*;
+
+///
+/// ## Resolve Traits Pass
+///
+/// - Imports the instance data members and instance methods of trait types into the types that
+///     implement them
+///     - ex. `type Sum3 { is Summable {...} }` gets all members of Summable, along with all methods
+///         of Summable that aren't replaced by its implementation block
+///     - Imported methods are copied, so they call the implementing type's versions of other methods
+/// - Traits of trait types are also traits of their implementors (ie. Sum4 is Sum3 is Summable)
+/// - Built-in traits (ie. PassByValue) don't have any members to import
+///
+pub struct ResolveTraits {}
+
+impl ResolveTraits
+{
+    pub fn new() -> ResolveTraits
+    {
+        ResolveTraits {}
+    }
+}
+
+pub struct PassState {}
+
+impl RecurTransform<Node, PassState, Error> for ResolveTraits
+{
+    fn get_root_state(&mut self, _node: &Node) -> PassState
+    {
+        PassState {}
+    }
+
+    fn enter(&mut self, node: &mut Node, _state: &mut PassState) -> ResultLog<(), Error>
+    {
+        match node
+        {
+            Node::Sequence(sequence) =>
+            {
+                // Trait types must be defined in the same sequence as the types that use them
+
+                let nodes = sequence.get_nodes_mut();
+                let mut is_resolved = vec![false; nodes.len()];
+                let mut errors = Vec::new();
+
+                for i in 0..nodes.len()
+                {
+                    if let Node::Class(_) = &nodes[i]
+                    {
+                        if let Err(error) = resolve_class(nodes, i, &mut is_resolved, &mut Vec::new())
+                        {
+                            errors.push(error);
+                        }
+                    }
+                }
+
+                ResultLog::maybe_error((), Vec::new(), errors)
+            }
+            _ => ResultLog::Ok(()),
+        }
+    }
+}
+
+///
+/// Import members from all trait types of the class at the given index, resolving the traits of
+///     those trait types first
+///
+fn resolve_class(
+    nodes: &mut Vec<Node>,
+    index: usize,
+    is_resolved: &mut Vec<bool>,
+    resolving: &mut Vec<String>,
+) -> Result<(), Error>
+{
+    if is_resolved[index]
+    {
+        return Ok(());
+    }
+
+    let (name, traits, source) = match &nodes[index]
+    {
+        Node::Class(class) => (
+            class.get_name().clone(),
+            class.get_traits().clone(),
+            class.get_source(),
+        ),
+        node => return Err(Error::Internal(format!("Expected class, found: {}", node))),
+    };
+
+    if resolving.contains(&name)
+    {
+        return Err(Error::RecursiveTrait(name, source));
+    }
+    resolving.push(name);
+
+    for trait_name in traits
+    {
+        if symbols::traits::contains(&trait_name)
+        {
+            continue;
+        }
+
+        let trait_index = nodes.iter().position(|node| match node
+        {
+            Node::Class(class) => *class.get_name() == trait_name,
+            _ => false,
+        });
+        let trait_index = match trait_index
+        {
+            Some(trait_index) => trait_index,
+            None => return Err(Error::UnknownTrait(trait_name, source)),
+        };
+
+        resolve_class(nodes, trait_index, is_resolved, resolving)?;
+
+        let trait_class = match &nodes[trait_index]
+        {
+            Node::Class(class) => class.clone(),
+            _ => unreachable!(),
+        };
+
+        if let Node::Class(class) = &mut nodes[index]
+        {
+            // Make sure implementation blocks only replace methods that exist in the trait
+
+            for method in class.get_methods()
+            {
+                if method.get_implements().as_ref() != Some(&trait_name)
+                {
+                    continue;
+                }
+
+                let is_implemented = trait_class.get_methods().iter().any(|trait_method| {
+                    trait_method.get_name() == method.get_name()
+                        && trait_method.get_scope() == method.get_scope()
+                });
+
+                if !is_implemented
+                {
+                    return Err(Error::UnknownTraitMethod(
+                        trait_name,
+                        method.get_name().clone(),
+                        method.get_source(),
+                    ));
+                }
+            }
+
+            class.inherit(&trait_class);
+        }
+    }
+
+    resolving.pop();
+    is_resolved[index] = true;
+
+    Ok(())
+}
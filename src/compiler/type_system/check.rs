@@ -1,23 +1,80 @@
 use super::super::common::*;
 use super::Check;
 
-pub struct State {}
+use std::collections::{HashMap, HashSet};
+
+///
+/// Pass state for Check
+///
+/// - Tracks where jumps can go (the return type of the enclosing function, and if there is an
+///     enclosing loop)
+/// - Tracks if the current node is the whole program, whose result is printed by main
+///
+pub struct State
+{
+    return_type: Option<TypeId>,
+    is_in_loop:  bool,
+    is_root:     bool,
+}
+
+impl State
+{
+    pub fn new(return_type: Option<TypeId>, is_in_loop: bool) -> State
+    {
+        State {
+            return_type,
+            is_in_loop,
+            is_root: false,
+        }
+    }
+}
 
 impl RecurTransform<Node, State, Error> for Check
 {
     fn get_root_state(&mut self, _root: &Node) -> State
     {
-        State {}
+        State {
+            is_root: true,
+            ..State::new(None, false)
+        }
     }
 
-    fn exit(&mut self, node: &mut Node, _state: &mut State) -> ResultLog<(), Error>
+    fn get_child_states(&mut self, state: &State, node: &Node) -> Vec<ChildState<State>>
+    {
+        match node
+        {
+            Node::Function(function) =>
+            {
+                // Jumps can't leave a function, so loops outside it don't matter
+
+                vec![ChildState::New(State::new(Some(function.get_return_type()), false))]
+            }
+            Node::Closure(closure) =>
+            {
+                vec![ChildState::New(State::new(Some(closure.get_return_type()), false))]
+            }
+            Node::WhileLoop(_) =>
+            {
+                // Only the body is inside the loop, since the condition is checked outside it
+
+                let return_type = state.return_type;
+                vec![
+                    ChildState::New(State::new(return_type, false)),
+                    ChildState::New(State::new(return_type, false)),
+                    ChildState::New(State::new(return_type, true)),
+                ]
+            }
+            _ => vec![ChildState::New(State::new(state.return_type, state.is_in_loop))],
+        }
+    }
+
+    fn exit(&mut self, node: &mut Node, state: &mut State) -> ResultLog<(), Error>
     {
         match node
         {
             Node::Call(call) =>
             {
-                let operator_type_indirect = call.get_operator().get_type();
-                let operator_type = operator_type_indirect.borrow();
+                let operator_type = call.get_operator().get_type().get();
 
                 let operand_types: Vec<_> = call
                     .get_operands()
@@ -25,16 +82,24 @@ impl RecurTransform<Node, State, Error> for Check
                     .map(|operand| operand.get_type())
                     .collect();
 
-                match &*operator_type
+                match &operator_type
                 {
                     Type::Function(function) =>
                     {
+                        // Methods called on bounded types don't have their self argument until
+                        //  they are monomorphized
+
+                        let arguments = match is_bounded_method_call(call)
+                        {
+                            true => &function.get_arguments()[1..],
+                            false => &function.get_arguments()[..],
+                        };
+
                         // Check that the number of operands matches the number of function arguments
 
-                        if function.get_arguments().len() != operand_types.len()
+                        if arguments.len() != operand_types.len()
                         {
-                            let argument_types =
-                                function.get_arguments().iter().map(|t| t.clone()).collect();
+                            let argument_types = arguments.to_vec();
                             let error = Error::BadOperandTypes(
                                 operand_types,
                                 argument_types,
@@ -47,13 +112,12 @@ impl RecurTransform<Node, State, Error> for Check
 
                         let mut operands_match_arguments = true;
 
-                        for (t_operand, t_argument) in
-                            operand_types.iter().zip(function.get_arguments().iter())
+                        for (t_operand, t_argument) in operand_types.iter().zip(arguments.iter())
                         {
-                            let t_operand = t_operand.borrow();
-                            let t_argument = t_argument.borrow();
+                            let t_operand = t_operand.get();
+                            let t_argument = t_argument.get();
 
-                            if &*t_operand != &*t_argument
+                            if !t_operand.satisfies(&t_argument)
                             {
                                 operands_match_arguments = false;
                                 break;
@@ -62,8 +126,7 @@ impl RecurTransform<Node, State, Error> for Check
 
                         if !operands_match_arguments
                         {
-                            let argument_types =
-                                function.get_arguments().iter().map(|t| t.clone()).collect();
+                            let argument_types = arguments.to_vec();
                             let error = Error::BadOperandTypes(
                                 operand_types,
                                 argument_types,
@@ -75,23 +138,464 @@ impl RecurTransform<Node, State, Error> for Check
                     _ =>
                     {
                         let error = Error::BadOperatorType(
-                            operator_type_indirect.clone(),
+                            call.get_operator().get_type(),
                             call.get_source(),
                         );
                         return ResultLog::new_error(error);
                     }
                 }
             }
-            Node::Assign(_) =>
-            {}
-            Node::Sequence(_) =>
-            {}
-            Node::Conditional(_) =>
-            {}
+            Node::Assign(assign) =>
+            {
+                // Values can only be written through mutable references
+
+                if !is_writable(assign.get_lhs())
+                {
+                    let error = Error::ImmutableAssign(assign.get_source());
+                    return ResultLog::new_error(error);
+                }
+            }
+            Node::Reference(reference) if reference.get_mode() == ReferenceMode::Mutable =>
+            {
+                // Mutable references can be written through, so they can't be taken through
+                //  immutable ones either
+
+                if !is_writable(reference.get_target())
+                {
+                    let error = Error::ImmutableReference(reference.get_source());
+                    return ResultLog::new_error(error);
+                }
+            }
+            Node::Binding(binding) =>
+            {
+                if binding.get_binding().get_type().get().is_never()
+                {
+                    let error = Error::NeverBinding(binding.get_name().clone(), binding.get_source());
+                    return ResultLog::new_error(error);
+                }
+            }
+            Node::Sequence(sequence) if state.is_root =>
+            {
+                // The result of the program is returned from main as an int (booleans are ints in
+                //  C, and nothing is returned if there's no result)
+
+                let result_type = sequence.get_type();
+                let is_printable = match result_type.get()
+                {
+                    Type::Integer | Type::Boolean | Type::Void | Type::Never => true,
+                    _ => false,
+                };
+
+                let mut errors = find_recursive_classes(sequence.get_nodes());
+                if !is_printable && !self.allow_any_result
+                {
+                    let source = match sequence.get_nodes().last()
+                    {
+                        Some(last) => last.get_source(),
+                        None => sequence.get_source(),
+                    };
+                    errors.push(Error::BadResultType(result_type, source));
+                }
+                return ResultLog::maybe_error((), Vec::new(), errors);
+            }
+            Node::Sequence(sequence) =>
+            {
+                // Types are stored inside of each other as C structs, so they can't contain
+                //  themselves except through a reference
+
+                let errors = find_recursive_classes(sequence.get_nodes());
+                return ResultLog::maybe_error((), Vec::new(), errors);
+            }
+            Node::Conditional(conditional) =>
+            {
+                // Both branches give the result, unless one always jumps away or there is no else
+
+                if let Node::Nothing(_) = conditional.get_else()
+                {
+                    return ResultLog::Ok(());
+                }
+
+                let then_type = conditional.get_then().get_type().get();
+                let else_type = conditional.get_else().get_type().get();
+
+                let is_mismatched = !then_type.is_never()
+                    && !else_type.is_never()
+                    && !then_type.satisfies(&else_type);
+
+                if is_mismatched
+                {
+                    let error = Error::BadBranchTypes(
+                        conditional.get_then().get_type(),
+                        conditional.get_else().get_type(),
+                        conditional.get_source(),
+                    );
+                    return ResultLog::new_error(error);
+                }
+            }
+            Node::WhileLoop(while_loop) =>
+            {
+                // Loop conditions must be booleans
+
+                let condition_type = while_loop.get_condition().get_type();
+                if !condition_type.get().satisfies(&Type::Boolean)
+                {
+                    let error = Error::BadConditionType(condition_type, while_loop.get_source());
+                    return ResultLog::new_error(error);
+                }
+            }
+            Node::Jump(jump) => match (jump.get_mode(), &state.return_type)
+            {
+                (JumpMode::Return, Some(return_type)) =>
+                {
+                    // Returned values must match the enclosing function's return type
+
+                    let value_type = jump.get_value().get_type();
+                    if !value_type.get().satisfies(&return_type.get())
+                    {
+                        let error =
+                            Error::BadReturnType(value_type, *return_type, jump.get_source());
+                        return ResultLog::new_error(error);
+                    }
+                }
+                (JumpMode::Return, None) =>
+                {
+                    return ResultLog::new_error(Error::ReturnOutsideFunction(jump.get_source()));
+                }
+                (mode, _) if !state.is_in_loop =>
+                {
+                    let error = Error::JumpOutsideLoop(format!("{}", mode), jump.get_source());
+                    return ResultLog::new_error(error);
+                }
+                _ => (),
+            },
             Node::Function(_) =>
             {}
+            Node::Closure(closure) =>
+            {
+                // Captured bindings are copied into the closure, so assigning to them would only
+                //  change the copy
+
+                let mut bound = closure
+                    .get_arguments()
+                    .iter()
+                    .map(|argument| argument.get_name().clone())
+                    .collect();
+
+                if let Some((name, source)) = find_captured_assign(closure.get_body(), &mut bound)
+                {
+                    return ResultLog::new_error(Error::CapturedAssign(name, source));
+                }
+            }
+            Node::Class(class) =>
+            {
+                // Check that member and method names are unique within the class
+
+                if let Type::Class(class_type) = class.get_type().get()
+                {
+                    let mut names = std::collections::HashSet::new();
+
+                    let members = class_type.get_members();
+                    let methods = class_type.get_methods();
+
+                    let member_names = members.iter().map(|m| m.get_name());
+                    let method_names = methods.iter().map(|m| m.get_name());
+
+                    for name in member_names.chain(method_names)
+                    {
+                        if !names.insert(name)
+                        {
+                            let error = Error::DuplicateMember(
+                                class.get_name().clone(),
+                                name.clone(),
+                                class.get_source(),
+                            );
+                            return ResultLog::new_error(error);
+                        }
+                    }
+                }
+            }
             _ => (),
         }
         ResultLog::Ok(())
     }
 }
+
+///
+/// Check if a call is to an instance method accessed from a bounded type (ie. `(x.Sum)` where x is
+///     `is Summable`)
+///
+fn is_bounded_method_call(call: &Call) -> bool
+{
+    match call.get_operator()
+    {
+        Node::Access(access) =>
+        {
+            let target_type = access.get_target().get_type();
+            let target_type = match target_type.get().dereference()
+            {
+                Some(t) => t,
+                None => target_type,
+            };
+
+            match target_type.get()
+            {
+                Type::Bounded(_) => true,
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+///
+/// Check if the place a node names can be written to
+///
+/// - Dereferences can only be written to if they dereference a mutable reference
+/// - Members are written to through their instance, so every reference to the instance has to be
+///     mutable, or the instance has to be a place that can be written to itself
+/// - Array elements are written to through their array in the same way
+///
+fn is_writable(node: &Node) -> bool
+{
+    match node
+    {
+        Node::Dereference(dereference) => match dereference.get_target().get_type().get()
+        {
+            Type::Reference(reference) => reference.get_mode() == ReferenceMode::Mutable,
+            _ => true,
+        },
+        Node::Access(access) =>
+        {
+            let target = access.get_target();
+            match target.get_type().get()
+            {
+                Type::Reference(_) => is_mutable_reference(target.get_type()),
+                _ => is_writable(target),
+            }
+        }
+        Node::Call(call) if is_array_at(call) =>
+        {
+            let array = &call.get_operands()[0];
+            match array.get_type().get()
+            {
+                Type::Reference(_) => is_mutable_reference(array.get_type()),
+                _ => is_writable(array),
+            }
+        }
+        _ => true,
+    }
+}
+
+fn is_array_at(call: &Call) -> bool
+{
+    match call.get_operator()
+    {
+        Node::PrimitiveOperator(operator) => operator.get_value() == primitive::Operator::ArrayAt,
+        _ => false,
+    }
+}
+
+///
+/// Check if a type is a reference whose targets are all mutable, all the way down to a value
+///
+fn is_mutable_reference(t: TypeId) -> bool
+{
+    match t.get()
+    {
+        Type::Reference(reference) => match reference.get_mode()
+        {
+            ReferenceMode::Mutable => match reference.get_target().get()
+            {
+                Type::Reference(_) => is_mutable_reference(reference.get_target()),
+                _ => true,
+            },
+            ReferenceMode::Immutable => false,
+        },
+        _ => false,
+    }
+}
+
+///
+/// Find the types defined in a sequence that contain themselves by value, giving an error for each
+///     group of types that contain each other
+///
+/// - Variants of an enumerated type are stored as the enumerated type, so the members of every
+///     variant are part of it
+/// - Types can only contain types defined in the same or an enclosing sequence, so containing
+///     each other means being defined in the same sequence
+///
+fn find_recursive_classes(nodes: &Vec<Node>) -> Vec<Error>
+{
+    let classes: Vec<(ClassType, Source)> = nodes
+        .iter()
+        .filter_map(|node| match node
+        {
+            Node::Class(class) => match class.get_type().get()
+            {
+                Type::Class(class_type) => Some((class_type, class.get_source())),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+
+    let enumerated = String::from(symbols::traits::ENUMERATED);
+
+    // Get the type each class is stored as
+
+    let get_storage = |class_type: ClassType| {
+        classes
+            .iter()
+            .map(|(other, _)| *other)
+            .find(|other| {
+                *other != class_type
+                    && other.get_traits().has(&enumerated)
+                    && class_type.get_traits().has(&other.get_name())
+            })
+            .unwrap_or(class_type)
+    };
+
+    // Get the types stored directly inside of each type stored as itself
+
+    let mut contents: HashMap<ClassType, Vec<ClassType>> = HashMap::new();
+    for (class_type, _) in classes.iter()
+    {
+        let storage = get_storage(*class_type);
+        let stored = contents.entry(storage).or_default();
+
+        for member in class_type.get_members()
+        {
+            if member.get_scope() != MemberScope::Instance
+            {
+                continue;
+            }
+
+            if let Type::Instance(instance) = member.get_type().get()
+            {
+                if let Some(member_class) = instance.get_class()
+                {
+                    stored.push(get_storage(member_class));
+                }
+            }
+        }
+    }
+
+    let get_reachable = |start: ClassType| {
+        let mut reachable = HashSet::new();
+        let mut pending = contents.get(&start).cloned().unwrap_or_default();
+
+        while let Some(class_type) = pending.pop()
+        {
+            if reachable.insert(class_type)
+            {
+                pending.extend(contents.get(&class_type).cloned().unwrap_or_default());
+            }
+        }
+
+        reachable
+    };
+
+    let mut reported: Vec<(ClassType, HashSet<ClassType>)> = Vec::new();
+    let mut errors = Vec::new();
+
+    for (class_type, source) in classes.iter()
+    {
+        if get_storage(*class_type) != *class_type
+        {
+            continue;
+        }
+
+        let reachable = get_reachable(*class_type);
+        if !reachable.contains(class_type)
+        {
+            continue;
+        }
+
+        let is_reported = reported.iter().any(|(other, other_reachable)| {
+            reachable.contains(other) && other_reachable.contains(class_type)
+        });
+
+        if !is_reported
+        {
+            errors.push(Error::RecursiveType(class_type.get_name(), source.clone()));
+            reported.push((*class_type, reachable));
+        }
+    }
+
+    errors
+}
+
+///
+/// Find an assignment in a closure body to a binding from outside of it, returning the name of
+///     the binding and where it was assigned
+///
+/// - Members of a captured instance are part of the copy too, but anything written through a
+///     reference is shared with the original
+/// - Nested closures and definitions are checked on their own
+///
+fn find_captured_assign(node: &Node, bound: &mut Vec<String>) -> Option<(String, Source)>
+{
+    match node
+    {
+        Node::Assign(assign) =>
+        {
+            if let Some(name) = get_assigned_binding(assign.get_lhs())
+            {
+                if !bound.contains(name)
+                {
+                    return Some((name.clone(), assign.get_source()));
+                }
+            }
+
+            find_captured_assign(assign.get_rhs(), bound)
+        }
+        Node::Binding(binding) =>
+        {
+            let result = find_captured_assign(binding.get_binding(), bound);
+            bound.push(binding.get_name().clone());
+
+            result
+        }
+        Node::Sequence(sequence) =>
+        {
+            // Bindings are only visible until the end of their sequence
+
+            let bound_count = bound.len();
+            let result = sequence
+                .get_nodes()
+                .iter()
+                .find_map(|child| find_captured_assign(child, bound));
+
+            if !sequence.is_transparent()
+            {
+                bound.truncate(bound_count);
+            }
+
+            result
+        }
+        Node::Closure(_) | Node::Function(_) | Node::Class(_) => None,
+        _ => node
+            .get_children()
+            .into_iter()
+            .find_map(|child| find_captured_assign(child, bound)),
+    }
+}
+
+///
+/// Get the name of the binding an assignment writes into, if it doesn't write through a reference
+///
+/// - Static members belong to their class rather than a binding
+///
+fn get_assigned_binding(node: &Node) -> Option<&String>
+{
+    match node
+    {
+        Node::Variable(variable) => Some(variable.get_name()),
+        Node::Access(access) => match access.get_target().get_type().get()
+        {
+            Type::Reference(_) | Type::Class(_) => None,
+            _ => get_assigned_binding(access.get_target()),
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,162 @@
+use super::super::common::*;
+use super::CheckVisibility;
+
+///
+/// Pass state for CheckVisibility
+///
+/// - Track the names of classes whose private members are visible (ie. we are inside a method)
+/// - Track if the current node is written to, either as the target of an assignment or through a
+///     mutable reference to it
+///
+pub struct State
+{
+    pub classes:         Vec<String>,
+    pub is_write_target: bool,
+}
+
+impl State
+{
+    pub fn new(classes: Vec<String>, is_write_target: bool) -> State
+    {
+        State {
+            classes,
+            is_write_target,
+        }
+    }
+}
+
+impl RecurTransform<Node, State, Error> for CheckVisibility
+{
+    fn get_root_state(&mut self, _root: &Node) -> State
+    {
+        State::new(Vec::new(), false)
+    }
+
+    fn get_child_states(&mut self, state: &State, node: &Node) -> Vec<ChildState<State>>
+    {
+        match node
+        {
+            Node::Class(class) =>
+            {
+                // Private members of a class are visible inside its methods
+
+                let mut classes = state.classes.clone();
+                classes.push(class.get_name().clone());
+
+                vec![ChildState::New(State::new(classes, false))]
+            }
+            Node::Assign(_) =>
+            {
+                // Only the left hand side of an assignment is written to
+
+                vec![
+                    ChildState::New(State::new(state.classes.clone(), true)),
+                    ChildState::New(State::new(state.classes.clone(), false)),
+                ]
+            }
+            Node::Reference(reference) if reference.get_mode() == ReferenceMode::Mutable =>
+            {
+                // Anything can be written through a mutable reference, including the receiver of
+                //  a method that takes a mutable self (ie. `(f.Method)` to `(Type.Method (mut f))`)
+
+                vec![ChildState::New(State::new(state.classes.clone(), true))]
+            }
+            Node::Access(access) =>
+            {
+                // Writing to a member writes to its instance too, unless it's behind a reference
+                //  (ie. `o.inner.n <- 5` writes to `o.inner`)
+
+                let is_write_target = match access.get_target().get_type().get()
+                {
+                    Type::Reference(_) => false,
+                    _ => state.is_write_target,
+                };
+
+                vec![ChildState::New(State::new(state.classes.clone(), is_write_target))]
+            }
+            _ => vec![ChildState::New(State::new(state.classes.clone(), false))],
+        }
+    }
+
+    fn exit(&mut self, node: &mut Node, state: &mut State) -> ResultLog<(), Error>
+    {
+        match node
+        {
+            Node::Access(access) =>
+            {
+                let target_type = access.get_target().get_type();
+                let instance_type = match target_type.get().dereference()
+                {
+                    Some(t) => t,
+                    None => target_type,
+                };
+
+                // Members of bounded types belong to one of their bounding types
+
+                let bound_type = match &instance_type.get()
+                {
+                    Type::Bounded(bounded) => bounded.get_bound_with_member(access.get_property()),
+                    _ => None,
+                };
+                let instance_type = match bound_type
+                {
+                    Some(t) => t,
+                    None => instance_type,
+                };
+
+                // Get the class and scope of the accessed member
+
+                let (class_type, scope) = match instance_type.get()
+                {
+                    Type::Class(_) => (instance_type, MemberScope::Static),
+                    Type::Instance(instance) => (instance.get_class_type(), MemberScope::Instance),
+                    _ => return ResultLog::Ok(()),
+                };
+
+                let class = match class_type.get()
+                {
+                    Type::Class(class) => class,
+                    _ => return ResultLog::Ok(()),
+                };
+
+                if state.classes.contains(&class.get_name())
+                {
+                    // Everything is visible from inside the class
+                    return ResultLog::Ok(());
+                }
+
+                let property = access.get_property();
+
+                let (read_visibility, write_visibility) = match class.get_member(property, scope)
+                {
+                    Some(member) => (member.get_read_visibility(), member.get_write_visibility()),
+                    None => match class.get_method(property)
+                    {
+                        Some(method) => (method.get_visibility(), Visibility::Private),
+                        None => return ResultLog::Ok(()),
+                    },
+                };
+
+                let error = match (state.is_write_target, read_visibility, write_visibility)
+                {
+                    (_, Visibility::Private, _) => Error::PrivateMember(
+                        class.get_name().clone(),
+                        property.clone(),
+                        access.get_source(),
+                    ),
+                    (true, Visibility::Public, Visibility::Private) => Error::ReadOnlyMember(
+                        class.get_name().clone(),
+                        property.clone(),
+                        access.get_source(),
+                    ),
+                    _ => return ResultLog::Ok(()),
+                };
+
+                return ResultLog::new_error(error);
+            }
+            _ => (),
+        }
+
+        ResultLog::Ok(())
+    }
+}
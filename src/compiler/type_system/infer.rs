@@ -1,14 +1,132 @@
 use super::super::common::*;
-use super::Infer;
+use super::{Infer, Unifier};
 
 ///
 /// Pass state for Infer
 ///
 /// - Maps symbol names to types, either newly created or referenced from other parts of the tree
 ///
-type State = BindingState<Indirect<Type>>;
+type State = BindingState<TypeId>;
 
-impl RecurTransform<Node, State, Error> for Infer
+impl Infer
+{
+    ///
+    /// Infer types in rounds, until no operators are deferred or a round doesn't solve anything
+    ///
+    /// - Only the last round's errors and warnings are kept, since earlier rounds can report
+    ///     operators that are inferred later
+    /// - Variables that are never solved are reported where they were made
+    ///
+    pub fn apply(mut self, root: &mut Node) -> ResultLog<(), Error>
+    {
+        let mut remaining = usize::MAX;
+
+        loop
+        {
+            self.deferred = 0;
+            let result = RecurTransform::apply(&mut self, root);
+
+            if self.deferred == 0 || self.is_last_round
+            {
+                let (mut errors, warnings) = match result
+                {
+                    ResultLog::Ok(()) => (Vec::new(), Vec::new()),
+                    ResultLog::Warn((), warnings) => (Vec::new(), warnings),
+                    ResultLog::Error(errors, warnings) => (errors, warnings),
+                };
+                errors.append(&mut self.errors);
+                errors.append(&mut self.unifier.take_unsolved());
+
+                // Fill in every variable in the tree with its solution, or the unknown type
+                root.map_all_types(&mut |t| self.unifier.resolve(t));
+
+                return ResultLog::maybe_error((), warnings, errors);
+            }
+
+            // Keep going while each round leaves fewer things to infer, then give it one last try
+
+            let new_remaining = self.deferred + self.unifier.count_unsolved();
+            if new_remaining >= remaining
+            {
+                self.is_last_round = true;
+            }
+            remaining = new_remaining;
+        }
+    }
+
+    ///
+    /// Unify two types, keeping any errors until the last round
+    ///
+    fn unify(&mut self, a: TypeId, b: TypeId, source: &Source)
+    {
+        if let Err(error) = self.unifier.unify(a, b, source)
+        {
+            self.errors.push(error);
+        }
+    }
+
+    ///
+    /// Start inferring the body of a function or closure with the given return type, and the error
+    ///     to report if it needs to be inferred but can't be
+    ///
+    fn enter_function(&mut self, return_type: TypeId, error: Error)
+    {
+        self.unifier.add_variable(return_type, error);
+        self.return_types.push(return_type);
+    }
+
+    ///
+    /// Finish inferring the body of a function or closure, solving its return type from the result
+    ///     of the body
+    ///
+    /// - Bodies that always jump away (ie. end with `return`) only return the values they return
+    ///
+    fn exit_function(&mut self, body: &Node)
+    {
+        let return_type = match self.return_types.pop()
+        {
+            Some(return_type) => return_type,
+            None => return,
+        };
+
+        let body_type = self.unifier.resolve(body.get_type());
+        if body_type.get().is_unknown()
+        {
+            // The body's type comes from an error that is reported elsewhere (ie. mismatched
+            //  branches), so the return type is left unknown without another error
+            self.unifier.dismiss_variable(return_type);
+        }
+        else if !body_type.get().is_never()
+        {
+            self.unify(body_type, return_type, &body.get_source());
+        }
+    }
+
+    ///
+    /// Check if a node's type, or the target of its reference type, is still a type variable
+    ///
+    /// - Nodes like this can't be used by operators that need to know their operand's type, so
+    ///     those operators are deferred until the last round
+    ///
+    fn should_defer(&mut self, node: &Node) -> bool
+    {
+        let node_type = self.unifier.resolve(node.get_type());
+        let target_type = match node_type.get().dereference()
+        {
+            Some(t) => t,
+            None => node_type,
+        };
+
+        let is_variable = target_type.get().is_variable();
+        if is_variable && !self.is_last_round
+        {
+            self.deferred += 1;
+        }
+        is_variable && !self.is_last_round
+    }
+}
+
+impl RecurTransform<Node, State, Error> for &mut Infer
 {
     fn get_root_state(&mut self, _node: &Node) -> State
     {
@@ -21,7 +139,7 @@ impl RecurTransform<Node, State, Error> for Infer
         {
             Node::Sequence(sequence) if !sequence.is_transparent() =>
             {
-                // Create a new scope with all this sequence's functions
+                // Create a new scope with all this sequence's functions and classes
                 //  Both bindings and definitions from the parent scope are visible
 
                 let mut new_state = State::empty(state, true, true);
@@ -29,6 +147,9 @@ impl RecurTransform<Node, State, Error> for Infer
                 let get_function_type = |function: &Function| function.get_type();
                 new_state.add_definitions_from_functions(sequence.get_nodes(), get_function_type);
 
+                let get_class_type = |class: &Class| class.get_type();
+                new_state.add_definitions_from_classes(sequence.get_nodes(), get_class_type);
+
                 vec![ChildState::New(new_state)]
             }
             Node::Function(function) =>
@@ -43,13 +164,84 @@ impl RecurTransform<Node, State, Error> for Infer
 
                 vec![ChildState::New(new_state)]
             }
+            Node::Closure(closure) =>
+            {
+                // Create a new scope with all this closure's arguments
+                //  Both bindings and definitions from the parent scope are visible
+
+                let mut new_state = State::empty(state, true, true);
+
+                let get_argument_type = |argument: &Argument| argument.get_type();
+                new_state.add_bindings_from_arguments(closure.get_arguments(), get_argument_type);
+
+                vec![ChildState::New(new_state)]
+            }
 
             _ => vec![ChildState::Inherit],
         }
     }
 
+    fn enter(&mut self, node: &mut Node, state: &mut State) -> ResultLog<(), Error>
+    {
+        match node
+        {
+            Node::Sequence(sequence) if !sequence.is_transparent() =>
+            {
+                // Link named types in the signatures of this sequence's definitions to their
+                //  classes before anything tries to use them
+
+                resolve_definition_types(sequence.get_nodes_mut(), state)
+            }
+            Node::Function(function) =>
+            {
+                let name = function.get_name().clone();
+                let error = Error::UnsolvedReturnType(name, function.get_source());
+                self.enter_function(function.get_return_type(), error);
+                ResultLog::Ok(())
+            }
+            Node::Closure(closure) =>
+            {
+                // Closures aren't definitions, so their signatures are resolved where they appear
+
+                let lookup = |name: &String| lookup_class_type(&Vec::new(), state, name);
+                let mut unknown_name = None;
+
+                closure.map_types(&mut |t| match resolve_type(t, &lookup)
+                {
+                    Ok(t) => t,
+                    Err(name) =>
+                    {
+                        unknown_name.get_or_insert(name);
+                        t
+                    }
+                });
+
+                let error = Error::UnsolvedType(closure.get_source());
+                self.enter_function(closure.get_return_type(), error);
+
+                match unknown_name
+                {
+                    None => ResultLog::Ok(()),
+                    Some(name) =>
+                    {
+                        ResultLog::new_error(Error::UnknownType(name, closure.get_source()))
+                    }
+                }
+            }
+            _ => ResultLog::Ok(()),
+        }
+    }
+
     fn exit(&mut self, node: &mut Node, state: &mut State) -> ResultLog<(), Error>
     {
+        // Fill in variables in the types of this node's children that have been solved since they
+        //  were inferred
+
+        for child in node.get_children_mut()
+        {
+            child.map_types(&mut |t| self.unifier.resolve(t));
+        }
+
         match node
         {
             Node::Variable(variable) =>
@@ -58,7 +250,7 @@ impl RecurTransform<Node, State, Error> for Infer
 
                 match state.lookup(variable.get_name())
                 {
-                    Some(t) => variable.set_type(t.clone()),
+                    Some(t) => variable.set_type(self.unifier.resolve(t)),
                     None =>
                     {
                         return ResultLog::new_error(Error::UnboundSymbolType(
@@ -78,10 +270,10 @@ impl RecurTransform<Node, State, Error> for Infer
 
                         FunctionType::from(
                             vec![
-                                basic_types::indirect::boolean(),
-                                basic_types::indirect::boolean(),
+                                basic_types::interned::boolean(),
+                                basic_types::interned::boolean(),
                             ],
-                            basic_types::indirect::boolean(),
+                            basic_types::interned::boolean(),
                         )
                     }
                     Operator::Not =>
@@ -89,8 +281,8 @@ impl RecurTransform<Node, State, Error> for Infer
                         // Unary logic operators are always (bool -> bool)
 
                         FunctionType::from(
-                            vec![basic_types::indirect::boolean()],
-                            basic_types::indirect::boolean(),
+                            vec![basic_types::interned::boolean()],
+                            basic_types::interned::boolean(),
                         )
                     }
                     _ =>
@@ -101,38 +293,78 @@ impl RecurTransform<Node, State, Error> for Infer
                     }
                 };
 
-                operator.set_type(Indirect::new(operator_type.to_type()))
+                operator.set_type(operator_type.to_type().intern())
             }
             Node::Call(call) =>
             {
                 let mut errors = Vec::new();
                 let mut warnings = Vec::new();
 
+                // Pass the instance explicitly to instance methods
+                //  (x.Method args) => ((Type . Method) x args)
+
+                if let Err(error) = desugar_method_call(call, &self.unifier)
+                {
+                    return ResultLog::new_error(error);
+                }
+
                 let call_source = call.get_source();
+                let call_type_is_unknown = call.get_type().get().is_unknown();
+                let mut call_variable = None;
+
                 let (operator, operands) = call.get_all_mut();
 
+                // Operators on values see through references to their operands
+                //  (aRef + 1) => ((deref aRef) + 1)
+
+                if let Node::PrimitiveOperator(primitive) = operator
+                {
+                    if takes_values(primitive.get_value())
+                    {
+                        for operand in operands.iter_mut()
+                        {
+                            auto_dereference(operand, false);
+                        }
+                    }
+                }
+
                 let operator_type = operator.get_type();
-                if operator_type.borrow().is_unknown()
+                if operator_type.get().is_unknown()
                 {
                     // Try to infer the operator type from its operands if it is not already known
 
                     match operator
                     {
+                        Node::PrimitiveOperator(primitive)
+                            if is_deferrable(primitive.get_value())
+                                && operands.iter().any(|operand| self.should_defer(operand)) =>
+                        {
+                            // Come back once the operand types are known, giving the call a type
+                            //  variable in the meantime so it can still be solved from how it's used
+                            //  (ie. the result of a recursive call being used as a return value)
+
+                            if call_type_is_unknown
+                            {
+                                call_variable =
+                                    Some(self.unifier.new_variable(call_source.clone()));
+                            }
+                        }
                         Node::PrimitiveOperator(primitive) =>
                         {
                             match infer_primitive_operator_type(
                                 primitive.get_value(),
                                 operands,
                                 call_source.clone(),
+                                &mut self.unifier,
                             )
                             {
                                 ResultLog::Ok(t) =>
                                 {
-                                    primitive.set_type(Indirect::new(t));
+                                    primitive.set_type(t.intern());
                                 }
                                 ResultLog::Warn(t, mut new_warnings) =>
                                 {
-                                    primitive.set_type(Indirect::new(t));
+                                    primitive.set_type(t.intern());
                                     warnings.append(&mut new_warnings);
                                 }
                                 ResultLog::Error(mut new_errors, mut new_warnings) =>
@@ -144,22 +376,75 @@ impl RecurTransform<Node, State, Error> for Infer
                         }
                         _ => warnings.push(Error::FailedToInferOperator(
                             format!("Can't infer type of non-primitive operator: {}", operator),
-                            call_source,
+                            call_source.clone(),
                         )),
                     }
                 }
 
-                // Get return type from operator if possible
+                // Calling a value whose type isn't known yet makes it a function of the operands
+
+                let operand_types: Vec<_> =
+                    operands.iter().map(|operand| operand.get_type()).collect();
 
                 let operator_type = operator.get_type();
-                let operator_type = operator_type.borrow();
+                if operator_type.get().is_variable()
+                {
+                    let return_type = self.unifier.new_variable(call_source.clone());
+                    let function_type = FunctionType::from(operand_types.clone(), return_type);
 
-                match &*operator_type
+                    self.unify(
+                        operator_type,
+                        function_type.to_type().intern(),
+                        &call_source,
+                    );
+                }
+
+                // Get return type from operator if possible, solving any variables in the operands
+                //  and arguments
+                //  note: Calls with the wrong number of operands are caught when checking types
+
+                let operator_type = self.unifier.resolve(operator.get_type());
+                let (argument_types, return_type) = match &operator_type.get()
+                {
+                    Type::Function(function) => (
+                        function.get_arguments().clone(),
+                        Some(function.get_return_type()),
+                    ),
+                    _ => (Vec::new(), None),
+                };
+
+                // Values are borrowed for arguments that take an immutable reference
+                //  (Sum p) => (Sum (ref p))
+
+                if argument_types.len() == operands.len()
                 {
-                    Type::Function(function) => call.set_type(function.get_return_type()),
-                    _ => (),
+                    for (operand, argument_type) in operands.iter_mut().zip(argument_types.iter())
+                    {
+                        auto_borrow(operand, *argument_type);
+                    }
                 }
 
+                let operand_types: Vec<_> =
+                    operands.iter().map(|operand| operand.get_type()).collect();
+
+                if argument_types.len() == operand_types.len()
+                {
+                    for (operand_type, argument_type) in
+                        operand_types.iter().zip(argument_types.iter())
+                    {
+                        self.unify(*operand_type, *argument_type, &call_source);
+                    }
+                }
+                if let Some(return_type) = return_type
+                {
+                    call.set_type(self.unifier.resolve(return_type));
+                }
+                if let Some(call_variable) = call_variable
+                {
+                    call.set_type(call_variable);
+                }
+
+                return ResultLog::maybe_error((), warnings, errors);
             }
             Node::Reference(reference) =>
             {
@@ -167,13 +452,18 @@ impl RecurTransform<Node, State, Error> for Infer
                 let reference_type =
                     ReferenceType::from(reference.get_mode(), target_type).to_type();
 
-                reference.set_type(Indirect::new(reference_type));
+                reference.set_type(reference_type.intern());
             }
             Node::Dereference(dereference) =>
             {
+                if self.should_defer(dereference.get_target())
+                {
+                    return ResultLog::Ok(());
+                }
+
                 let target_type = dereference.get_target().get_type();
 
-                let dereferenced = target_type.borrow().dereference();
+                let dereferenced = target_type.get().dereference();
                 match dereferenced
                 {
                     Some(t) => dereference.set_type(t),
@@ -186,11 +476,36 @@ impl RecurTransform<Node, State, Error> for Infer
                     }
                 }
             }
-            Node::Assign(_) =>
+            Node::Assign(assign) =>
             {
-                // Assign nodes already have a void type
+                // Assign nodes already have a void type, but their value can solve variables in their
+                //  target or the other way around
+
+                let target_type = assign.get_lhs().get_type();
+                let value_type = assign.get_rhs().get_type();
+                self.unify(target_type, value_type, &assign.get_source());
+            }
+            Node::Access(access) =>
+            {
+                // Look up the accessed member in the target's class
+
+                if self.should_defer(access.get_target())
+                {
+                    return ResultLog::Ok(());
+                }
+
+                // Members are accessed through at most one reference
+                //  (aRefRef.x) => ((deref aRefRef).x)
+
+                auto_dereference(access.get_target_mut(), true);
+                let target_type = access.get_target().get_type();
+
+                match infer_access_type(target_type, access.get_property(), access.get_source())
+                {
+                    Ok(t) => access.set_type(t),
+                    Err(error) => return ResultLog::new_error(error),
+                }
             }
-            Node::Access(_) => unimplemented!(),
             Node::Binding(binding) =>
             {
                 // Track the binding in the current state
@@ -203,19 +518,75 @@ impl RecurTransform<Node, State, Error> for Infer
                 let result_type = match sequence.get_result_node()
                 {
                     Some(result) => result.get_type(),
-                    None => basic_types::indirect::void(),
+                    None => basic_types::interned::void(),
                 };
 
                 sequence.set_type(result_type);
             }
             Node::Conditional(conditional) =>
             {
+                // A branch that always jumps away doesn't contribute to the result type
+
+                let then_type = conditional.get_then().get_type();
                 let else_type = conditional.get_else().get_type();
-                conditional.set_type(else_type);
+
+                let then_is_never = then_type.get().is_never();
+                let else_is_never = else_type.get().is_never();
+
+                let result_type = match (then_is_never, else_is_never)
+                {
+                    (_, true) => then_type,
+                    (true, false) => else_type,
+                    (false, false) =>
+                    {
+                        // Both branches give the result, so they must be the same type
+                        self.unify(then_type, else_type, &conditional.get_source());
+
+                        let then_type = self.unifier.resolve(then_type);
+                        let else_type = self.unifier.resolve(else_type);
+
+                        // note: Mismatched branches are reported when checking types, so the
+                        //  result is left unknown rather than taking the type of either one
+
+                        let has_else = match conditional.get_else()
+                        {
+                            Node::Nothing(_) => false,
+                            _ => true,
+                        };
+                        let is_mismatched = has_else
+                            && !then_type.get().has_variables()
+                            && !else_type.get().has_variables()
+                            && !then_type.get().satisfies(&else_type.get());
+
+                        match is_mismatched
+                        {
+                            true => basic_types::interned::unknown(),
+                            false => else_type,
+                        }
+                    }
+                };
+                conditional.set_type(result_type);
+            }
+            Node::Function(function) =>
+            {
+                // Function types should have been built already, apart from any inferred return type
+
+                self.exit_function(function.get_body());
             }
-            Node::Function(_) =>
+            Node::Closure(closure) =>
             {
-                // Function types should have been built already
+                self.exit_function(closure.get_body());
+            }
+            Node::Jump(jump) if jump.get_mode() == JumpMode::Return =>
+            {
+                // Returned values solve the return type of the enclosing function
+                //  note: Returns outside of functions are caught when checking types
+
+                if let Some(return_type) = self.return_types.last().cloned()
+                {
+                    let value_type = jump.get_value().get_type();
+                    self.unify(value_type, return_type, &jump.get_source());
+                }
             }
             Node::Class(_) =>
             {
@@ -230,12 +601,729 @@ impl RecurTransform<Node, State, Error> for Infer
     }
 }
 
+///
+/// Resolve instance types in the signatures of all functions and classes in a list of nodes, looking
+///     up class names in the given nodes first and then the parent scope
+///
+fn resolve_definition_types(nodes: &mut Vec<Node>, state: &State) -> ResultLog<(), Error>
+{
+    let classes = nodes
+        .iter()
+        .filter_map(|node| match node
+        {
+            Node::Class(class) => Some((class.get_name().clone(), class.get_type())),
+            _ => None,
+        })
+        .collect();
+    let lookup = |name: &String| lookup_class_type(&classes, state, name);
+
+    let mut errors = Vec::new();
+
+    for node in nodes.iter_mut()
+    {
+        // note: The same type can appear in several places of a definition (e.g. an argument
+        //       and the function type), so each unknown name is only reported once
+        let source = node.get_source();
+        let mut unknown_names = Vec::new();
+        let mut resolve = |t: TypeId| match resolve_type(t, &lookup)
+        {
+            Ok(t) => t,
+            Err(name) =>
+            {
+                if !unknown_names.contains(&name)
+                {
+                    unknown_names.push(name.clone());
+                    errors.push(Error::UnknownType(name, source.clone()));
+                }
+                t
+            }
+        };
+
+        match node
+        {
+            Node::Function(_) => node.map_types(&mut resolve),
+            Node::Class(_) =>
+            {
+                // note: Method signatures are resolved in both the class type and the methods
+                node.map_types(&mut resolve);
+                for method in node.get_children_mut()
+                {
+                    method.map_types(&mut resolve);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    ResultLog::maybe_error((), Vec::new(), errors)
+}
+
+///
+/// Find the class type with the given name, looking in the given classes first and then the scope
+///
+fn lookup_class_type(
+    classes: &Vec<(String, TypeId)>,
+    state: &State,
+    name: &String,
+) -> Option<ClassType>
+{
+    let found_type = classes
+        .iter()
+        .find(|(class_name, _)| class_name == name)
+        .map(|(_, class_type)| *class_type)
+        .or_else(|| state.lookup(name))?;
+
+    match found_type.get()
+    {
+        Type::Class(class_type) => Some(class_type),
+        _ => None,
+    }
+}
+
+///
+/// Recursively link instance types to their classes, returning the name of any unknown type
+///
+fn resolve_type<TLookup>(t: TypeId, lookup: &TLookup) -> Result<TypeId, String>
+where
+    TLookup: Fn(&String) -> Option<ClassType>,
+{
+    match t.get()
+    {
+        Type::Instance(instance) if !instance.is_resolved() => match lookup(&instance.get_name())
+        {
+            Some(class_type) => Ok(InstanceType::from_class(class_type).to_type().intern()),
+            None => Err(instance.get_name()),
+        },
+        Type::Bounded(bounded) =>
+        {
+            // Built-in traits aren't types, so they have no class to link to
+
+            let mut bounds = Vec::new();
+            for bound in bounded.get_bounds()
+            {
+                let is_built_in = match bound.get()
+                {
+                    Type::Instance(instance) => symbols::traits::contains(&instance.get_name()),
+                    _ => false,
+                };
+
+                match is_built_in
+                {
+                    true => bounds.push(*bound),
+                    false => bounds.push(resolve_type(*bound, lookup)?),
+                }
+            }
+
+            Ok(BoundedType::from(bounds, bounded.get_traits().clone())
+                .to_type()
+                .intern())
+        }
+        t @ (Type::Reference(_) | Type::Array(_) | Type::Function(_)) =>
+        {
+            let mut unknown_name = None;
+            let resolved = t.map_inner(&mut |inner| match resolve_type(inner, lookup)
+            {
+                Ok(inner) => inner,
+                Err(name) =>
+                {
+                    unknown_name.get_or_insert(name);
+                    inner
+                }
+            });
+
+            match unknown_name
+            {
+                Some(name) => Err(name),
+                None => Ok(resolved.intern()),
+            }
+        }
+        _ => Ok(t),
+    }
+}
+
+///
+/// Get the type of a member accessed from a target of the given type
+///
+/// - Classes (ie. `Type.member`) have static data members and all methods
+/// - Instances (ie. `instance.member`) have instance data members and instance methods
+/// - References to instances are accessed the same way as instances
+/// - Bounded types (ie. `is Summable`) have the instance members of their bounding types
+///
+fn infer_access_type(
+    target_type: TypeId,
+    property: &String,
+    source: Source,
+) -> Result<TypeId, Error>
+{
+    match &target_type.get()
+    {
+        Type::Class(class) =>
+        {
+            if let Some(member) = class.get_member(property, MemberScope::Static)
+            {
+                return Ok(member.get_type());
+            }
+            if let Some(method) = class.get_method(property)
+            {
+                return Ok(method.get_type());
+            }
+
+            Err(Error::UnknownMember(
+                class.get_name(),
+                property.clone(),
+                source,
+            ))
+        }
+        Type::Instance(instance) =>
+        {
+            let class = match instance.get_class()
+            {
+                Some(class) => class,
+                None => return Err(Error::UnknownType(instance.get_name(), source)),
+            };
+
+            if let Some(member) = class.get_member(property, MemberScope::Instance)
+            {
+                return Ok(member.get_type());
+            }
+            match class.get_method(property)
+            {
+                Some(method) if method.get_scope() == MemberScope::Instance =>
+                {
+                    return Ok(method.get_type());
+                }
+                _ => (),
+            }
+
+            Err(Error::UnknownMember(
+                class.get_name(),
+                property.clone(),
+                source,
+            ))
+        }
+        Type::Bounded(bounded) =>
+        {
+            match bounded.get_bound_with_member(property)
+            {
+                Some(bound) => infer_access_type(bound, property, source),
+                None => Err(Error::UnknownMember(
+                    format!("{}", bounded),
+                    property.clone(),
+                    source,
+                )),
+            }
+        }
+        Type::Reference(reference) =>
+        {
+            let reference_target = reference.get_target();
+            let is_instance = match &reference_target.get()
+            {
+                Type::Instance(_) | Type::Bounded(_) => true,
+                _ => false,
+            };
+
+            match is_instance
+            {
+                true => infer_access_type(reference_target, property, source),
+                false => Err(Error::BadAccessType(target_type, source)),
+            }
+        }
+        _ => Err(Error::BadAccessType(target_type, source)),
+    }
+}
+
+///
+/// Rewrite a call to an instance method accessed from an instance to a call to the method accessed
+///     from its class, with the instance as the first operand
+///
+/// - The instance is referenced or dereferenced to match the method's self argument
+/// - Methods called on bounded types are left alone until the call is monomorphized, since the
+///     method that is actually called depends on the concrete type
+///
+fn desugar_method_call(call: &mut Call, unifier: &Unifier) -> Result<(), Error>
+{
+    let (operator, operands) = call.get_all_mut();
+
+    let access = match operator
+    {
+        Node::Access(access) => access,
+        _ => return Ok(()),
+    };
+
+    // Find the method's class and self type through the target's instance type
+
+    let target_type = unifier.resolve(access.get_target().get_type());
+    let instance_type = match target_type.get().dereference()
+    {
+        Some(t) => t,
+        None => target_type,
+    };
+
+    let (class_name, class_type, self_type) = {
+        let instance = match instance_type.get()
+        {
+            Type::Instance(instance) => instance,
+            _ => return Ok(()),
+        };
+        let class = match instance.get_class()
+        {
+            Some(class) => class,
+            None => return Ok(()),
+        };
+        let method = match class.get_method(access.get_property())
+        {
+            Some(method) if method.get_scope() == MemberScope::Instance => method,
+            _ => return Ok(()),
+        };
+
+        let self_type = match &method.get_type().get()
+        {
+            Type::Function(function) => function.get_arguments()[0],
+            t =>
+            {
+                return Err(Error::Internal(format!(
+                    "Expected instance method to have a function type, found: {}",
+                    t
+                )))
+            }
+        };
+
+        (class.get_name(), instance.get_class_type(), self_type)
+    };
+
+    let source = access.get_source();
+
+    // Make the instance match the self argument
+
+    let mut receiver = access.get_target_mut().extract_temp();
+
+    let receiver_mode = match &target_type.get()
+    {
+        Type::Reference(reference) => Some(reference.get_mode()),
+        _ => None,
+    };
+    let self_mode = match &self_type.get()
+    {
+        Type::Reference(reference) => Some(reference.get_mode()),
+        _ => None,
+    };
+
+    // note: Immutable references are passed as-is to methods that take a mutable reference, so
+    //  they fail type checking instead of being silently converted
+
+    let is_allowed = match (receiver_mode, self_mode)
+    {
+        (Some(ReferenceMode::Immutable), Some(ReferenceMode::Mutable)) => false,
+        _ => true,
+    };
+
+    if is_allowed && receiver_mode.is_some() && receiver_mode != self_mode
+    {
+        let mut dereference = Dereference::new(receiver, source.clone());
+        dereference.set_type(instance_type);
+        receiver = dereference.to_node();
+    }
+    if let Some(mode) = self_mode
+    {
+        if is_allowed && receiver_mode != self_mode
+        {
+            let mut reference = Reference::new(mode, receiver, source.clone());
+            reference.set_type(self_type);
+            receiver = reference.to_node();
+        }
+    }
+
+    operands.insert(0, receiver);
+
+    // Access the method from its class instead
+
+    let class_variable = Variable::new_typed(class_name, class_type, source.clone());
+    let method_type = access.get_type();
+
+    let mut class_access = Access::new(
+        class_variable.to_node(),
+        access.get_property().clone(),
+        source,
+    );
+    class_access.set_type(method_type);
+
+    *operator = class_access.to_node();
+
+    Ok(())
+}
+
+///
+/// Dereference a node until its type is no longer a reference, or only a single reference if
+///     `keep_reference` is set
+///
+fn auto_dereference(node: &mut Node, keep_reference: bool)
+{
+    while let Some(target_type) = node.get_type().get().dereference()
+    {
+        if keep_reference && !target_type.get().is_reference()
+        {
+            return;
+        }
+
+        let source = node.get_source();
+        let mut dereference = Dereference::new(node.extract_temp(), source);
+        dereference.set_type(target_type);
+        *node = dereference.to_node();
+    }
+}
+
+///
+/// Reference an operand if it is a value used for an argument that takes an immutable reference
+///
+/// - Only nodes that name a place (ie. bindings and members) are borrowed, since temporary values
+///     have nowhere to be referenced from
+///
+fn auto_borrow(node: &mut Node, argument_type: TypeId)
+{
+    match argument_type.get()
+    {
+        Type::Reference(reference) if reference.get_mode() == ReferenceMode::Immutable => (),
+        _ => return,
+    }
+
+    let node_type = node.get_type();
+    let is_value = match node_type.get()
+    {
+        Type::Reference(_) | Type::Variable(_) | Type::Unknown | Type::Never | Type::Class(_) =>
+        {
+            false
+        }
+        _ => true,
+    };
+    let is_place = match node
+    {
+        Node::Variable(_) | Node::Access(_) | Node::Dereference(_) => true,
+        _ => false,
+    };
+
+    if !is_value || !is_place
+    {
+        return;
+    }
+
+    let source = node.get_source();
+    let mut reference = Reference::new(ReferenceMode::Immutable, node.extract_temp(), source);
+    reference.set_type(
+        ReferenceType::from(ReferenceMode::Immutable, node_type)
+            .to_type()
+            .intern(),
+    );
+    *node = reference.to_node();
+}
+
+///
+/// Get the type of a create operator from the class it is applied to
+///
+/// - `(create T)` creates an instance of T in place
+/// - `(heap-alloc T)` creates an instance of T on the heap, and returns a mutable reference to it
+/// - Enumerated types can't be created directly, only their variants can
+///
+fn infer_create_type(
+    operator: Operator,
+    operands: &Vec<Node>,
+    call_source: Source,
+) -> ResultLog<Type, Error>
+{
+    let class_type = match operands.as_slice()
+    {
+        [operand] => operand.get_type(),
+        _ =>
+        {
+            let error = Error::BadCreateType(
+                format!("Expected a single type operand for {}", operator),
+                call_source,
+            );
+            return ResultLog::new_error(error);
+        }
+    };
+
+    let class = match class_type.get()
+    {
+        Type::Class(class)
+            if class
+                .get_traits()
+                .has(&String::from(symbols::traits::ENUMERATED)) =>
+        {
+            // Enumerated types only exist as one of their variants
+
+            let error = Error::BadCreateType(
+                format!(
+                    "Can't {} enumerated type {}, expected one of its variants",
+                    operator,
+                    class.get_name()
+                ),
+                call_source,
+            );
+            return ResultLog::new_error(error);
+        }
+        Type::Class(class) => class,
+        t =>
+        {
+            let error = Error::BadCreateType(
+                format!("Expected a type operand for {}, found: {}", operator, t),
+                call_source,
+            );
+            return ResultLog::new_error(error);
+        }
+    };
+
+    let instance_type = InstanceType::from_class(class);
+
+    let result_type = match operator
+    {
+        Operator::HeapAllocate => instance_type.to_type().into_reference(ReferenceMode::Mutable),
+        _ => instance_type.to_type(),
+    };
+
+    let function_type = FunctionType::from(vec![class_type], result_type.intern());
+    ResultLog::Ok(function_type.to_type())
+}
+
+///
+/// Infer the type of an iterator operator from the iterable it's applied to (ie. the `I` of
+///     `for x in I {...}`), which is either a range or an array
+///
+/// - `(iterator I)` makes a range to count through, which is I itself or the indices of the array
+/// - `(iterator-has-next i)` and `(iterator-next i I)` use that range, and only `iterator` reports
+///     iterables of the wrong type so they aren't reported for each operator
+/// - `iterator-next` gives the next number in the range, or the array element at that index
+///
+fn infer_iterator_type(
+    operator: Operator,
+    operands: &Vec<Node>,
+    call_source: Source,
+) -> ResultLog<Type, Error>
+{
+    let function_type = match (operator, operands.as_slice())
+    {
+        (Operator::MakeIterator, [iterable]) =>
+        {
+            let iterable_type = iterable.get_type().get();
+            let is_range = match iterable_type.dereference()
+            {
+                Some(target_type) => target_type.get() == Type::Range,
+                None => iterable_type == Type::Range,
+            };
+            let is_iterable = is_range || get_array_element_type(iterable).is_some();
+            if !is_iterable
+            {
+                return ResultLog::new_error(Error::BadIteratorType(
+                    iterable.get_type(),
+                    call_source,
+                ));
+            }
+
+            FunctionType::from(vec![iterable.get_type()], basic_types::interned::range())
+        }
+        (Operator::IteratorHasNext, [_iterator]) =>
+        {
+            let argument_type = basic_types::range().into_reference(ReferenceMode::Immutable);
+            FunctionType::new(vec![argument_type], basic_types::boolean())
+        }
+        (Operator::IteratorNext, [_iterator, iterable]) =>
+        {
+            let argument_type = basic_types::range().into_reference(ReferenceMode::Mutable);
+            let result_type = match get_array_element_type(iterable)
+            {
+                Some(element_type) => element_type,
+                None => basic_types::interned::integer(),
+            };
+
+            FunctionType::from(
+                vec![argument_type.intern(), iterable.get_type()],
+                result_type,
+            )
+        }
+        _ =>
+        {
+            let error = Error::Internal(format!("Unexpected operands for {}", operator));
+            return ResultLog::new_error(error);
+        }
+    };
+
+    ResultLog::Ok(function_type.to_type())
+}
+
+///
+/// Infer the type of an array operator from its operands
+///
+/// - `[a b c]` makes an array of the type of its first element, with a fixed length
+/// - `[]` makes an empty array, whose element type is solved from how it is used
+/// - `(make-array n x)` makes a dynamic array with n copies of x
+/// - `(at a i)` and `(length a)` can be used on arrays or references to arrays
+///
+fn infer_array_operator_type(
+    operator: Operator,
+    operands: &Vec<Node>,
+    call_source: Source,
+    unifier: &mut Unifier,
+) -> ResultLog<Type, Error>
+{
+    let function_type = match (operator, operands.as_slice())
+    {
+        (Operator::ArrayLiteral, []) =>
+        {
+            let element_type = unifier.new_variable(call_source);
+            let array_type = ArrayType::from(element_type, Some(0));
+
+            FunctionType::from(Vec::new(), array_type.to_type().intern())
+        }
+        (Operator::ArrayLiteral, elements) =>
+        {
+            // Elements that don't match the first are caught when checking types
+
+            let element_type = elements[0].get_type();
+            let array_type = ArrayType::from(element_type, Some(elements.len()));
+
+            FunctionType::from(
+                vec![element_type; elements.len()],
+                array_type.to_type().intern(),
+            )
+        }
+        (Operator::MakeArray, [_length, value]) =>
+        {
+            let element_type = value.get_type();
+            let array_type = ArrayType::from(element_type, None);
+
+            FunctionType::from(
+                vec![basic_types::interned::integer(), element_type],
+                array_type.to_type().intern(),
+            )
+        }
+        (Operator::ArrayAt, [array, _index]) =>
+        {
+            let element_type = match get_array_element_type(array)
+            {
+                Some(t) => t,
+                None =>
+                {
+                    return ResultLog::new_error(Error::BadArrayType(array.get_type(), call_source))
+                }
+            };
+
+            FunctionType::from(
+                vec![array.get_type(), basic_types::interned::integer()],
+                element_type,
+            )
+        }
+        (Operator::ArrayLength, [array]) =>
+        {
+            if get_array_element_type(array).is_none()
+            {
+                return ResultLog::new_error(Error::BadArrayType(array.get_type(), call_source));
+            }
+
+            FunctionType::from(vec![array.get_type()], basic_types::interned::integer())
+        }
+        _ =>
+        {
+            let error = Error::UnexpectedOperands(
+                format!("Unexpected operands for primitive operator: {}", operator),
+                call_source,
+            );
+            return ResultLog::new_error(error);
+        }
+    };
+
+    ResultLog::Ok(function_type.to_type())
+}
+
+///
+/// Get the element type of an array or a reference to an array
+///
+fn get_array_element_type(node: &Node) -> Option<TypeId>
+{
+    let node_type = node.get_type();
+    let array_type = match node_type.get().dereference()
+    {
+        Some(t) => t,
+        None => node_type,
+    };
+
+    match array_type.get()
+    {
+        Type::Array(array) => Some(array.get_element()),
+        _ => None,
+    }
+}
+
+///
+/// Check if a primitive operator needs to know its operand types to be inferred
+///
+/// - Array literals and `make-array` just hold their operands, so they can hold type variables
+///
+fn is_deferrable(operator: Operator) -> bool
+{
+    match operator
+    {
+        Operator::ArrayLiteral | Operator::MakeArray => false,
+        _ => true,
+    }
+}
+
+///
+/// Check if a primitive operator works on values, so references used as its operands are
+///     dereferenced
+///
+fn takes_values(operator: Operator) -> bool
+{
+    match operator
+    {
+        Operator::Add
+        | Operator::Subtract
+        | Operator::Multiply
+        | Operator::Divide
+        | Operator::Modulo
+        | Operator::Equal
+        | Operator::NotEqual
+        | Operator::Less
+        | Operator::Greater
+        | Operator::LessEqual
+        | Operator::GreaterEqual
+        | Operator::Not
+        | Operator::And
+        | Operator::Or
+        | Operator::ExclusiveOr
+        | Operator::ToInteger
+        | Operator::ToFloat
+        | Operator::Range
+        | Operator::Print => true,
+        _ => false,
+    }
+}
+
 fn infer_primitive_operator_type(
     operator: Operator,
     operands: &Vec<Node>,
     call_source: Source,
+    unifier: &mut Unifier,
 ) -> ResultLog<Type, Error>
 {
+    match operator
+    {
+        Operator::Create | Operator::HeapAllocate =>
+        {
+            return infer_create_type(operator, operands, call_source);
+        }
+        Operator::MakeIterator | Operator::IteratorHasNext | Operator::IteratorNext =>
+        {
+            return infer_iterator_type(operator, operands, call_source);
+        }
+        Operator::ArrayLiteral
+        | Operator::MakeArray
+        | Operator::ArrayAt
+        | Operator::ArrayLength =>
+        {
+            return infer_array_operator_type(operator, operands, call_source, unifier);
+        }
+        _ => (),
+    }
+
     macro_rules! match_primitive_call {
         {
             ($operator:expr, $operands:expr, $source:expr)
@@ -337,6 +1425,28 @@ fn infer_primitive_operator_type(
                 (a: Integer, b: Float => Boolean),
                 (a: Float, b: Integer => Boolean),
             }
+
+            Operator::ToInteger =>
+            {
+                (a: Float => Integer),
+                (a: Integer => Integer),
+            }
+
+            Operator::ToFloat =>
+            {
+                (a: Integer => Float),
+                (a: Float => Float),
+            }
+
+            Operator::Range =>
+            {
+                (start: Integer, end: Integer => Range),
+            }
+
+            Operator::Print =>
+            {
+                (a: Type::String => Void),
+            }
         }
     };
 
@@ -1,18 +1,51 @@
 mod check;
+mod check_visibility;
 mod infer;
+mod unify;
+
+use crate::errors::compile_error::Error;
+use crate::language::types::TypeId;
+use unify::Unifier;
 
 ///
 /// ## Infer Types
 ///
 /// - Fill in type information for all nodes
+/// - Types that aren't known yet are type variables, which are solved by unifying them with the
+///     types they are used as
+/// - Operators that need to know their operand types are deferred to a later round if those types
+///     are still variables (ie. a call to a function whose return type is solved further down)
+/// - Functions without a return type get theirs from the result of their body and the values they
+///     return
+/// - References are coerced by inserting Reference and Dereference nodes
+///     - Operands of value operators are dereferenced (ie. `aRef + 1` is `(deref aRef) + 1`)
+///     - Members are accessed through at most one reference (ie. `pRefRef.x` is `(deref pRefRef).x`)
+///     - Bindings and members are borrowed for arguments that take an immutable reference
+///         (ie. `(Sum p)` is `(Sum (ref p))` when Sum takes `(ref Point)`)
 ///
-pub struct Infer {}
+pub struct Infer
+{
+    unifier:       Unifier,
+    deferred:      usize,
+    is_last_round: bool,
+    errors:        Vec<Error>,
+
+    // The return types of the functions and closures being inferred, innermost last
+    return_types: Vec<TypeId>,
+}
 
 impl Infer
 {
     pub fn new() -> Infer
     {
-        Infer {}
+        Infer {
+            unifier:       Unifier::new(),
+            deferred:      0,
+            is_last_round: false,
+            errors:        Vec::new(),
+
+            return_types: Vec::new(),
+        }
     }
 }
 
@@ -20,13 +53,33 @@ impl Infer
 /// ## Check Types
 /// 
 /// - Verify that all inferred and annotated type information makes sense
+/// - Verify that the result of the program can be returned from main as an int, unless any result
+///     is allowed (ie. when it's evaluated by the interpreter instead)
 /// 
-pub struct Check {}
+pub struct Check
+{
+    allow_any_result: bool,
+}
 
 impl Check
 {
-    pub fn new() -> Check
+    pub fn new(allow_any_result: bool) -> Check
+    {
+        Check { allow_any_result }
+    }
+}
+///
+/// ## Check Visibility
+///
+/// - Verify that private members and methods are only used from inside their class
+/// - Verify that data members are only written from outside their class through `read-write` accessors
+///
+pub struct CheckVisibility {}
+
+impl CheckVisibility
+{
+    pub fn new() -> CheckVisibility
     {
-        Check {}
+        CheckVisibility {}
     }
-}
\ No newline at end of file
+}
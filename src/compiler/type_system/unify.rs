@@ -0,0 +1,243 @@
+use super::super::common::*;
+
+use std::collections::HashMap;
+
+///
+/// Solves type variables by unifying the types they are used as
+///
+/// - Solutions are kept by the unifier rather than written into types, so types have to be
+///     resolved through it to see what their variables were solved as
+/// - Only the parts of types that are variables are solved, and types that don't match are left
+///     alone for Check to report
+///
+pub struct Unifier
+{
+    // The type each solved variable was solved as, which can hold other variables
+    solutions: HashMap<usize, TypeId>,
+
+    // The error to report for each variable if it's never solved
+    errors: HashMap<usize, Error>,
+
+    // Whether unsolved variables have been given up on, so they resolve to the unknown type
+    is_finished: bool,
+}
+
+impl Unifier
+{
+    pub fn new() -> Unifier
+    {
+        Unifier {
+            solutions:   HashMap::new(),
+            errors:      HashMap::new(),
+            is_finished: false,
+        }
+    }
+
+    ///
+    /// Make a new type variable for the type of something at the given source
+    ///
+    pub fn new_variable(&mut self, source: Source) -> TypeId
+    {
+        let variable = TypeVariable::new();
+        self.errors
+            .insert(variable.get_id(), Error::UnsolvedType(source));
+
+        variable.to_type().intern()
+    }
+
+    ///
+    /// Keep track of a type variable made outside the unifier (ie. the return type of a function
+    ///     without one), with the error to report if it's never solved
+    ///
+    pub fn add_variable(&mut self, t: TypeId, error: Error)
+    {
+        let variable = match self.prune(t).get()
+        {
+            Type::Variable(variable) => variable,
+            _ => return,
+        };
+
+        self.errors.entry(variable.get_id()).or_insert(error);
+    }
+
+    ///
+    /// Stop reporting a variable if it's never solved, since whatever stopped it from being solved
+    ///     is reported elsewhere
+    ///
+    pub fn dismiss_variable(&mut self, t: TypeId)
+    {
+        if let Type::Variable(variable) = self.prune(t).get()
+        {
+            self.errors.remove(&variable.get_id());
+        }
+    }
+
+    ///
+    /// Get the number of variables that haven't been solved yet
+    ///
+    pub fn count_unsolved(&self) -> usize
+    {
+        self.errors.len()
+    }
+
+    ///
+    /// Get a type with every solved variable in it replaced by its solution
+    ///
+    /// - Once unsolved variables have been taken, they are replaced with the unknown type
+    ///
+    pub fn resolve(&self, t: TypeId) -> TypeId
+    {
+        let t = self.prune(t);
+        match t.get()
+        {
+            Type::Variable(_) if self.is_finished => basic_types::interned::unknown(),
+            Type::Variable(_) => t,
+            t => t.map_inner(&mut |inner| self.resolve(inner)).intern(),
+        }
+    }
+
+    ///
+    /// Follow a chain of solved variables to the type at the end of it (ie. another variable, or a
+    ///     type that may still hold variables)
+    ///
+    fn prune(&self, t: TypeId) -> TypeId
+    {
+        let mut t = t;
+        while let Type::Variable(variable) = t.get()
+        {
+            match self.solutions.get(&variable.get_id())
+            {
+                Some(solution) => t = *solution,
+                None => break,
+            }
+        }
+        t
+    }
+
+    ///
+    /// Solve any variables in two types so they become the same type
+    ///
+    pub fn unify(&mut self, a: TypeId, b: TypeId, source: &Source) -> Result<(), Error>
+    {
+        let a = self.prune(a);
+        let b = self.prune(b);
+
+        if a == b
+        {
+            return Ok(());
+        }
+
+        // Find the variable to solve, or the parts of both types to unify
+
+        match (a.get(), b.get())
+        {
+            // note: Unknown types come from errors, so they can't solve anything
+            (Type::Unknown, _) | (_, Type::Unknown) => Ok(()),
+
+            (Type::Variable(variable), _) => self.solve(variable, a, b, source),
+            (_, Type::Variable(variable)) => self.solve(variable, b, a, source),
+
+            (Type::Reference(reference_a), Type::Reference(reference_b)) =>
+            {
+                self.unify(reference_a.get_target(), reference_b.get_target(), source)
+            }
+            (Type::Array(array_a), Type::Array(array_b)) =>
+            {
+                self.unify(array_a.get_element(), array_b.get_element(), source)
+            }
+            (Type::Function(function_a), Type::Function(function_b))
+                if function_a.get_arguments().len() == function_b.get_arguments().len() =>
+            {
+                for (argument_a, argument_b) in function_a
+                    .get_arguments()
+                    .iter()
+                    .zip(function_b.get_arguments().iter())
+                {
+                    self.unify(*argument_a, *argument_b, source)?;
+                }
+
+                self.unify(
+                    function_a.get_return_type(),
+                    function_b.get_return_type(),
+                    source,
+                )
+            }
+
+            // note: Other types can't hold variables, and mismatches are caught when checking types
+            _ => Ok(()),
+        }
+    }
+
+    ///
+    /// Solve a variable as some other type
+    ///
+    /// - Variables solved as another variable are merged into it, so both are solved together
+    ///
+    fn solve(
+        &mut self,
+        variable: TypeVariable,
+        variable_type: TypeId,
+        t: TypeId,
+        source: &Source,
+    ) -> Result<(), Error>
+    {
+        match t.get()
+        {
+            Type::Variable(other_variable) =>
+            {
+                if let Some(solved_error) = self.errors.remove(&variable.get_id())
+                {
+                    self.errors
+                        .entry(other_variable.get_id())
+                        .or_insert(solved_error);
+                }
+            }
+            _ if self.contains_variable(t, variable) =>
+            {
+                return Err(Error::InfiniteType(variable_type, t, source.clone()));
+            }
+            _ =>
+            {
+                self.errors.remove(&variable.get_id());
+            }
+        }
+
+        self.solutions.insert(variable.get_id(), t);
+        Ok(())
+    }
+
+    ///
+    /// Check if a type is or refers to the given variable
+    ///
+    fn contains_variable(&self, t: TypeId, variable: TypeVariable) -> bool
+    {
+        match self.prune(t).get()
+        {
+            Type::Variable(other_variable) => other_variable == variable,
+            Type::Reference(reference) => self.contains_variable(reference.get_target(), variable),
+            Type::Array(array) => self.contains_variable(array.get_element(), variable),
+            Type::Function(function) =>
+            {
+                function
+                    .get_arguments()
+                    .iter()
+                    .any(|argument| self.contains_variable(*argument, variable))
+                    || self.contains_variable(function.get_return_type(), variable)
+            }
+            _ => false,
+        }
+    }
+
+    ///
+    /// Give up on all unsolved variables, so they resolve to the unknown type, returning an error
+    ///     for each
+    ///
+    pub fn take_unsolved(&mut self) -> Vec<Error>
+    {
+        let mut unsolved: Vec<_> = self.errors.drain().collect();
+        unsolved.sort_by_key(|(id, _)| *id);
+
+        self.is_finished = true;
+        unsolved.into_iter().map(|(_, error)| error).collect()
+    }
+}
@@ -0,0 +1,348 @@
+use crate::errors::*;
+use crate::interpreter::Interpreter;
+use crate::repl::Repl;
+use crate::*;
+use std::fs;
+
+const USAGE: &str = "\
+Usage: specs <command> [options]
+
+Commands:
+    build <file>    Compile a program to C
+    check <file>    Check a program for errors without compiling it
+    run <file>      Evaluate a program with the interpreter
+    emit <file>     Print the result of a compiler stage
+    repl            Start an interactive session
+
+Options:
+    -o <path>       Write output to a file (build defaults to the input path with a .c extension)
+    --emit=<stage>  The stage to emit: sexpr, preprocessed, ast, typed, flat or c (default: c)
+    --passes        Print the result of each compiler pass
+    --quiet         Only print errors";
+
+/* -------------------------------------------------------------------------- */
+/*                                   Options                                  */
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Command
+{
+    Build,
+    Check,
+    Run,
+    Emit,
+    Repl,
+    Help,
+}
+
+struct Options
+{
+    command:     Command,
+    input_path:  Option<String>,
+    output_path: Option<String>,
+    emit:        Stage,
+    passes:      bool,
+    quiet:       bool,
+}
+
+///
+/// Get options from command-line arguments (not including the executable name)
+///
+fn parse_options(args: Vec<String>) -> Result<Options, String>
+{
+    let mut args = args.into_iter();
+
+    let command = match args.next()
+    {
+        Some(command) => match command.as_str()
+        {
+            "build" => Command::Build,
+            "check" => Command::Check,
+            "run" => Command::Run,
+            "emit" => Command::Emit,
+            "repl" => Command::Repl,
+            "help" | "-h" | "--help" => Command::Help,
+            _ => return Err(format!("Unknown command '{}'", command)),
+        },
+        None => return Err(String::from("Expected a command")),
+    };
+
+    let mut options = Options {
+        command,
+        input_path: None,
+        output_path: None,
+        emit: Stage::C,
+        passes: false,
+        quiet: false,
+    };
+
+    while let Some(arg) = args.next()
+    {
+        if arg == "-o"
+        {
+            match args.next()
+            {
+                Some(path) => options.output_path = Some(path),
+                None => return Err(String::from("Expected an output path after '-o'")),
+            }
+        }
+        else if let Some(stage) = arg.strip_prefix("--emit=")
+        {
+            options.emit = match stage
+            {
+                "sexpr" => Stage::SExpression,
+                "preprocessed" => Stage::Preprocessed,
+                "ast" => Stage::Ast,
+                "typed" => Stage::Typed,
+                "flat" => Stage::Flat,
+                "c" => Stage::C,
+                stage => return Err(format!("Unknown stage '{}'", stage)),
+            };
+        }
+        else if arg == "--passes"
+        {
+            options.passes = true;
+        }
+        else if arg == "--quiet"
+        {
+            options.quiet = true;
+        }
+        else if arg.starts_with("-")
+        {
+            return Err(format!("Unknown option '{}'", arg));
+        }
+        else if options.input_path.is_none()
+        {
+            options.input_path = Some(arg);
+        }
+        else
+        {
+            return Err(format!("Unexpected argument '{}'", arg));
+        }
+    }
+
+    // Check that options make sense for the command
+
+    match (options.command, &options.input_path)
+    {
+        (Command::Repl, _) | (Command::Help, _) => (),
+        (_, None) => return Err(String::from("Expected an input file")),
+        _ => (),
+    }
+    match (options.command, &options.output_path)
+    {
+        (Command::Check, Some(_)) | (Command::Run, Some(_)) | (Command::Repl, Some(_)) =>
+        {
+            return Err(String::from("Option '-o' is only used by build and emit"));
+        }
+        _ => (),
+    }
+    if options.emit != Stage::C && options.command != Command::Emit
+    {
+        return Err(String::from("Option '--emit' is only used by emit"));
+    }
+
+    Ok(options)
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Driver                                   */
+/* -------------------------------------------------------------------------- */
+
+///
+/// Run the command given by the command-line arguments, exiting with a failure code if it fails
+///
+pub fn run()
+{
+    let options = match parse_options(std::env::args().skip(1).collect())
+    {
+        Ok(options) => options,
+        Err(message) =>
+        {
+            eprintln!("{}\n\n{}", message, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let success = match options.command
+    {
+        Command::Help =>
+        {
+            println!("{}", USAGE);
+            true
+        }
+        Command::Repl =>
+        {
+            Repl::new().run();
+            true
+        }
+        _ => run_command(&options),
+    };
+
+    if !success
+    {
+        std::process::exit(1);
+    }
+}
+
+///
+/// Run a command on an input file, returning false if there were any errors
+///
+fn run_command(options: &Options) -> bool
+{
+    let input_path = options.input_path.clone().unwrap_or_default();
+
+    let source = match fs::read_to_string(&input_path)
+    {
+        Ok(source) => source,
+        Err(error) =>
+        {
+            eprintln!("Failed to open file '{}': {}", input_path, error);
+            return false;
+        }
+    };
+
+    match options.command
+    {
+        Command::Build =>
+        {
+            let (output, _types) = match compile(source, Stage::C, options)
+            {
+                Some(result) => result,
+                None => return false,
+            };
+
+            let output_path = match &options.output_path
+            {
+                Some(path) => path.clone(),
+                None => std::path::Path::new(&input_path)
+                    .with_extension("c")
+                    .to_string_lossy()
+                    .into_owned(),
+            };
+
+            write_output(&output, &output_path, options.quiet)
+        }
+        Command::Check => compile(source, Stage::Typed, options).is_some(),
+        Command::Run =>
+        {
+            let (node, types) = match compile(source, Stage::Typed, options)
+            {
+                Some((Output::Node(node), types)) => (node, types),
+                _ => return false,
+            };
+
+            let _scope = types.enter();
+            match report(Interpreter::new().evaluate(&node), options.quiet)
+            {
+                Some(value) =>
+                {
+                    if !options.quiet
+                    {
+                        // Print the result like the C runtime does
+                        match value.get_program_result()
+                        {
+                            Some(result) => println!("out: {}", result),
+                            None => println!("out: {}", value),
+                        }
+                    }
+                    true
+                }
+                None => false,
+            }
+        }
+        Command::Emit =>
+        {
+            let (output, _types) = match compile(source, options.emit, options)
+            {
+                Some(result) => result,
+                None => return false,
+            };
+
+            match &options.output_path
+            {
+                Some(path) => write_output(&output, path, options.quiet),
+                None =>
+                {
+                    println!("{}", output);
+                    true
+                }
+            }
+        }
+        Command::Repl | Command::Help => true,
+    }
+}
+
+///
+/// Run the compiler on some source text up to the given stage, printing any errors
+///
+/// - The output comes with the type context that its types are kept in
+///
+fn compile(source: String, stage: Stage, options: &Options) -> Option<(Output, TypeContext)>
+{
+    let compile_options = CompileOptions {
+        stage,
+        print_passes: options.passes,
+    };
+    let result = crate::compile(source, &compile_options);
+
+    if !options.quiet
+    {
+        print_warnings(&result.warnings);
+    }
+    print_errors(&result.errors);
+
+    let types = result.types;
+    result.output.map(|output| (output, types))
+}
+
+fn write_output(output: &Output, path: &String, quiet: bool) -> bool
+{
+    match fs::write(path, format!("{}", output))
+    {
+        Ok(()) =>
+        {
+            if !quiet
+            {
+                println!("Wrote '{}'", path);
+            }
+            true
+        }
+        Err(error) =>
+        {
+            eprintln!("Failed to write file '{}': {}", path, error);
+            false
+        }
+    }
+}
+
+///
+/// Print the errors and warnings from some result, unless warnings are hidden
+///
+fn report<TResult, TError: ErrorTrait>(
+    result: ResultLog<TResult, TError>,
+    quiet: bool,
+) -> Option<TResult>
+{
+    match result
+    {
+        ResultLog::Ok(result) => Some(result),
+        ResultLog::Warn(result, warnings) =>
+        {
+            if !quiet
+            {
+                print_warnings(&warnings);
+            }
+            Some(result)
+        }
+        ResultLog::Error(errors, warnings) =>
+        {
+            if !quiet
+            {
+                print_warnings(&warnings);
+            }
+            print_errors(&errors);
+            None
+        }
+    }
+}
@@ -1,25 +1,62 @@
 pub use super::*;
 
 use crate::language::node::*;
-use crate::utilities::*;
-
-type IndirectType = Indirect<Type>;
 
 pub enum Error
 {
+    // Instantiate types
+    BadTypeArguments(String, usize, usize, Source),
+    BadTypeArgument(String, TypeId, Source),
+    RecursiveAlias(String, Source),
+    ExpandingTypeArguments(String, Source),
+
+    // Resolve traits
+    UnknownTrait(String, Source),
+    RecursiveTrait(String, Source),
+    UnknownTraitMethod(String, String, Source),
+
     // Infer types
     UnboundSymbolType(String, Source),
-    BadDereferenceType(IndirectType, Source),
+    BadDereferenceType(TypeId, Source),
     UnexpectedOperands(String, Source),
     FailedToInferOperator(String, Source),
+    UnknownType(String, Source),
+    UnknownMember(String, String, Source),
+    BadAccessType(TypeId, Source),
+    BadCreateType(String, Source),
+    BadIteratorType(TypeId, Source),
+    BadArrayType(TypeId, Source),
+    UnsolvedType(Source),
+    UnsolvedReturnType(String, Source),
+    InfiniteType(TypeId, TypeId, Source),
 
     // Check types
-    BadOperatorType(Indirect<Type>, Source),
-    BadOperandTypes(Vec<IndirectType>, Vec<IndirectType>, Source),
-    BadAssignTypes(IndirectType, IndirectType, Source),
-    BadReturnType(IndirectType, IndirectType, Source),
-    BadConditionType(IndirectType, Source),
-    BadBranchTypes(IndirectType, IndirectType, Source),
+    BadOperatorType(TypeId, Source),
+    BadOperandTypes(Vec<TypeId>, Vec<TypeId>, Source),
+    BadAssignTypes(TypeId, TypeId, Source),
+    ImmutableAssign(Source),
+    ImmutableReference(Source),
+    CapturedAssign(String, Source),
+    BadReturnType(TypeId, TypeId, Source),
+    BadConditionType(TypeId, Source),
+    BadBranchTypes(TypeId, TypeId, Source),
+    DuplicateMember(String, String, Source),
+    RecursiveType(String, Source),
+    ReturnOutsideFunction(Source),
+    JumpOutsideLoop(String, Source),
+    NeverBinding(String, Source),
+    BadResultType(TypeId, Source),
+
+    // Check visibility
+    PrivateMember(String, String, Source),
+    ReadOnlyMember(String, String, Source),
+
+    // Check initialization
+    UninitializedMembers(String, Vec<String>, Source),
+    UninitializedMemberRead(String, String, Source),
+
+    // Explicate enumerations
+    NoVariants(String, Source),
 
     Internal(String),
 }
@@ -30,17 +67,51 @@ impl ErrorTrait for Error
     {
         match self
         {
+            Error::BadTypeArguments(.., source)
+            | Error::BadTypeArgument(.., source)
+            | Error::RecursiveAlias(.., source)
+            | Error::ExpandingTypeArguments(.., source) => Some(source),
+
+            Error::UnknownTrait(.., source)
+            | Error::RecursiveTrait(.., source)
+            | Error::UnknownTraitMethod(.., source) => Some(source),
+
             Error::UnboundSymbolType(.., source)
             | Error::BadDereferenceType(.., source)
             | Error::UnexpectedOperands(.., source)
-            | Error::FailedToInferOperator(.., source) => Some(source),
+            | Error::FailedToInferOperator(.., source)
+            | Error::UnknownType(.., source)
+            | Error::UnknownMember(.., source)
+            | Error::BadAccessType(.., source)
+            | Error::BadCreateType(.., source)
+            | Error::BadIteratorType(.., source)
+            | Error::BadArrayType(.., source)
+            | Error::UnsolvedType(source)
+            | Error::UnsolvedReturnType(.., source)
+            | Error::InfiniteType(.., source) => Some(source),
 
             Error::BadOperatorType(.., source)
             | Error::BadOperandTypes(.., source)
             | Error::BadAssignTypes(.., source)
+            | Error::ImmutableAssign(source)
+            | Error::ImmutableReference(source)
+            | Error::CapturedAssign(.., source)
             | Error::BadReturnType(.., source)
             | Error::BadConditionType(.., source)
-            | Error::BadBranchTypes(.., source) => Some(source),
+            | Error::BadBranchTypes(.., source)
+            | Error::DuplicateMember(.., source)
+            | Error::RecursiveType(.., source)
+            | Error::ReturnOutsideFunction(source)
+            | Error::JumpOutsideLoop(.., source)
+            | Error::NeverBinding(.., source)
+            | Error::BadResultType(.., source) => Some(source),
+
+            Error::PrivateMember(.., source) | Error::ReadOnlyMember(.., source) => Some(source),
+
+            Error::UninitializedMembers(.., source)
+            | Error::UninitializedMemberRead(.., source) => Some(source),
+
+            Error::NoVariants(.., source) => Some(source),
 
             _ => None,
         }
@@ -51,7 +122,8 @@ impl ErrorTrait for Error
         match self
         {
             Error::UnexpectedOperands(description, _)
-            | Error::FailedToInferOperator(description, _) => Some(description),
+            | Error::FailedToInferOperator(description, _)
+            | Error::BadCreateType(description, _) => Some(description),
             _ => None,
         }
     }
@@ -70,10 +142,56 @@ impl fmt::Display for Error
     {
         match &self
         {
+            Error::BadTypeArguments(name, expected, found, _) => write!(
+                f,
+                "Type '{}' expects {} type arguments, found {}",
+                name, expected, found
+            ),
+            Error::BadTypeArgument(name, t, _) =>
+            {
+                write!(f, "Can't instantiate type '{}' with type argument '{}'", name, t)
+            }
+            Error::RecursiveAlias(name, _) => write!(f, "Type alias '{}' can't name itself", name),
+            Error::ExpandingTypeArguments(name, _) => write!(
+                f,
+                "Type '{}' can't apply itself to larger type arguments, since its copies would never end",
+                name
+            ),
+
+            Error::UnknownTrait(name, _) => write!(f, "Unknown trait '{}'", name),
+            Error::RecursiveTrait(name, _) => write!(f, "Type '{}' is its own trait", name),
+            Error::UnknownTraitMethod(trait_name, method_name, _) => write!(
+                f,
+                "Trait '{}' has no method '{}' to implement",
+                trait_name, method_name
+            ),
+
             Error::UnboundSymbolType(name, _) => write!(f, "Unbound Symbol '{}'", name),
             Error::BadDereferenceType(t, _) => write!(f, "Can't dereference type '{}'", t),
             Error::UnexpectedOperands(_, _) => write!(f, "Unexpected operand types"),
             Error::FailedToInferOperator(_, _) => write!(f, "Failed to infer operator type"),
+            Error::UnknownType(name, _) => write!(f, "Unknown type '{}'", name),
+            Error::UnknownMember(type_name, member_name, _) =>
+            {
+                write!(f, "Type '{}' has no member '{}'", type_name, member_name)
+            }
+            Error::BadAccessType(t, _) => write!(f, "Can't access members of type '{}'", t),
+            Error::BadCreateType(..) => write!(f, "Can't create instance"),
+            Error::BadIteratorType(t, _) =>
+            {
+                write!(f, "Can't iterate over type '{}', expected: range or array", t)
+            }
+            Error::BadArrayType(t, _) => write!(f, "Expected an array, found: {}", t),
+            Error::UnsolvedType(_) => write!(f, "Can't infer type, try adding a type annotation"),
+            Error::UnsolvedReturnType(name, _) => write!(
+                f,
+                "Can't infer the return type of function '{}', try adding one with '->'",
+                name
+            ),
+            Error::InfiniteType(variable, t, _) =>
+            {
+                write!(f, "Type '{}' can't be '{}', since it would contain itself", variable, t)
+            }
 
             Error::BadOperatorType(found_type, _) => write!(
                 f,
@@ -107,6 +225,19 @@ impl fmt::Display for Error
                 "Unexpected types in assign statement, expected: {}, found: {}",
                 expected_type, found_type
             ),
+            Error::ImmutableAssign(_) =>
+            {
+                write!(f, "Can't assign through an immutable reference")
+            }
+            Error::ImmutableReference(_) =>
+            {
+                write!(f, "Can't take a mutable reference through an immutable reference")
+            }
+            Error::CapturedAssign(name, _) => write!(
+                f,
+                "Can't assign to '{}' inside a closure, since closures capture a copy of it",
+                name
+            ),
             Error::BadReturnType(found_type, expected_type, _) => write!(
                 f,
                 "Return type of function body doesn't match definition, expected: {}, found: {}",
@@ -122,6 +253,63 @@ impl fmt::Display for Error
                 "Conditional branch types do not match, then: {}, else: {}",
                 then_type, else_type
             ),
+            Error::DuplicateMember(class_name, member_name, _) => write!(
+                f,
+                "Type '{}' has more than one member named '{}'",
+                class_name, member_name
+            ),
+            Error::RecursiveType(name, _) => write!(
+                f,
+                "Type '{}' contains itself, try storing it behind a reference",
+                name
+            ),
+            Error::ReturnOutsideFunction(_) => write!(f, "Can't return from outside a function"),
+            Error::JumpOutsideLoop(jump, _) => write!(f, "Can't {} from outside a loop", jump),
+            Error::NeverBinding(name, _) => write!(
+                f,
+                "Binding '{}' is never given a value, since its value always jumps away",
+                name
+            ),
+            Error::BadResultType(found_type, _) => write!(
+                f,
+                "Unexpected type for the result of the program, expected: int or bool, found: {}",
+                found_type
+            ),
+
+            Error::PrivateMember(type_name, member_name, _) => write!(
+                f,
+                "Member '{}' of type '{}' is private",
+                member_name, type_name
+            ),
+            Error::ReadOnlyMember(type_name, member_name, _) => write!(
+                f,
+                "Member '{}' of type '{}' is read-only",
+                member_name, type_name
+            ),
+
+            Error::UninitializedMembers(name, member_names, _) =>
+            {
+                let _ = write!(f, "Instance '{}' is used before members are initialized: ", name);
+                for (i, member_name) in member_names.into_iter().enumerate()
+                {
+                    let _ = match i
+                    {
+                        0 => write!(f, "{}", member_name),
+                        _ => write!(f, ", {}", member_name),
+                    };
+                }
+                Ok(())
+            }
+            Error::UninitializedMemberRead(name, member_name, _) => write!(
+                f,
+                "Member '{}' of instance '{}' is read before it is initialized",
+                member_name, name
+            ),
+
+            Error::NoVariants(name, _) =>
+            {
+                write!(f, "Enumerated type '{}' has no variants", name)
+            }
 
             Error::Internal(message) => write!(f, "Internal '{}'", message),
         }
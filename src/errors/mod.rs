@@ -2,6 +2,7 @@ use std::fmt::Display;
 
 pub mod compile_error;
 pub mod parse_error;
+pub mod runtime_error;
 pub mod s_expression_error;
 
 pub use crate::source::Source;
@@ -67,6 +68,36 @@ impl<TResult, TError: ErrorTrait> ResultLog<TResult, TError>
         }
     }
 
+    ///
+    /// Pass a successful result to some function, keeping warnings from both
+    ///
+    pub fn and_then<TNext, F>(self, f: F) -> ResultLog<TNext, TError>
+    where
+        F: FnOnce(TResult) -> ResultLog<TNext, TError>,
+    {
+        let (result, mut warnings) = match self
+        {
+            ResultLog::Ok(result) => (result, Vec::new()),
+            ResultLog::Warn(result, warnings) => (result, warnings),
+            ResultLog::Error(errors, warnings) => return ResultLog::Error(errors, warnings),
+        };
+
+        match f(result)
+        {
+            ResultLog::Ok(next) => ResultLog::maybe_warn(next, warnings),
+            ResultLog::Warn(next, mut new_warnings) =>
+            {
+                warnings.append(&mut new_warnings);
+                ResultLog::Warn(next, warnings)
+            }
+            ResultLog::Error(errors, mut new_warnings) =>
+            {
+                warnings.append(&mut new_warnings);
+                ResultLog::Error(errors, warnings)
+            }
+        }
+    }
+
     pub fn add_warning(self, warning: TError) -> Self
     {
         match self
@@ -8,11 +8,16 @@ pub enum Error
     UnknownSymbol(String, Source),
 
     UnexpectedKeyword(String, Source),
+    BadEscapeSequence(String, Source),
+    BadFloat(String, Source),
 
     BadFunctionName(String, Source),
     BadFunctionLayout(String, Source),
     BadFunctionArgument(String, Source),
 
+    BadTypeDefinition(String, Source),
+    BadType(String, Source),
+
     Internal(String),
 }
 
@@ -26,11 +31,16 @@ impl ErrorTrait for Error
             Error::UnknownSymbol(_, source) => Some(source),
 
             Error::UnexpectedKeyword(_, source) => Some(source),
+            Error::BadEscapeSequence(_, source) => Some(source),
+            Error::BadFloat(_, source) => Some(source),
 
             Error::BadFunctionName(_, source) => Some(source),
             Error::BadFunctionLayout(_, source) => Some(source),
             Error::BadFunctionArgument(_, source) => Some(source),
 
+            Error::BadTypeDefinition(_, source) => Some(source),
+            Error::BadType(_, source) => Some(source),
+
             Error::Internal(..) => None,
         }
     }
@@ -42,11 +52,16 @@ impl ErrorTrait for Error
             Error::UnknownSymbol(..) => None,
 
             Error::UnexpectedKeyword(..) => None,
+            Error::BadEscapeSequence(..) => None,
+            Error::BadFloat(..) => None,
 
             Error::BadFunctionName(description, _) => Some(description),
             Error::BadFunctionLayout(description, _) => Some(description),
             Error::BadFunctionArgument(description, _) => Some(description),
 
+            Error::BadTypeDefinition(description, _) => Some(description),
+            Error::BadType(description, _) => Some(description),
+
             Error::Internal(..) => None,
         }
     }
@@ -72,11 +87,19 @@ impl fmt::Display for Error
             Error::UnknownSymbol(symbol, _) => write!(f, "Unknown symbol '{}'", symbol),
 
             Error::UnexpectedKeyword(symbol, _) => write!(f, "Unexpected keyword '{}'", symbol),
+            Error::BadEscapeSequence(sequence, _) =>
+            {
+                write!(f, "Unknown escape sequence '{}' in string", sequence)
+            }
+            Error::BadFloat(symbol, _) => write!(f, "Invalid float literal '{}'", symbol),
 
             Error::BadFunctionName(..) => write!(f, "Bad function name"),
             Error::BadFunctionLayout(..) => write!(f, "Bad function layout"),
             Error::BadFunctionArgument(..) => write!(f, "Bad function argument"),
 
+            Error::BadTypeDefinition(..) => write!(f, "Bad type definition"),
+            Error::BadType(..) => write!(f, "Bad type"),
+
             Error::Internal(message) => write!(f, "Internal '{}'", message),
         }
     }
@@ -0,0 +1,54 @@
+pub use super::*;
+
+pub enum Error
+{
+    DivideByZero(Source),
+    IndexOutOfBounds(i64, usize, Source),
+    NegativeLength(i64, Source),
+    Unsupported(String, Source),
+
+    Internal(String),
+}
+
+impl ErrorTrait for Error
+{
+    fn get_source(&self) -> Option<&Source>
+    {
+        match self
+        {
+            Error::DivideByZero(source)
+            | Error::IndexOutOfBounds(.., source)
+            | Error::NegativeLength(.., source)
+            | Error::Unsupported(.., source) => Some(source),
+
+            Error::Internal(_) => None,
+        }
+    }
+}
+
+use std::fmt;
+
+impl fmt::Display for Error
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match &self
+        {
+            Error::DivideByZero(_) => write!(f, "Divided by zero"),
+            Error::IndexOutOfBounds(index, length, _) => write!(
+                f,
+                "Index {} is out of bounds for array of length {}",
+                index, length
+            ),
+            Error::NegativeLength(length, _) =>
+            {
+                write!(f, "Can't make an array of negative length {}", length)
+            }
+            Error::Unsupported(description, _) =>
+            {
+                write!(f, "Can't interpret {} yet", description)
+            }
+            Error::Internal(message) => write!(f, "Internal '{}'", message),
+        }
+    }
+}
@@ -4,6 +4,7 @@ pub enum Error
 {
     Internal(String),
     UnclosedBracket(String, Source),
+    UnclosedString(Source),
     FailedToParse(Source),
 }
 
@@ -15,6 +16,7 @@ impl ErrorTrait for Error
         {
             Error::Internal(_) => None,
             Error::UnclosedBracket(_, source) => Some(source),
+            Error::UnclosedString(source) => Some(source),
             Error::FailedToParse(source) => Some(source),
         }
     }
@@ -30,6 +32,7 @@ impl fmt::Display for Error
         {
             Error::Internal(message) => write!(f, "Internal error: {}", message,),
             Error::UnclosedBracket(bracket, _) => write!(f, "Unclosed Bracket '{}'", bracket,),
+            Error::UnclosedString(_) => write!(f, "Unclosed String"),
             Error::FailedToParse(_) => write!(f, "Failed to parse text"),
         }
     }
@@ -0,0 +1,649 @@
+use crate::errors::runtime_error::*;
+use crate::language::node::*;
+use crate::utilities::*;
+
+use std::io::Write;
+use std::rc::Rc;
+
+mod value;
+
+pub use value::*;
+
+///
+/// An evaluator for typed node trees, which can run programs without going through C
+///
+/// - Nodes are evaluated after type checking, before any of the flattening passes
+/// - Bindings and definitions at the root of a program are kept between calls to `evaluate`
+/// - Nodes are assumed to be well-typed, so mismatched values are internal errors
+///
+pub struct Interpreter
+{
+    scope: Indirect<Scope>,
+}
+
+///
+/// Anything that stops a node from producing a value
+///
+/// - Jumps are caught by the enclosing function or loop
+///
+enum Interrupt
+{
+    Jump(JumpMode, Value),
+    Error(Error),
+}
+
+impl From<Error> for Interrupt
+{
+    fn from(error: Error) -> Self
+    {
+        Interrupt::Error(error)
+    }
+}
+
+type Evaluation = Result<Value, Interrupt>;
+
+impl Interpreter
+{
+    pub fn new() -> Interpreter
+    {
+        Interpreter {
+            scope: Scope::root(),
+        }
+    }
+
+    ///
+    /// Evaluate a typed node, keeping any bindings and definitions from a root sequence
+    ///
+    pub fn evaluate(&mut self, node: &Node) -> ResultLog<Value, Error>
+    {
+        let result = match node
+        {
+            Node::Sequence(sequence) => self.evaluate_sequence(sequence, &self.scope),
+            node => self.evaluate_node(node, &self.scope),
+        };
+
+        match result
+        {
+            Ok(value) => ResultLog::Ok(value),
+            Err(Interrupt::Error(error)) => ResultLog::new_error(error),
+            Err(Interrupt::Jump(mode, _)) => ResultLog::new_error(Error::Internal(format!(
+                "Jump '{}' escaped the program",
+                mode
+            ))),
+        }
+    }
+
+    ///
+    /// Get the current value of a binding or definition from a root sequence
+    ///
+    pub fn lookup(&self, name: &String) -> Option<Value>
+    {
+        match self.scope.borrow().lookup(name)
+        {
+            Some(slot) => Some(slot.borrow().clone()),
+            None => None,
+        }
+    }
+
+    fn evaluate_node(&self, node: &Node, scope: &Indirect<Scope>) -> Evaluation
+    {
+        match node
+        {
+            Node::Nothing(_) | Node::Comment(_) | Node::Alias(_) => Ok(Value::Void),
+
+            Node::Integer(literal) => Ok(integer(literal.get_value())),
+            Node::Float(float) => Ok(Value::Float(float.get_value())),
+            Node::Boolean(boolean) => Ok(Value::Boolean(boolean.get_value())),
+            Node::Text(text) => Ok(Value::String(Rc::new(text.get_value().clone()))),
+
+            Node::Variable(variable) =>
+            {
+                let slot = lookup(variable, scope)?;
+                let value = slot.borrow().clone();
+                Ok(value)
+            }
+
+            Node::Call(call) => self.evaluate_call(call, scope),
+
+            Node::Reference(reference) =>
+            {
+                let slot = self.evaluate_place(reference.get_target(), scope)?;
+                Ok(Value::Reference(slot))
+            }
+            Node::Dereference(dereference) =>
+            {
+                match self.evaluate_node(dereference.get_target(), scope)?
+                {
+                    Value::Reference(slot) =>
+                    {
+                        let value = slot.borrow().clone();
+                        Ok(value)
+                    }
+                    value => Err(unexpected_value(&value, "a reference")),
+                }
+            }
+            Node::Assign(assign) =>
+            {
+                let slot = self.evaluate_place(assign.get_lhs(), scope)?;
+                let value = self.evaluate_node(assign.get_rhs(), scope)?;
+
+                *slot.borrow_mut() = value;
+                Ok(Value::Void)
+            }
+
+            Node::Binding(binding) =>
+            {
+                let value = self.evaluate_node(binding.get_binding(), scope)?;
+                scope.borrow_mut().add(binding.get_name().clone(), value);
+
+                Ok(Value::Void)
+            }
+
+            Node::Sequence(sequence) => match sequence.get_mode()
+            {
+                SequenceMode::Scope => self.evaluate_sequence(sequence, &Scope::child(scope)),
+                SequenceMode::Transparent => self.evaluate_sequence(sequence, scope),
+            },
+            Node::Conditional(conditional) =>
+            {
+                let condition = self.evaluate_node(conditional.get_condition(), scope)?;
+                match expect_boolean(&condition)?
+                {
+                    true => self.evaluate_node(conditional.get_then(), scope),
+                    false => self.evaluate_node(conditional.get_else(), scope),
+                }
+            }
+            Node::WhileLoop(while_loop) =>
+            {
+                loop
+                {
+                    self.evaluate_node(while_loop.get_prelude(), scope)?;
+
+                    let condition = self.evaluate_node(while_loop.get_condition(), scope)?;
+                    if !expect_boolean(&condition)?
+                    {
+                        break;
+                    }
+
+                    match self.evaluate_node(while_loop.get_body(), scope)
+                    {
+                        Ok(_) | Err(Interrupt::Jump(JumpMode::Continue, _)) => (),
+                        Err(Interrupt::Jump(JumpMode::Break, _)) => break,
+                        Err(interrupt) => return Err(interrupt),
+                    }
+                }
+
+                Ok(Value::Void)
+            }
+            Node::Jump(jump) =>
+            {
+                let value = self.evaluate_node(jump.get_value(), scope)?;
+                Err(Interrupt::Jump(jump.get_mode(), value))
+            }
+
+            // Functions are added to their scope before the rest of their sequence is evaluated
+            Node::Function(_) => Ok(Value::Void),
+            Node::Closure(closure) =>
+            {
+                let function = FunctionValue::new(
+                    closure.get_arguments(),
+                    closure.get_body(),
+                    Scope::capture(scope),
+                );
+                Ok(Value::Function(Rc::new(function)))
+            }
+
+            Node::PrimitiveOperator(operator) => Err(Error::Unsupported(
+                format!(
+                    "primitive operator '{}' outside of a call",
+                    operator.get_value()
+                ),
+                operator.get_source(),
+            )
+            .into()),
+            Node::Class(class) => Err(Error::Unsupported(
+                format!("type definition '{}'", class.get_name()),
+                class.get_source(),
+            )
+            .into()),
+            Node::Access(access) => Err(Error::Unsupported(
+                format!("member access '{}'", access.get_property()),
+                access.get_source(),
+            )
+            .into()),
+            Node::CNode(cnode) =>
+            {
+                Err(Error::Internal(format!("Can't interpret flattened C node: {}", cnode)).into())
+            }
+        }
+    }
+
+    ///
+    /// Evaluate the nodes of a sequence in some scope, resulting in the value of its result node
+    ///
+    /// - All functions in the sequence are added first, so they can be called from anywhere in it
+    ///
+    fn evaluate_sequence(&self, sequence: &Sequence, scope: &Indirect<Scope>) -> Evaluation
+    {
+        for node in sequence.get_nodes()
+        {
+            if let Node::Function(function) = node
+            {
+                // note: Functions hold the scope they are defined in, so their scope is never freed
+                let value = FunctionValue::new(
+                    function.get_arguments(),
+                    function.get_body(),
+                    scope.clone(),
+                );
+                scope
+                    .borrow_mut()
+                    .add(function.get_name().clone(), Value::Function(Rc::new(value)));
+            }
+        }
+
+        let result_index = sequence.get_result_index();
+        let mut result = Value::Void;
+
+        for (i, node) in sequence.get_nodes().iter().enumerate()
+        {
+            let value = self.evaluate_node(node, scope)?;
+            if Some(i) == result_index
+            {
+                result = value;
+            }
+        }
+
+        Ok(result)
+    }
+
+    ///
+    /// Evaluate a node to the slot holding its value, so it can be assigned to or referenced
+    ///
+    /// - Nodes that aren't stored anywhere are given a new slot
+    ///
+    fn evaluate_place(&self, node: &Node, scope: &Indirect<Scope>) -> Result<Slot, Interrupt>
+    {
+        match node
+        {
+            Node::Variable(variable) => lookup(variable, scope),
+            Node::Dereference(dereference) =>
+            {
+                match self.evaluate_node(dereference.get_target(), scope)?
+                {
+                    Value::Reference(slot) => Ok(slot),
+                    value => Err(unexpected_value(&value, "a reference")),
+                }
+            }
+            Node::Call(call) if is_primitive_call(call, primitive::Operator::ArrayAt) =>
+            {
+                self.evaluate_array_element(call, scope)
+            }
+            node =>
+            {
+                let value = self.evaluate_node(node, scope)?;
+                Ok(Slot::new(value))
+            }
+        }
+    }
+
+    fn evaluate_call(&self, call: &Call, scope: &Indirect<Scope>) -> Evaluation
+    {
+        if let Node::PrimitiveOperator(operator) = call.get_operator()
+        {
+            return self.evaluate_primitive_call(operator.get_value(), call, scope);
+        }
+
+        let function = match self.evaluate_node(call.get_operator(), scope)?
+        {
+            Value::Function(function) => function,
+            value => return Err(unexpected_value(&value, "a function")),
+        };
+
+        let mut arguments = Vec::new();
+        for operand in call.get_operands()
+        {
+            arguments.push(self.evaluate_node(operand, scope)?);
+        }
+
+        // Arguments are bound in a new scope under the one the function was defined in
+
+        let function_scope = Scope::child(&function.get_scope());
+        for (name, value) in function.get_arguments().iter().zip(arguments)
+        {
+            function_scope.borrow_mut().add(name.clone(), value);
+        }
+
+        match self.evaluate_node(function.get_body(), &function_scope)
+        {
+            Err(Interrupt::Jump(JumpMode::Return, value)) => Ok(value),
+            result => result,
+        }
+    }
+
+    ///
+    /// Evaluate a call to a primitive operator
+    ///
+    /// - Logical operators only evaluate their second operand if needed, like their C versions
+    /// - Integers are C ints, so arithmetic wraps at 32 bits
+    ///
+    fn evaluate_primitive_call(
+        &self,
+        operator: primitive::Operator,
+        call: &Call,
+        scope: &Indirect<Scope>,
+    ) -> Evaluation
+    {
+        use primitive::Operator;
+
+        match operator
+        {
+            Operator::And | Operator::Or =>
+            {
+                let operands = call.get_operands();
+                let a = expect_boolean(&self.evaluate_node(&operands[0], scope)?)?;
+
+                return match (operator, a)
+                {
+                    (Operator::And, false) => Ok(Value::Boolean(false)),
+                    (Operator::Or, true) => Ok(Value::Boolean(true)),
+                    _ => self.evaluate_node(&operands[1], scope),
+                };
+            }
+            Operator::ArrayAt =>
+            {
+                let slot = self.evaluate_array_element(call, scope)?;
+                let value = slot.borrow().clone();
+                return Ok(value);
+            }
+            _ => (),
+        }
+
+        let mut operands = Vec::new();
+        for operand in call.get_operands()
+        {
+            operands.push(self.evaluate_node(operand, scope)?);
+        }
+
+        let result = match (operator, operands.as_slice())
+        {
+            // Arithmetic operators
+            (Operator::Subtract, [Value::Integer(a)]) => integer(a.wrapping_neg()),
+            (Operator::Subtract, [Value::Float(a)]) => Value::Float(-a),
+
+            (Operator::Divide, [Value::Integer(_), Value::Integer(0)])
+            | (Operator::Modulo, [Value::Integer(_), Value::Integer(0)]) =>
+            {
+                return Err(Error::DivideByZero(call.get_source()).into());
+            }
+
+            (Operator::Add, [a, b])
+            | (Operator::Subtract, [a, b])
+            | (Operator::Multiply, [a, b])
+            | (Operator::Divide, [a, b])
+            | (Operator::Modulo, [a, b]) => match (operator, get_numbers(a, b)?)
+            {
+                (Operator::Add, Numbers::Integers(a, b)) => integer(a.wrapping_add(b)),
+                (Operator::Subtract, Numbers::Integers(a, b)) => integer(a.wrapping_sub(b)),
+                (Operator::Multiply, Numbers::Integers(a, b)) => integer(a.wrapping_mul(b)),
+                (Operator::Divide, Numbers::Integers(a, b)) => integer(a.wrapping_div(b)),
+                (_, Numbers::Integers(a, b)) => integer(a.wrapping_rem(b)),
+
+                (Operator::Add, Numbers::Floats(a, b)) => Value::Float(a + b),
+                (Operator::Subtract, Numbers::Floats(a, b)) => Value::Float(a - b),
+                (Operator::Multiply, Numbers::Floats(a, b)) => Value::Float(a * b),
+                (Operator::Divide, Numbers::Floats(a, b)) => Value::Float(a / b),
+                (_, Numbers::Floats(a, b)) => Value::Float(a % b),
+            },
+
+            // Comparison operators
+            (Operator::Equal, [Value::Boolean(a), Value::Boolean(b)]) => Value::Boolean(a == b),
+            (Operator::NotEqual, [Value::Boolean(a), Value::Boolean(b)]) => Value::Boolean(a != b),
+
+            (Operator::Equal, [a, b])
+            | (Operator::NotEqual, [a, b])
+            | (Operator::Less, [a, b])
+            | (Operator::Greater, [a, b])
+            | (Operator::LessEqual, [a, b])
+            | (Operator::GreaterEqual, [a, b]) =>
+            {
+                let ordering = match get_numbers(a, b)?
+                {
+                    Numbers::Integers(a, b) => a.partial_cmp(&b),
+                    Numbers::Floats(a, b) => a.partial_cmp(&b),
+                };
+
+                // Comparisons with NaN are always false, except for not-equal
+
+                use std::cmp::Ordering;
+                let result = match (operator, ordering)
+                {
+                    (Operator::NotEqual, None) => true,
+                    (_, None) => false,
+
+                    (Operator::Equal, Some(ordering)) => ordering == Ordering::Equal,
+                    (Operator::NotEqual, Some(ordering)) => ordering != Ordering::Equal,
+                    (Operator::Less, Some(ordering)) => ordering == Ordering::Less,
+                    (Operator::Greater, Some(ordering)) => ordering == Ordering::Greater,
+                    (Operator::LessEqual, Some(ordering)) => ordering != Ordering::Greater,
+                    (_, Some(ordering)) => ordering != Ordering::Less,
+                };
+
+                Value::Boolean(result)
+            }
+
+            // Logical operators
+            (Operator::Not, [Value::Boolean(a)]) => Value::Boolean(!a),
+            (Operator::ExclusiveOr, [Value::Boolean(a), Value::Boolean(b)]) =>
+            {
+                Value::Boolean(a ^ b)
+            }
+
+            // Conversion operators
+            (Operator::ToInteger, [Value::Integer(a)]) => Value::Integer(*a),
+            (Operator::ToInteger, [Value::Float(a)]) => integer(*a as i64),
+            (Operator::ToFloat, [Value::Integer(a)]) => Value::Float(*a as f64),
+            (Operator::ToFloat, [Value::Float(a)]) => Value::Float(*a),
+
+            // Iterator operators
+            (Operator::Range, [Value::Integer(start), Value::Integer(end)]) =>
+            {
+                Value::Range(*start, *end)
+            }
+            (Operator::MakeIterator, [iterable]) => match iterable.dereference_all()
+            {
+                Value::Array(elements) => Value::Range(0, elements.len() as i64),
+                iterable =>
+                {
+                    let (start, end) = get_range(&iterable)?;
+                    Value::Range(start, end)
+                }
+            },
+            (Operator::IteratorHasNext, [iterator]) =>
+            {
+                let (n, end) = get_range(iterator)?;
+                Value::Boolean(n < end)
+            }
+            (Operator::IteratorNext, [Value::Reference(slot), iterable]) =>
+            {
+                let (n, end) = get_range(&slot.borrow())?;
+                *slot.borrow_mut() = Value::Range(n + 1, end);
+
+                // Arrays are iterated over by index
+                match iterable.dereference_all()
+                {
+                    Value::Array(elements) => elements[n as usize].borrow().clone(),
+                    _ => Value::Integer(n),
+                }
+            }
+
+            // Array operators
+            (Operator::ArrayLiteral, elements) =>
+            {
+                let slots = elements.iter().cloned().map(Slot::new).collect();
+                Value::Array(Rc::new(slots))
+            }
+            (Operator::MakeArray, [Value::Integer(length), _]) if *length < 0 =>
+            {
+                return Err(Error::NegativeLength(*length, call.get_source()).into());
+            }
+            (Operator::MakeArray, [Value::Integer(length), value]) =>
+            {
+                let slots = (0..*length).map(|_| Slot::new(value.clone())).collect();
+                Value::Array(Rc::new(slots))
+            }
+            (Operator::ArrayLength, [array]) => match array.dereference_all()
+            {
+                Value::Array(elements) => Value::Integer(elements.len() as i64),
+                value => return Err(unexpected_value(&value, "an array")),
+            },
+
+            // Other operators
+            (Operator::Print, [Value::String(text)]) =>
+            {
+                print!("{}", text);
+                let _ = std::io::stdout().flush();
+
+                Value::Void
+            }
+
+            (Operator::Create, _)
+            | (Operator::HeapAllocate, _)
+            | (Operator::HeapFree, _)
+            | (Operator::MakeClosure, _)
+            | (Operator::ApplyClosure, _)
+            | (Operator::Return, _) =>
+            {
+                return Err(Error::Unsupported(
+                    format!("primitive operator '{}'", operator),
+                    call.get_source(),
+                )
+                .into());
+            }
+            _ =>
+            {
+                return Err(Error::Internal(format!(
+                    "Unexpected operands for primitive operator '{}'",
+                    operator
+                ))
+                .into());
+            }
+        };
+
+        Ok(result)
+    }
+
+    ///
+    /// Get the slot for the element of an array given to the `at` operator, checking its bounds
+    ///
+    fn evaluate_array_element(
+        &self,
+        call: &Call,
+        scope: &Indirect<Scope>,
+    ) -> Result<Slot, Interrupt>
+    {
+        let operands = call.get_operands();
+
+        let array = self.evaluate_node(&operands[0], scope)?.dereference_all();
+        let index = self.evaluate_node(&operands[1], scope)?;
+
+        match (array, index)
+        {
+            (Value::Array(elements), Value::Integer(index)) =>
+            {
+                if index < 0 || index as usize >= elements.len()
+                {
+                    return Err(
+                        Error::IndexOutOfBounds(index, elements.len(), call.get_source()).into(),
+                    );
+                }
+
+                Ok(elements[index as usize].clone())
+            }
+            (array, _) => Err(unexpected_value(&array, "an array and an index")),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Values                                   */
+/* -------------------------------------------------------------------------- */
+
+///
+/// The operands of an arithmetic or comparison operator, where integers are converted to floats
+///     if used with a float
+///
+enum Numbers
+{
+    Integers(i64, i64),
+    Floats(f64, f64),
+}
+
+fn get_numbers(a: &Value, b: &Value) -> Result<Numbers, Interrupt>
+{
+    match (a, b)
+    {
+        (Value::Integer(a), Value::Integer(b)) => Ok(Numbers::Integers(*a, *b)),
+        (Value::Float(a), Value::Float(b)) => Ok(Numbers::Floats(*a, *b)),
+        (Value::Integer(a), Value::Float(b)) => Ok(Numbers::Floats(*a as f64, *b)),
+        (Value::Float(a), Value::Integer(b)) => Ok(Numbers::Floats(*a, *b as f64)),
+        (Value::Integer(_), b) | (Value::Float(_), b) => Err(unexpected_value(b, "a number")),
+        (a, _) => Err(unexpected_value(a, "a number")),
+    }
+}
+
+///
+/// Get the state of a range iterator, which may be given as a reference
+///
+fn get_range(value: &Value) -> Result<(i64, i64), Interrupt>
+{
+    match value.dereference_all()
+    {
+        Value::Range(n, end) => Ok((n, end)),
+        value => Err(unexpected_value(&value, "a range")),
+    }
+}
+
+fn integer(value: i64) -> Value
+{
+    Value::Integer(value as i32 as i64)
+}
+
+fn expect_boolean(value: &Value) -> Result<bool, Interrupt>
+{
+    match value
+    {
+        Value::Boolean(value) => Ok(*value),
+        value => Err(unexpected_value(value, "a boolean")),
+    }
+}
+
+fn unexpected_value(value: &Value, expected: &str) -> Interrupt
+{
+    Error::Internal(format!("Expected {}, found: {}", expected, value)).into()
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Helpers                                  */
+/* -------------------------------------------------------------------------- */
+
+fn lookup(variable: &Variable, scope: &Indirect<Scope>) -> Result<Slot, Interrupt>
+{
+    match scope.borrow().lookup(variable.get_name())
+    {
+        Some(slot) => Ok(slot),
+        None => Err(Error::Internal(format!(
+            "Variable '{}' isn't bound to a value",
+            variable.get_name()
+        ))
+        .into()),
+    }
+}
+
+fn is_primitive_call(call: &Call, operator: primitive::Operator) -> bool
+{
+    match call.get_operator()
+    {
+        Node::PrimitiveOperator(call_operator) => call_operator.get_value() == operator,
+        _ => false,
+    }
+}
@@ -0,0 +1,212 @@
+use crate::language::node::*;
+use crate::utilities::Indirect;
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+///
+/// A place that holds a value, which references can point to
+///
+pub type Slot = Indirect<Value>;
+
+/* -------------------------------------------------------------------------- */
+/*                                    Value                                   */
+/* -------------------------------------------------------------------------- */
+
+///
+/// The result of evaluating a node
+///
+/// - References, arrays and functions share their data when copied, like their C versions
+///
+#[derive(Clone)]
+pub enum Value
+{
+    Void,
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    String(Rc<String>),
+    Reference(Slot),
+    Range(i64, i64),
+    Array(Rc<Vec<Slot>>),
+    Function(Rc<FunctionValue>),
+}
+
+impl Value
+{
+    ///
+    /// Follow references until a value that isn't a reference is found
+    ///
+    pub fn dereference_all(&self) -> Value
+    {
+        match self
+        {
+            Value::Reference(slot) => slot.borrow().dereference_all(),
+            value => value.clone(),
+        }
+    }
+
+    ///
+    /// Get the int that the C runtime prints for a program with this result
+    ///
+    /// - Booleans are C ints, and programs without a result return 0 from main
+    /// - Other values can't be returned from main (see Check)
+    ///
+    pub fn get_program_result(&self) -> Option<i64>
+    {
+        match self.dereference_all()
+        {
+            Value::Void => Some(0),
+            Value::Integer(value) => Some(value),
+            Value::Boolean(value) => Some(value as i64),
+            _ => None,
+        }
+    }
+}
+
+///
+/// A function or closure along with the scope it can see bindings and definitions from
+///
+pub struct FunctionValue
+{
+    arguments: Vec<String>,
+    body:      Node,
+    scope:     Indirect<Scope>,
+}
+impl FunctionValue
+{
+    pub fn new(arguments: &Vec<Argument>, body: &Node, scope: Indirect<Scope>) -> Self
+    {
+        Self {
+            arguments: arguments
+                .iter()
+                .map(|argument| argument.get_name().clone())
+                .collect(),
+            body: body.clone(),
+            scope,
+        }
+    }
+
+    get!(get_arguments -> arguments : &Vec<String>);
+    get!(get_body      -> body : &Node);
+    get!(get_scope     -> scope.clone() : Indirect<Scope>);
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                    Scope                                   */
+/* -------------------------------------------------------------------------- */
+
+///
+/// Maps names to the slots holding their values, falling back to a parent scope
+///
+pub struct Scope
+{
+    slots:  HashMap<String, Slot>,
+    parent: Option<Indirect<Scope>>,
+}
+impl Scope
+{
+    pub fn root() -> Indirect<Scope>
+    {
+        Indirect::new(Scope {
+            slots:  HashMap::new(),
+            parent: None,
+        })
+    }
+
+    pub fn child(parent: &Indirect<Scope>) -> Indirect<Scope>
+    {
+        Indirect::new(Scope {
+            slots:  HashMap::new(),
+            parent: Some(parent.clone()),
+        })
+    }
+
+    ///
+    /// Create a new root scope holding copies of all values visible from some scope
+    ///
+    /// - Closures capture the values of their bindings when they are created (see ConvertClosures)
+    ///
+    pub fn capture(scope: &Indirect<Scope>) -> Indirect<Scope>
+    {
+        let captured = Scope::root();
+
+        let mut current = Some(scope.clone());
+        while let Some(scope) = current
+        {
+            for (name, slot) in &scope.borrow().slots
+            {
+                // Inner scopes are visited first, so they shadow outer bindings
+
+                if !captured.borrow().slots.contains_key(name)
+                {
+                    let value = slot.borrow().clone();
+                    captured.borrow_mut().add(name.clone(), value);
+                }
+            }
+
+            current = scope.borrow().parent.clone();
+        }
+
+        captured
+    }
+
+    pub fn add(&mut self, name: String, value: Value)
+    {
+        self.slots.insert(name, Slot::new(value));
+    }
+
+    pub fn lookup(&self, name: &String) -> Option<Slot>
+    {
+        match (self.slots.get(name), &self.parent)
+        {
+            (Some(slot), _) => Some(slot.clone()),
+            (None, Some(parent)) => parent.borrow().lookup(name),
+            (None, None) => None,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Display                                  */
+/* -------------------------------------------------------------------------- */
+
+impl std::fmt::Display for Value
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            Value::Void => write!(f, "nothing"),
+            Value::Integer(value) => write!(f, "{}", value),
+            Value::Float(value) => write!(f, "{:?}", value),
+            Value::Boolean(value) => write!(f, "{}", value),
+            Value::String(value) => write!(f, "{:?}", value),
+            Value::Reference(slot) => write!(f, "(ref {})", slot.borrow()),
+            Value::Range(start, end) => write!(f, "(range {} {})", start, end),
+            Value::Array(elements) =>
+            {
+                let _ = write!(f, "[");
+                for (i, element) in elements.iter().enumerate()
+                {
+                    match i
+                    {
+                        0 =>
+                        {
+                            let _ = write!(f, "{}", element.borrow());
+                        }
+                        _ =>
+                        {
+                            let _ = write!(f, " {}", element.borrow());
+                        }
+                    }
+                }
+                write!(f, "]")
+            }
+            Value::Function(function) =>
+            {
+                write!(f, "(fn [{}])", function.get_arguments().join(" "))
+            }
+        }
+    }
+}
@@ -12,7 +12,7 @@ pub mod types;
 /*                                 Basic Stuff                                */
 /* -------------------------------------------------------------------------- */
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum ReferenceMode
 {
     Mutable,
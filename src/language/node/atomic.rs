@@ -5,10 +5,10 @@ use super::*;
 /*                                Data Literals                               */
 /* -------------------------------------------------------------------------- */
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Nothing
 {
-    node_type: Indirect<Type>,
+    node_type: TypeId,
     source:    Source,
 }
 impl Nothing
@@ -16,28 +16,28 @@ impl Nothing
     pub fn new(source: Source) -> Self
     {
         Self {
-            node_type: basic_types::indirect::void(),
+            node_type: basic_types::interned::void(),
             source,
         }
     }
 
-    pub fn new_typed(node_type: Indirect<Type>, source: Source) -> Self
+    pub fn new_typed(node_type: TypeId, source: Source) -> Self
     {
         Self { node_type, source }
     }
 
-    get!(get_type -> node_type.clone() : Indirect<Type>);
-    get!(borrow_type -> node_type.borrow() : Ref<Type>);
+    get!(get_type -> node_type : TypeId);
+    set!(set_type -> node_type : TypeId);
 
     get!(get_source -> source.clone() : Source);
 }
 
 impl_recur! { Nothing [] }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Comment
 {
-    node_type: Indirect<Type>,
+    node_type: TypeId,
     content:   String,
     source:    Source,
 }
@@ -46,14 +46,13 @@ impl Comment
     pub fn new(content: String, source: Source) -> Self
     {
         Self {
-            node_type: basic_types::indirect::unknown(),
+            node_type: basic_types::interned::unknown(),
             content,
             source,
         }
     }
 
-    get!(get_type -> node_type.clone() : Indirect<Type>);
-    get!(borrow_type -> node_type.borrow() : Ref<Type>);
+    get!(get_type -> node_type : TypeId);
 
     get!(get_content -> content : &String);
 
@@ -62,11 +61,11 @@ impl Comment
 
 impl_recur! { Comment [] }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Integer
 {
     value:     i64,
-    node_type: Indirect<Type>,
+    node_type: TypeId,
     source:    Source,
 }
 impl Integer
@@ -75,25 +74,50 @@ impl Integer
     {
         Self {
             value,
-            node_type: basic_types::indirect::integer(),
+            node_type: basic_types::interned::integer(),
             source,
         }
     }
 
     get!(get_value -> value : i64);
-    get!(get_type -> node_type.clone() : Indirect<Type>);
-    get!(borrow_type -> node_type.borrow() : Ref<Type>);
+    get!(get_type -> node_type : TypeId);
 
     get!(get_source -> source.clone() : Source);
 }
 
 impl_recur! { Integer [] }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+pub struct Float
+{
+    value:     f64,
+    node_type: TypeId,
+    source:    Source,
+}
+impl Float
+{
+    pub fn new(value: f64, source: Source) -> Self
+    {
+        Self {
+            value,
+            node_type: basic_types::interned::float(),
+            source,
+        }
+    }
+
+    get!(get_value -> value : f64);
+    get!(get_type -> node_type : TypeId);
+
+    get!(get_source -> source.clone() : Source);
+}
+
+impl_recur! { Float [] }
+
+#[derive(Clone, Debug)]
 pub struct Boolean
 {
     value:     bool,
-    node_type: Indirect<Type>,
+    node_type: TypeId,
     source:    Source,
 }
 impl Boolean
@@ -102,29 +126,57 @@ impl Boolean
     {
         Self {
             value,
-            node_type: basic_types::indirect::boolean(),
+            node_type: basic_types::interned::boolean(),
             source,
         }
     }
 
     get!(get_value -> value : bool);
-    get!(get_type -> node_type.clone() : Indirect<Type>);
-    get!(borrow_type -> node_type.borrow() : Ref<Type>);
+    get!(get_type -> node_type : TypeId);
 
     get!(get_source -> source.clone() : Source);
 }
 
 impl_recur!{ Boolean [] }
 
+///
+/// A string literal, holding its text with any escape sequences already replaced
+///
+#[derive(Clone, Debug)]
+pub struct Text
+{
+    value:     String,
+    node_type: TypeId,
+    source:    Source,
+}
+impl Text
+{
+    pub fn new(value: String, source: Source) -> Self
+    {
+        Self {
+            value,
+            node_type: basic_types::interned::string(),
+            source,
+        }
+    }
+
+    get!(get_value -> value : &String);
+    get!(get_type -> node_type : TypeId);
+
+    get!(get_source -> source.clone() : Source);
+}
+
+impl_recur!{ Text [] }
+
 /* -------------------------------------------------------------------------- */
 /*                                  Variables                                 */
 /* -------------------------------------------------------------------------- */
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Variable
 {
     name:      String,
-    node_type: Indirect<Type>,
+    node_type: TypeId,
     source:    Source,
 }
 impl Variable
@@ -133,11 +185,11 @@ impl Variable
     {
         return Self {
             name,
-            node_type: basic_types::indirect::unknown(),
+            node_type: basic_types::interned::unknown(),
             source,
         };
     }
-    pub fn new_typed(name: String, node_type: Indirect<Type>, source: Source) -> Self
+    pub fn new_typed(name: String, node_type: TypeId, source: Source) -> Self
     {
         return Self {
             name,
@@ -149,9 +201,8 @@ impl Variable
     get!(get_name     -> name : &String);
     get!(get_name_mut -> name : &mut String);
 
-    get!(get_type    -> node_type.clone() : Indirect<Type>);
-    get!(borrow_type -> node_type.borrow() : Ref<Type>);
-    set!(set_type    -> node_type : Indirect<Type>);
+    get!(get_type    -> node_type : TypeId);
+    set!(set_type    -> node_type : TypeId);
 
     get!(get_source -> source.clone() : Source);
 }
@@ -162,11 +213,11 @@ impl_recur!{ Variable [] }
 /*                             Primitive Operators                            */
 /* -------------------------------------------------------------------------- */
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct PrimitiveOperator
 {
     operator:  primitive::Operator,
-    node_type: Indirect<Type>,
+    node_type: TypeId,
     source:    Source,
 }
 impl PrimitiveOperator
@@ -175,7 +226,7 @@ impl PrimitiveOperator
     {
         return Self {
             operator,
-            node_type: basic_types::indirect::unknown(),
+            node_type: basic_types::interned::unknown(),
             source,
         };
     }
@@ -183,9 +234,8 @@ impl PrimitiveOperator
     get!(get_value     -> operator : primitive::Operator);
     get!(get_value_mut -> operator : &mut primitive::Operator);
 
-    get!(get_type    -> node_type.clone() : Indirect<Type>);
-    get!(borrow_type -> node_type.borrow() : Ref<Type>);
-    set!(set_type    -> node_type : Indirect<Type>);
+    get!(get_type    -> node_type : TypeId);
+    set!(set_type    -> node_type : TypeId);
 
     get!(get_source -> source.clone() : Source);
 }
@@ -205,9 +255,15 @@ simple_fmt_display! {
 simple_fmt_display! {
     Integer : "[int {}]", value
 }
+simple_fmt_display! {
+    Float : "[float {:?}]", value
+}
 simple_fmt_display! {
     Boolean : "[bool {}]", value
 }
+simple_fmt_display! {
+    Text : "[string {:?}]", value
+}
 simple_fmt_display! {
     PrimitiveOperator : "[op {}]", operator
 }
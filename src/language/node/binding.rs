@@ -1,11 +1,11 @@
 use super::*;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Binding
 {
     name:      String,
     binding:   OtherNode,
-    node_type: Indirect<Type>,
+    node_type: TypeId,
     source:    Source,
 }
 impl Binding
@@ -15,7 +15,7 @@ impl Binding
         return Self {
             name,
             binding: OtherNode::new(binding),
-            node_type: basic_types::indirect::void(),
+            node_type: basic_types::interned::void(),
             source,
         };
     }
@@ -23,8 +23,7 @@ impl Binding
     get!(get_name     -> name : &String);
     get!(get_name_mut -> name : &mut String);
 
-    get!(get_type    -> node_type.clone() : Indirect<Type>);
-    get!(borrow_type -> node_type.borrow() : Ref<Type>);
+    get!(get_type    -> node_type : TypeId);
 
     get!(get_source -> source.clone() : Source);
 
@@ -47,7 +46,7 @@ impl std::fmt::Display for Binding
             f,
             "(let [{} {}] = {})",
             self.get_name(),
-            self.get_binding().borrow_type(),
+            self.get_binding().get_type(),
             self.get_binding()
         )
     }
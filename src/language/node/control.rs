@@ -11,12 +11,12 @@ pub enum SequenceMode
     Transparent,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Sequence
 {
     mode:      SequenceMode,
     nodes:     OtherNodes,
-    node_type: Indirect<Type>,
+    node_type: TypeId,
     source:    Source,
 }
 impl Sequence
@@ -26,7 +26,7 @@ impl Sequence
         return Self {
             mode,
             nodes,
-            node_type: basic_types::indirect::unknown(),
+            node_type: basic_types::interned::unknown(),
             source,
         };
     }
@@ -37,9 +37,8 @@ impl Sequence
     get!(get_nodes     -> nodes : &Vec<Node>);
     get!(get_nodes_mut -> nodes : &mut Vec<Node>);
 
-    get!(get_type    -> node_type.clone() : Indirect<Type>);
-    get!(borrow_type -> node_type.borrow() : Ref<Type>);
-    set!(set_type    -> node_type : Indirect<Type>);
+    get!(get_type    -> node_type : TypeId);
+    set!(set_type    -> node_type : TypeId);
 
     pub fn is_transparent(&self) -> bool
     {
@@ -104,14 +103,14 @@ impl Recur<Node> for Sequence
 /*                                Conditionals                                */
 /* -------------------------------------------------------------------------- */
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Conditional
 {
     condition: OtherNode,
     then_node: OtherNode,
     else_node: OtherNode,
 
-    node_type: Indirect<Type>,
+    node_type: TypeId,
     source:    Source,
 }
 impl Conditional
@@ -122,7 +121,7 @@ impl Conditional
             condition: OtherNode::new(condition),
             then_node: OtherNode::new(then_node),
             else_node: OtherNode::new(else_node),
-            node_type: basic_types::indirect::unknown(),
+            node_type: basic_types::interned::unknown(),
             source,
         };
     }
@@ -141,15 +140,129 @@ impl Conditional
         get_else,      get_else_mut      -> else_node,
     }
 
-    get!(get_type    -> node_type.clone() : Indirect<Type>);
-    get!(borrow_type -> node_type.borrow() : Ref<Type>);
-    set!(set_type    -> node_type : Indirect<Type>);
+    get!(get_type    -> node_type : TypeId);
+    set!(set_type    -> node_type : TypeId);
 
     get!(get_source -> source.clone() : Source);
 }
 
 impl_recur! { Conditional [condition, then_node, else_node] }
 
+/* -------------------------------------------------------------------------- */
+/*                                    Loops                                   */
+/* -------------------------------------------------------------------------- */
+
+///
+/// A loop that runs its body as long as its condition is true
+///
+/// - `for x in I {...}` loops are parsed as a while loop over an iterator
+/// - The prelude is run before the condition on every iteration, so a condition that needs
+///     multiple statements can be checked in C (see FlattenOperands)
+///
+#[derive(Clone, Debug)]
+pub struct WhileLoop
+{
+    prelude:   OtherNode,
+    condition: OtherNode,
+    body:      OtherNode,
+
+    node_type: TypeId,
+    source:    Source,
+}
+impl WhileLoop
+{
+    pub fn new(condition: Node, body: Node, source: Source) -> Self
+    {
+        return Self {
+            prelude: OtherNode::new(Node::nothing(source.clone())),
+            condition: OtherNode::new(condition),
+            body: OtherNode::new(body),
+            node_type: basic_types::interned::void(),
+            source,
+        };
+    }
+
+    pub fn has_prelude(&self) -> bool
+    {
+        match self.get_prelude()
+        {
+            Node::Nothing(_) => false,
+            _ => true,
+        }
+    }
+    get_children! {
+        get_prelude,   get_prelude_mut   -> prelude,
+        get_condition, get_condition_mut -> condition,
+        get_body,      get_body_mut      -> body,
+    }
+
+    get!(get_type    -> node_type : TypeId);
+
+    get!(get_source -> source.clone() : Source);
+}
+
+impl_recur! { WhileLoop [prelude, condition, body] }
+
+/* -------------------------------------------------------------------------- */
+/*                                    Jumps                                   */
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum JumpMode
+{
+    Return,
+    Break,
+    Continue,
+}
+
+///
+/// An early exit from the enclosing function or loop
+///
+/// - Jumps never produce a value, so they can be used in place of any other expression
+/// - Only returns have a value, which is nothing for functions that return void
+///
+#[derive(Clone, Debug)]
+pub struct Jump
+{
+    mode:  JumpMode,
+    value: OtherNode,
+
+    node_type: TypeId,
+    source:    Source,
+}
+impl Jump
+{
+    pub fn new(mode: JumpMode, value: Node, source: Source) -> Self
+    {
+        return Self {
+            mode,
+            value: OtherNode::new(value),
+            node_type: basic_types::interned::never(),
+            source,
+        };
+    }
+
+    get!(get_mode -> mode : JumpMode);
+
+    pub fn has_value(&self) -> bool
+    {
+        match self.get_value()
+        {
+            Node::Nothing(_) => false,
+            _ => true,
+        }
+    }
+    get_children! {
+        get_value, get_value_mut -> value,
+    }
+
+    get!(get_type    -> node_type : TypeId);
+
+    get!(get_source -> source.clone() : Source);
+}
+
+impl_recur! { Jump [value] }
+
 /* -------------------------------------------------------------------------- */
 /*                                   Display                                  */
 /* -------------------------------------------------------------------------- */
@@ -189,3 +302,43 @@ simple_fmt_display! {
         get_then(),
         get_else(),
 }
+impl std::fmt::Display for JumpMode
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            JumpMode::Return => write!(f, "return"),
+            JumpMode::Break => write!(f, "break"),
+            JumpMode::Continue => write!(f, "continue"),
+        }
+    }
+}
+impl std::fmt::Display for Jump
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self.has_value()
+        {
+            true => write!(f, "({} {})", self.mode, self.get_value()),
+            false => write!(f, "({})", self.mode),
+        }
+    }
+}
+impl std::fmt::Display for WhileLoop
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self.has_prelude()
+        {
+            true => write!(
+                f,
+                "(while {} {} {})",
+                self.get_prelude(),
+                self.get_condition(),
+                self.get_body()
+            ),
+            false => write!(f, "(while {} {})", self.get_condition(), self.get_body()),
+        }
+    }
+}
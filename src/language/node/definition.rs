@@ -1,19 +1,20 @@
 use super::*;
 use crate::language::types::Type;
+use crate::language::symbols::{self, keywords};
 use crate::language::{MemberScope, Visibility};
 
 /* -------------------------------------------------------------------------- */
 /*                             Function Definition                            */
 /* -------------------------------------------------------------------------- */
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Function
 {
     name:        String,
     arguments:   Vec<Argument>,
-    return_type: Indirect<Type>,
+    return_type: TypeId,
     body:        OtherNode,
-    node_type:   Indirect<Type>,
+    node_type:   TypeId,
     source:      Source,
 }
 impl Function
@@ -26,20 +27,30 @@ impl Function
         source: Source,
     ) -> Self
     {
-        let return_indirect = Indirect::new(return_type);
+        Self::from(name, arguments, return_type.intern(), body, source)
+    }
+
+    pub fn from(
+        name: String,
+        arguments: Vec<Argument>,
+        return_type: TypeId,
+        body: Node,
+        source: Source,
+    ) -> Self
+    {
         let argument_types = arguments
             .iter()
             .map(|argument| argument.get_type())
             .collect();
 
-        let function_type = FunctionType::from(argument_types, return_indirect.clone());
+        let function_type = FunctionType::from(argument_types, return_type);
 
         return Self {
             name,
             arguments,
-            return_type: return_indirect,
+            return_type,
             body: OtherNode::new(body),
-            node_type: Indirect::new(function_type.to_type()),
+            node_type: function_type.to_type().intern(),
             source,
         };
     }
@@ -47,94 +58,750 @@ impl Function
     get!(get_name     -> name : &String);
     get!(get_name_mut -> name : &mut String);
 
-    get!(get_return_type -> return_type.clone() : Indirect<Type>);
+    get!(get_return_type -> return_type : TypeId);
     get!(get_arguments     -> arguments : &Vec<Argument>);
     get!(get_arguments_mut -> arguments : &mut Vec<Argument>);
 
-    get!(get_type    -> node_type.clone() : Indirect<Type>);
-    get!(borrow_type -> node_type.borrow() : Ref<Type>);
-    set!(set_type    -> node_type : Indirect<Type>);
+    get!(get_type    -> node_type : TypeId);
+    set!(set_type    -> node_type : TypeId);
 
     get!(get_source -> source.clone() : Source);
 
     get_children! {
         get_body, get_body_mut -> body
     }
+
+    ///
+    /// Add a `self` argument to the front of the argument list (for instance methods)
+    ///
+    pub fn add_self_argument(&mut self, self_type: Type)
+    {
+        let self_argument = Argument::new(String::from(keywords::SELF), self_type);
+        self.arguments.insert(0, self_argument);
+
+        let argument_types = self
+            .arguments
+            .iter()
+            .map(|argument| argument.get_type())
+            .collect();
+
+        let function_type = FunctionType::from(argument_types, self.return_type);
+        self.node_type = function_type.to_type().intern();
+    }
+
+    ///
+    /// Replace the types of all arguments (ie. when instantiating a generic function)
+    ///
+    pub fn set_argument_types(&mut self, argument_types: Vec<TypeId>)
+    {
+        for (argument, argument_type) in self.arguments.iter_mut().zip(argument_types.iter())
+        {
+            argument.argument_type = *argument_type;
+        }
+
+        let function_type = FunctionType::from(argument_types, self.return_type);
+        self.node_type = function_type.to_type().intern();
+    }
+
+    ///
+    /// Replace the type of the `self` argument added by add_self_argument
+    ///
+    pub fn set_self_type(&mut self, self_type: Type)
+    {
+        self.arguments[0] = Argument::new(String::from(keywords::SELF), self_type);
+
+        let argument_types = self
+            .arguments
+            .iter()
+            .map(|argument| argument.get_type())
+            .collect();
+
+        let function_type = FunctionType::from(argument_types, self.return_type);
+        self.node_type = function_type.to_type().intern();
+    }
+
+    ///
+    /// Give all arguments and the return type new types made from their current ones (ie. when
+    ///     instantiating a parameterized type)
+    ///
+    /// - The function type is rebuilt from the new types
+    ///
+    pub fn substitute_types<F>(&mut self, substitute: &mut F)
+    where
+        F: FnMut(TypeId) -> TypeId,
+    {
+        self.return_type = substitute(self.return_type);
+
+        let argument_types = self
+            .arguments
+            .iter()
+            .map(|argument| substitute(argument.argument_type))
+            .collect();
+
+        self.set_argument_types(argument_types);
+    }
+
+    ///
+    /// Replace the types of all arguments, the return type and the function type itself
+    ///
+    pub fn map_types<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(TypeId) -> TypeId,
+    {
+        self.return_type = f(self.return_type);
+        for argument in self.arguments.iter_mut()
+        {
+            argument.argument_type = f(argument.argument_type);
+        }
+        self.node_type = f(self.node_type);
+    }
 }
 
 impl_recur! { Function [body] }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Argument
 {
     name:          String,
-    argument_type: Indirect<Type>,
+    argument_type: TypeId,
 }
 impl Argument
 {
     get!(get_name -> name : &String);
-    get!(get_type -> argument_type.clone() : Indirect<Type>);
+    get!(get_type -> argument_type : TypeId);
 
     pub fn new(name: String, argument_type: Type) -> Self
+    {
+        Self::from(name, argument_type.intern())
+    }
+
+    pub fn from(name: String, argument_type: TypeId) -> Self
     {
         return Self {
             name,
-            argument_type: Indirect::new(argument_type),
+            argument_type,
+        };
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Closure                                  */
+/* -------------------------------------------------------------------------- */
+
+///
+/// An anonymous function expression, which can use bindings from the scope it is created in
+///     (see ConvertClosures)
+///
+#[derive(Clone, Debug)]
+pub struct Closure
+{
+    arguments:   Vec<Argument>,
+    return_type: TypeId,
+    body:        OtherNode,
+    node_type:   TypeId,
+    source:      Source,
+}
+impl Closure
+{
+    pub fn new(arguments: Vec<Argument>, return_type: Type, body: Node, source: Source) -> Self
+    {
+        let return_type = return_type.intern();
+        let argument_types = arguments
+            .iter()
+            .map(|argument| argument.get_type())
+            .collect();
+
+        let function_type = FunctionType::from(argument_types, return_type);
+
+        return Self {
+            arguments,
+            return_type,
+            body: OtherNode::new(body),
+            node_type: function_type.to_type().intern(),
+            source,
         };
     }
+
+    get!(get_return_type -> return_type : TypeId);
+    get!(get_arguments   -> arguments : &Vec<Argument>);
+
+    get!(get_type    -> node_type : TypeId);
+    set!(set_type    -> node_type : TypeId);
+
+    get!(get_source -> source.clone() : Source);
+
+    get_children! {
+        get_body, get_body_mut -> body
+    }
+
+    ///
+    /// Replace the types of all arguments, the return type and the function type itself
+    ///
+    pub fn map_types<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(TypeId) -> TypeId,
+    {
+        self.return_type = f(self.return_type);
+        for argument in self.arguments.iter_mut()
+        {
+            argument.argument_type = f(argument.argument_type);
+        }
+        self.node_type = f(self.node_type);
+    }
 }
 
+impl_recur! { Closure [body] }
+
 /* -------------------------------------------------------------------------- */
 /*                                    Type                                    */
 /* -------------------------------------------------------------------------- */
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Class
 {
-    name:      String,
-    node_type: Indirect<Type>,
-    source:    Source,
+    name:       String,
+    parameters: Vec<Parameter>,
+    members:    Vec<Member>,
+    methods:    Vec<Method>,
+    traits:     Vec<String>,
+    node_type:  TypeId,
+    source:     Source,
 }
 impl Class
 {
+    pub fn new(
+        name: String,
+        parameters: Vec<Parameter>,
+        members: Vec<Member>,
+        mut methods: Vec<Method>,
+        traits: Vec<String>,
+        source: Source,
+    ) -> Self
+    {
+        // Instance methods take the instance they are called on as their first argument
+        //  note: the type of self is filled in once the mutating methods are known
+
+        for method in methods.iter_mut()
+        {
+            if method.get_scope() == MemberScope::Instance
+            {
+                if let Node::Function(function) = method.function.as_mut()
+                {
+                    function.add_self_argument(Type::Unknown);
+                }
+            }
+        }
+
+        let mut class = Self {
+            name,
+            parameters,
+            members,
+            methods,
+            traits,
+            node_type: basic_types::interned::unknown(),
+            source,
+        };
+        class.build_types();
+
+        return class;
+    }
+
+    ///
+    /// Import the instance data members and instance methods of a trait type that aren't already
+    ///     defined by this type
+    ///
+    /// - Static members stay with the trait type (ie. `Trait.member`)
+    /// - Methods implementing the trait take on the visibility of the method they replace
+    /// - Traits of the trait type are also traits of this type (except for marking it as a trait
+    ///     or enumerated type)
+    ///
+    pub fn inherit(&mut self, trait_class: &Class)
+    {
+        for member in trait_class.members.iter()
+        {
+            if member.scope != MemberScope::Instance
+            {
+                continue;
+            }
+
+            let is_defined = self.members.iter().any(|existing| {
+                existing.name == member.name && existing.scope == member.scope
+            });
+
+            if !is_defined
+            {
+                self.members.push(member.clone());
+            }
+        }
+
+        for method in trait_class.methods.iter()
+        {
+            if method.scope != MemberScope::Instance
+            {
+                continue;
+            }
+
+            let method_name = method.get_name();
+            match self
+                .methods
+                .iter_mut()
+                .find(|existing| existing.get_name() == method_name)
+            {
+                Some(existing) =>
+                {
+                    if existing.implements.as_ref() == Some(&trait_class.name)
+                    {
+                        existing.visibility = method.visibility;
+                    }
+                }
+                None => self.methods.push(method.clone()),
+            }
+        }
+
+        for trait_name in trait_class.traits.iter()
+        {
+            let is_inherited = trait_name != symbols::traits::TRAIT
+                && trait_name != symbols::traits::ENUMERATED;
+
+            if is_inherited && !self.traits.contains(trait_name)
+            {
+                self.traits.push(trait_name.clone());
+            }
+        }
+
+        self.build_types();
+    }
+
+    ///
+    /// Check if this type has type parameters (ie. `type Numbered [T is Type] {...}`)
+    ///
+    pub fn is_parameterized(&self) -> bool
+    {
+        !self.parameters.is_empty()
+    }
+
+    ///
+    /// Copy a parameterized type as a concrete type with the given name, making new types for all
+    ///     data members and method signatures from the original ones
+    ///
+    /// - The substitution is expected to replace type parameters with the type arguments
+    ///
+    pub fn instantiate<F>(&self, name: String, substitute: &mut F) -> Class
+    where
+        F: FnMut(TypeId) -> TypeId,
+    {
+        let mut class = self.clone();
+        class.name = name;
+        class.parameters = Vec::new();
+
+        for member in class.members.iter_mut()
+        {
+            member.member_type = substitute(member.member_type);
+        }
+        for method in class.methods.iter_mut()
+        {
+            if let Node::Function(function) = method.function.as_mut()
+            {
+                function.substitute_types(substitute);
+            }
+        }
+
+        // note: self arguments are given the new type name here
+        class.build_types();
+
+        return class;
+    }
+
+    ///
+    /// Build the types of self arguments and the class type from this type's members and methods
+    ///
+    fn build_types(&mut self)
+    {
+        // Instance methods take the instance they are called on as their first argument
+        //  - PassByValue types take a copy of the instance
+        //  - Other types take a reference, which is mutable if the method assigns to a member of
+        //      self or calls another method that does
+
+        let is_pass_by_value = self
+            .traits
+            .iter()
+            .any(|name| name == symbols::traits::VALUE);
+
+        let mut mutating_methods = Vec::new();
+        loop
+        {
+            let mut changed = false;
+            for method in self.methods.iter()
+            {
+                if let Node::Function(function) = method.function.as_ref()
+                {
+                    if method.get_scope() == MemberScope::Instance
+                        && !mutating_methods.contains(function.get_name())
+                        && mutates_self(function.get_body(), &mutating_methods)
+                    {
+                        mutating_methods.push(function.get_name().clone());
+                        changed = true;
+                    }
+                }
+            }
+
+            if !changed
+            {
+                break;
+            }
+        }
+
+        for method in self.methods.iter_mut()
+        {
+            if method.get_scope() == MemberScope::Instance
+            {
+                if let Node::Function(function) = method.function.as_mut()
+                {
+                    let instance_type = InstanceType::new(self.name.clone()).to_type();
+
+                    let self_type = match is_pass_by_value
+                    {
+                        true => instance_type,
+                        false => match mutating_methods.contains(function.get_name())
+                        {
+                            true => instance_type.into_reference(ReferenceMode::Mutable),
+                            false => instance_type.into_reference(ReferenceMode::Immutable),
+                        },
+                    };
+
+                    function.set_self_type(self_type);
+                }
+            }
+        }
+
+        let member_types = self
+            .members
+            .iter()
+            .map(|member| {
+                MemberType::new(
+                    member.name.clone(),
+                    member.member_type,
+                    member.scope,
+                    member.read_visibility,
+                    member.write_visibility,
+                )
+            })
+            .collect();
+
+        let method_types = self
+            .methods
+            .iter()
+            .filter_map(|method| match method.function.as_ref()
+            {
+                Node::Function(function) => Some(MethodType::new(
+                    function.get_name().clone(),
+                    function.get_type(),
+                    method.scope,
+                    method.visibility,
+                )),
+                _ => None,
+            })
+            .collect();
+
+        let class_type = ClassType::new(self.name.clone(), member_types, method_types);
+        class_type.set_traits(TraitSet::new(
+            self.traits.iter().cloned().map(Trait::new).collect(),
+        ));
+
+        self.node_type = class_type.to_type().intern();
+    }
+
+    ///
+    /// Replace the types of all data members and type parameters, in both this node and its class
+    ///     type
+    ///
+    /// - Method signatures belong to the method functions, which are children of this node
+    ///
+    pub fn map_types<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(TypeId) -> TypeId,
+    {
+        for parameter in self.parameters.iter_mut()
+        {
+            parameter.bound = f(parameter.bound);
+        }
+        for member in self.members.iter_mut()
+        {
+            member.member_type = f(member.member_type);
+        }
+
+        if let Type::Class(class_type) = self.node_type.get()
+        {
+            class_type.map_types(f);
+        }
+    }
+
+    ///
+    /// Get the type of instances of this type, linked to its class type
+    ///
+    pub fn get_instance_type(&self) -> TypeId
+    {
+        match self.node_type.get()
+        {
+            Type::Class(class_type) => InstanceType::from_class(class_type).to_type().intern(),
+            _ => basic_types::interned::unknown(),
+        }
+    }
+
     get!(get_name     -> name : &String);
     get!(get_name_mut -> name : &mut String);
 
-    get!(get_type    -> node_type.clone() : Indirect<Type>);
-    get!(borrow_type -> node_type.borrow() : Ref<Type>);
-    set!(set_type    -> node_type : Indirect<Type>);
+    get!(get_parameters  -> parameters : &Vec<Parameter>);
+    get!(get_members     -> members : &Vec<Member>);
+    get!(get_members_mut -> members : &mut Vec<Member>);
+    get!(get_methods     -> methods : &Vec<Method>);
+    get!(get_traits      -> traits : &Vec<String>);
+
+    get!(get_type    -> node_type : TypeId);
+    set!(set_type    -> node_type : TypeId);
 
     get!(get_source -> source.clone() : Source);
 }
 
-impl_recur! { Class [] }
+impl Recur<Node> for Class
+{
+    fn get_children(&self) -> Vec<&Node>
+    {
+        self.methods
+            .iter()
+            .map(|method| method.function.as_ref())
+            .collect()
+    }
+    fn get_children_mut(&mut self) -> Vec<&mut Node>
+    {
+        self.methods
+            .iter_mut()
+            .map(|method| method.function.as_mut())
+            .collect()
+    }
+}
+
+///
+/// Check if a method body assigns to a data member of self, or calls one of the given methods on self
+///
+fn mutates_self(node: &Node, mutating_methods: &Vec<String>) -> bool
+{
+    fn is_self_access(node: &Node) -> Option<&String>
+    {
+        match node
+        {
+            Node::Access(access) => match access.get_target()
+            {
+                Node::Variable(variable) if variable.get_name() == keywords::SELF =>
+                {
+                    Some(access.get_property())
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    let is_mutation = match node
+    {
+        Node::Assign(assign) => is_self_access(assign.get_lhs()).is_some(),
+        Node::Call(call) => match is_self_access(call.get_operator())
+        {
+            Some(name) => mutating_methods.contains(name),
+            None => false,
+        },
+        // Nested definitions have their own self
+        Node::Function(_) | Node::Class(_) => return false,
+        _ => false,
+    };
+
+    is_mutation
+        || node
+            .get_children()
+            .into_iter()
+            .any(|child| mutates_self(child, mutating_methods))
+}
+
+///
+/// A type parameter of a parameterized type, with the traits any type argument must have
+///
+#[derive(Clone, Debug)]
+pub struct Parameter
+{
+    name:  String,
+    bound: TypeId,
+}
+impl Parameter
+{
+    pub fn new(name: String, bound: Type) -> Self
+    {
+        return Self {
+            name,
+            bound: bound.intern(),
+        };
+    }
+
+    get!(get_name  -> name : &String);
+    get!(get_bound -> bound : TypeId);
+}
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Member
 {
     name:             String,
+    member_type:      TypeId,
     scope:            MemberScope,
     read_visibility:  Visibility,
     write_visibility: Visibility,
     source:           Source,
 }
-impl Member {}
+impl Member
+{
+    pub fn new(
+        name: String,
+        member_type: Type,
+        scope: MemberScope,
+        read_visibility: Visibility,
+        write_visibility: Visibility,
+        source: Source,
+    ) -> Self
+    {
+        Self::from(
+            name,
+            member_type.intern(),
+            scope,
+            read_visibility,
+            write_visibility,
+            source,
+        )
+    }
+
+    pub fn from(
+        name: String,
+        member_type: TypeId,
+        scope: MemberScope,
+        read_visibility: Visibility,
+        write_visibility: Visibility,
+        source: Source,
+    ) -> Self
+    {
+        return Self {
+            name,
+            member_type,
+            scope,
+            read_visibility,
+            write_visibility,
+            source,
+        };
+    }
+
+    get!(get_name     -> name : &String);
+    get!(get_name_mut -> name : &mut String);
+    get!(get_type     -> member_type : TypeId);
+    get!(get_scope    -> scope : MemberScope);
+
+    get!(get_read_visibility  -> read_visibility : Visibility);
+    get!(get_write_visibility -> write_visibility : Visibility);
+
+    get!(get_source -> source.clone() : Source);
+}
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Method
 {
-    function:   Function,
+    function:   OtherNode,
     scope:      MemberScope,
     visibility: Visibility,
+    implements: Option<String>,
     source:     Source,
 }
-impl Method {}
+impl Method
+{
+    pub fn new(
+        function: Function,
+        scope: MemberScope,
+        visibility: Visibility,
+        implements: Option<String>,
+    ) -> Self
+    {
+        let source = function.get_source();
 
-/*
+        return Self {
+            function: OtherNode::new(function.to_node()),
+            scope,
+            visibility,
+            implements,
+            source,
+        };
+    }
 
-    TODO: Finish implementing Class node, etc.
+    get!(get_scope      -> scope : MemberScope);
+    get!(get_visibility -> visibility : Visibility);
+    get!(get_implements -> implements : &Option<String>);
 
-*/
+    get!(get_source -> source.clone() : Source);
+
+    ///
+    /// Get the name of the method's function
+    ///
+    pub fn get_name(&self) -> &String
+    {
+        match self.function.as_ref()
+        {
+            Node::Function(function) => function.get_name(),
+            node => unreachable!("Expected method function, found: {}", node),
+        }
+    }
+
+    get_children! {
+        get_function, get_function_mut -> function
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                 Type Alias                                 */
+/* -------------------------------------------------------------------------- */
+
+///
+/// Another name for a type (ie. `type NumberedSum3 = (Numbered Sum3)`)
+///
+#[derive(Clone, Debug)]
+pub struct Alias
+{
+    name:         String,
+    aliased_type: TypeId,
+    node_type:    TypeId,
+    source:       Source,
+}
+impl Alias
+{
+    pub fn new(name: String, aliased_type: Type, source: Source) -> Self
+    {
+        return Self {
+            name,
+            aliased_type: aliased_type.intern(),
+            node_type: basic_types::interned::void(),
+            source,
+        };
+    }
+
+    get!(get_name         -> name : &String);
+    get!(get_aliased_type -> aliased_type : TypeId);
+
+    get!(get_type    -> node_type : TypeId);
+    set!(set_type    -> node_type : TypeId);
+
+    get!(get_source -> source.clone() : Source);
+
+    pub fn map_types<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(TypeId) -> TypeId,
+    {
+        self.aliased_type = f(self.aliased_type);
+    }
+}
+
+impl_recur! { Alias [] }
 
 /* -------------------------------------------------------------------------- */
 /*                                   Display                                  */
@@ -168,10 +835,90 @@ impl std::fmt::Display for Function
     }
 }
 
+impl std::fmt::Display for Closure
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        let _ = write!(f, "(fn <");
+        for (i, argument) in self.arguments.iter().enumerate()
+        {
+            match i
+            {
+                0 =>
+                {
+                    let _ = write!(f, "{}", argument);
+                }
+                _ =>
+                {
+                    let _ = write!(f, " {}", argument);
+                }
+            }
+        }
+        write!(f, "> -> {} {})", self.return_type, self.get_body())
+    }
+}
+
+impl std::fmt::Display for Member
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self.scope
+        {
+            MemberScope::Instance => write!(f, "[self.{} {}]", self.name, self.member_type),
+            MemberScope::Static => write!(f, "[{} {}]", self.name, self.member_type),
+        }
+    }
+}
+
 impl std::fmt::Display for Class
 {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
     {
-        write!(f, "[Class]")
+        let _ = write!(f, "(type {} ", self.name);
+        for parameter in self.parameters.iter()
+        {
+            let _ = write!(f, "{} ", parameter);
+        }
+        let _ = write!(f, "<");
+        for (i, member) in self.members.iter().enumerate()
+        {
+            match i
+            {
+                0 =>
+                {
+                    let _ = write!(f, "{}", member);
+                }
+                _ =>
+                {
+                    let _ = write!(f, " {}", member);
+                }
+            }
+        }
+        let _ = write!(f, ">");
+        for method in self.methods.iter()
+        {
+            let _ = write!(f, " {}", method.get_function());
+        }
+        write!(f, ")")
+    }
+}
+
+impl std::fmt::Display for Parameter
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self.bound.get()
+        {
+            Type::Unknown => write!(f, "[{}]", self.name),
+            bound => write!(f, "[{} {}]", self.name, bound),
+        }
+    }
+}
+
+impl std::fmt::Display for Alias
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        write!(f, "(type {} = {})", self.name, self.aliased_type)
     }
 }
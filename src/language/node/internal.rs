@@ -15,30 +15,35 @@ pub enum CNodeType
     Block,
     BlockTransparent,
     Conditional,
+    Loop,
     Call,
+    MakeClosure,
+    ApplyClosure,
+    ArrayLiteral,
+    MakeArray,
+    ArrayAt,
+    ArrayLength,
+    Range,
+    MakeIterator,
+    IteratorNext,
     Function,
     Struct,
     Comment,
     Nothing,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct CNode
 {
     cnode_type: CNodeType,
     text:       String,
 
-    node_type: Indirect<Type>,
+    node_type: TypeId,
     source:    Source,
 }
 impl CNode
 {
-    pub fn new(
-        cnode_type: CNodeType,
-        text: String,
-        node_type: Indirect<Type>,
-        source: Source,
-    ) -> Self
+    pub fn new(cnode_type: CNodeType, text: String, node_type: TypeId, source: Source) -> Self
     {
         Self {
             cnode_type,
@@ -51,8 +56,7 @@ impl CNode
     get!(get_node_type -> cnode_type : CNodeType);
     get!(get_text -> text : &String);
 
-    get!(get_type -> node_type.clone() : Indirect<Type>);
-    get!(borrow_type -> node_type.borrow() : Ref<Type>);
+    get!(get_type -> node_type : TypeId);
 
     get!(get_source -> source.clone() : Source);
 }
@@ -21,7 +21,6 @@ pub use super::types::*;
 pub use super::ReferenceMode;
 
 use crate::source::Source;
-use crate::utilities::Indirect;
 use crate::utilities::Recur;
 
 /* -------------------------------------------------------------------------- */
@@ -31,7 +30,7 @@ use crate::utilities::Recur;
 // Build the Node enum type and associated boilerplate
 macro_rules! nodes {
     { $( $name:ident : $data:path, )* } => {
-        #[derive(Debug)]
+        #[derive(Clone, Debug)]
         pub enum Node
         {
             // Generate Node::* variants
@@ -81,7 +80,7 @@ macro_rules! nodes {
 
             // Pass get_type to data
 
-            pub fn get_type(&self) -> Indirect<Type>
+            pub fn get_type(&self) -> TypeId
             {
                 match self
                 {
@@ -91,18 +90,6 @@ macro_rules! nodes {
                 }
             }
 
-            // Pass borrow_type to data
-
-            pub fn borrow_type(&self) -> Ref<Type>
-            {
-                match self
-                {
-                    $(
-                        Node::$name(data) => data.borrow_type(),
-                    )*
-                }
-            }
-
             // Debugging helpers
 
             pub fn get_source(&self) -> Source
@@ -168,7 +155,9 @@ nodes! {
     Comment : atomic::Comment,
 
     Integer           : atomic::Integer,
+    Float             : atomic::Float,
     Boolean           : atomic::Boolean,
+    Text              : atomic::Text,
     Variable          : atomic::Variable,
     PrimitiveOperator : atomic::PrimitiveOperator,
 
@@ -182,20 +171,30 @@ nodes! {
 
     Sequence    : control::Sequence,
     Conditional : control::Conditional,
+    WhileLoop   : control::WhileLoop,
+    Jump        : control::Jump,
 
     Function : definition::Function,
+    Closure  : definition::Closure,
     Class    : definition::Class,
+    Alias    : definition::Alias,
 
     CNode : internal::CNode,
 }
 
+// Types can be shared between threads, so keep trees Send too (ie. share source text with Arc, not Rc)
+const _: fn() = || {
+    fn is_send<T: Send>() {}
+    is_send::<Node>();
+};
+
 impl Node
 {
     pub fn nothing(source: Source) -> Node
     {
         Node::Nothing(atomic::Nothing::new(source))
     }
-    pub fn nothing_typed(node_type: Indirect<Type>, source: Source) -> Node
+    pub fn nothing_typed(node_type: TypeId, source: Source) -> Node
     {
         Node::Nothing(atomic::Nothing::new_typed(node_type, source))
     }
@@ -207,7 +206,7 @@ impl Node
     {
         match self
         {
-            Node::Function(_) => true,
+            Node::Function(_) | Node::Class(_) => true,
             _ => false,
         }
     }
@@ -219,7 +218,11 @@ impl Node
     {
         match self
         {
-            Node::Sequence(_) | Node::Conditional(_) | Node::Function(_) | Node::Class(_) => true,
+            Node::Sequence(_)
+            | Node::Conditional(_)
+            | Node::WhileLoop(_)
+            | Node::Function(_)
+            | Node::Class(_) => true,
             _ => false,
         }
     }
@@ -259,16 +262,57 @@ impl Node
     /// 
     pub fn is_type(&self, t: &Type) -> bool
     {
-        let t_indirect = self.get_type();
-        let t_ref = t_indirect.borrow();
+        &self.get_type().get() == t
+    }
 
-        &*t_ref == t
+    ///
+    /// Replace every type held by this node with a new type made from it, including the types in
+    ///     the signature of a definition (but not its body)
+    ///
+    /// - Types that are fixed by the kind of node (ie. the void type of an assignment) are left alone
+    ///
+    pub fn map_types<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(TypeId) -> TypeId,
+    {
+        match self
+        {
+            Node::Function(function) => function.map_types(f),
+            Node::Closure(closure) => closure.map_types(f),
+            Node::Class(class) => class.map_types(f),
+            Node::Alias(alias) => alias.map_types(f),
+
+            Node::Nothing(nothing) => nothing.set_type(f(nothing.get_type())),
+            Node::Variable(variable) => variable.set_type(f(variable.get_type())),
+            Node::PrimitiveOperator(operator) => operator.set_type(f(operator.get_type())),
+            Node::Call(call) => call.set_type(f(call.get_type())),
+            Node::Reference(reference) => reference.set_type(f(reference.get_type())),
+            Node::Dereference(dereference) => dereference.set_type(f(dereference.get_type())),
+            Node::Access(access) => access.set_type(f(access.get_type())),
+            Node::Sequence(sequence) => sequence.set_type(f(sequence.get_type())),
+            Node::Conditional(conditional) => conditional.set_type(f(conditional.get_type())),
+
+            _ => (),
+        }
+    }
+
+    ///
+    /// Replace every type held by this node and all the nodes inside it
+    ///
+    pub fn map_all_types<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(TypeId) -> TypeId,
+    {
+        self.map_types(f);
+        for child in self.get_children_mut()
+        {
+            child.map_all_types(f);
+        }
     }
 }
 
 pub type OtherNode = Box<Node>;
 pub type OtherNodes = Vec<Node>;
-use crate::utilities::Ref;
 
 /* -------------------------------------------------------------------------- */
 /*                                   Traits                                   */
@@ -5,12 +5,12 @@ use crate::language::ReferenceMode;
 /*                                    Call                                    */
 /* -------------------------------------------------------------------------- */
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Call
 {
     operator:  OtherNode,
     operands:  OtherNodes,
-    node_type: Indirect<Type>,
+    node_type: TypeId,
     source:    Source,
 }
 impl Call
@@ -20,7 +20,7 @@ impl Call
         return Self {
             operator: OtherNode::new(operator),
             operands,
-            node_type: basic_types::indirect::unknown(),
+            node_type: basic_types::interned::unknown(),
             source,
         };
     }
@@ -31,9 +31,8 @@ impl Call
     get!(get_operands     -> operands : &Vec<Node>);
     get!(get_operands_mut -> operands : &mut Vec<Node>);
 
-    get!(get_type    -> node_type.clone() : Indirect<Type>);
-    get!(borrow_type -> node_type.borrow() : Ref<Type>);
-    set!(set_type    -> node_type : Indirect<Type>);
+    get!(get_type    -> node_type : TypeId);
+    set!(set_type    -> node_type : TypeId);
 
     pub fn get_all_mut(&mut self) -> (&mut Node, &mut Vec<Node>)
     {
@@ -65,12 +64,12 @@ impl Recur<Node> for Call
 /*                                  Reference                                 */
 /* -------------------------------------------------------------------------- */
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Reference
 {
     mode:      ReferenceMode,
     target:    OtherNode,
-    node_type: Indirect<Type>,
+    node_type: TypeId,
     source:    Source,
 }
 impl Reference
@@ -80,16 +79,15 @@ impl Reference
         return Self {
             mode,
             target: OtherNode::new(target),
-            node_type: basic_types::indirect::unknown(),
+            node_type: basic_types::interned::unknown(),
             source,
         };
     }
 
     get!(get_mode -> mode : ReferenceMode);
 
-    get!(get_type    -> node_type.clone() : Indirect<Type>);
-    get!(borrow_type -> node_type.borrow() : Ref<Type>);
-    set!(set_type    -> node_type : Indirect<Type>);
+    get!(get_type    -> node_type : TypeId);
+    set!(set_type    -> node_type : TypeId);
 
     get!(get_source -> source.clone() : Source);
 
@@ -104,11 +102,11 @@ impl_recur!{ Reference [target] }
 /*                                 Dereference                                */
 /* -------------------------------------------------------------------------- */
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Dereference
 {
     target:    OtherNode,
-    node_type: Indirect<Type>,
+    node_type: TypeId,
     source:    Source,
 }
 impl Dereference
@@ -117,14 +115,13 @@ impl Dereference
     {
         return Self {
             target: OtherNode::new(target),
-            node_type: basic_types::indirect::unknown(),
+            node_type: basic_types::interned::unknown(),
             source,
         };
     }
 
-    get!(get_type    -> node_type.clone() : Indirect<Type>);
-    get!(borrow_type -> node_type.borrow() : Ref<Type>);
-    set!(set_type    -> node_type : Indirect<Type>);
+    get!(get_type    -> node_type : TypeId);
+    set!(set_type    -> node_type : TypeId);
 
     get!(get_source -> source.clone() : Source);
 
@@ -139,12 +136,12 @@ impl_recur!{ Dereference [target] }
 /*                                   Assign                                   */
 /* -------------------------------------------------------------------------- */
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Assign
 {
     lhs:       OtherNode,
     rhs:       OtherNode,
-    node_type: Indirect<Type>,
+    node_type: TypeId,
     source:    Source,
 }
 impl Assign
@@ -154,13 +151,12 @@ impl Assign
         return Self {
             lhs: OtherNode::new(lhs),
             rhs: OtherNode::new(rhs),
-            node_type: basic_types::indirect::void(),
+            node_type: basic_types::interned::void(),
             source,
         };
     }
 
-    get!(get_type    -> node_type.clone() : Indirect<Type>);
-    get!(borrow_type -> node_type.borrow() : Ref<Type>);
+    get!(get_type    -> node_type : TypeId);
 
     get!(get_source -> source.clone() : Source);
 
@@ -176,12 +172,12 @@ impl_recur!{ Assign [lhs, rhs] }
 /*                                   Access                                   */
 /* -------------------------------------------------------------------------- */
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Access
 {
     target:        OtherNode,
     property_name: String,
-    node_type:     Indirect<Type>,
+    node_type:     TypeId,
     source:        Source,
 }
 impl Access
@@ -191,7 +187,7 @@ impl Access
         return Self {
             target: OtherNode::new(target),
             property_name,
-            node_type: basic_types::indirect::unknown(),
+            node_type: basic_types::interned::unknown(),
             source,
         };
     }
@@ -199,9 +195,8 @@ impl Access
     get!(get_property     -> property_name : &String);
     get!(get_property_mut -> property_name : &mut String);
 
-    get!(get_type    -> node_type.clone() : Indirect<Type>);
-    get!(borrow_type -> node_type.borrow() : Ref<Type>);
-    set!(set_type    -> node_type : Indirect<Type>);
+    get!(get_type    -> node_type : TypeId);
+    set!(set_type    -> node_type : TypeId);
 
     get!(get_source -> source.clone() : Source);
 
@@ -245,6 +240,6 @@ simple_fmt_display! {
 }
 simple_fmt_display! {
     Access : "({} . {})",
-        get_property(),
         get_target(),
+        get_property(),
 }
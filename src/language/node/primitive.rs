@@ -31,7 +31,28 @@ pub enum Operator
     HeapAllocate,
     HeapFree,
 
+    // Closure operators
+    MakeClosure,
+    ApplyClosure,
+
+    // Conversion operators
+    ToInteger,
+    ToFloat,
+
+    // Iterator operators
+    Range,
+    MakeIterator,
+    IteratorHasNext,
+    IteratorNext,
+
+    // Array operators
+    ArrayLiteral,
+    MakeArray,
+    ArrayAt,
+    ArrayLength,
+
     // Other operators
+    Print,
     Return,
 }
 
@@ -71,7 +92,28 @@ impl std::fmt::Display for Operator
             Operator::HeapAllocate => "heap-alloc",
             Operator::HeapFree => "heap-free",
 
+            // Closure operators
+            Operator::MakeClosure => "make-closure",
+            Operator::ApplyClosure => "apply-closure",
+
+            // Conversion operators
+            Operator::ToInteger => "to-int",
+            Operator::ToFloat => "to-float",
+
+            // Iterator operators
+            Operator::Range => "range",
+            Operator::MakeIterator => "iterator",
+            Operator::IteratorHasNext => "iterator-has-next",
+            Operator::IteratorNext => "iterator-next",
+
+            // Array operators
+            Operator::ArrayLiteral => "[...]",
+            Operator::MakeArray => "make-array",
+            Operator::ArrayAt => "at",
+            Operator::ArrayLength => "length",
+
             // Other operators
+            Operator::Print => "print",
             Operator::Return => "return",
         };
         write!(f, "{}", s)
@@ -1,6 +1,25 @@
 pub mod names
 {
     pub const ALLOCATE_FUNCTION : &str = "_specs__Allocate";
+    pub const CLOSURE_TYPE : &str = "_specs__Closure";
+    pub const STRING_TYPE : &str = "_specs__String";
+    pub const PRINT_FUNCTION : &str = "_specs__Print";
+
+    // note: Ranges are the numeric iterator from runtime_src, and arrays are iterated over with a
+    //  range of their indices
+    pub const RANGE_TYPE : &str = "_Specs_NumericIterator";
+    pub const RANGE_FUNCTION : &str = "_Specs_NumericIterator__New";
+    pub const RANGE_HAS_NEXT_FUNCTION : &str = "_Specs_NumericIterator__HasNext";
+    pub const RANGE_NEXT_FUNCTION : &str = "_Specs_NumericIterator__Next";
+
+    // note: Array types and functions are emitted for each element type
+    //  ie. `_specs__Array__int`, `_specs__Array__int__At`
+    pub const ARRAY_TYPE : &str = "_specs__Array";
+    pub const ARRAY_NEW_FUNCTION : &str = "New";
+    pub const ARRAY_FROM_FUNCTION : &str = "From";
+    pub const ARRAY_AT_FUNCTION : &str = "At";
+    pub const CHECK_BOUNDS_FUNCTION : &str = "_specs__CheckBounds";
+    pub const CHECK_LENGTH_FUNCTION : &str = "_specs__CheckLength";
 
     pub const SIZE_OPERATOR : &str = "sizeof";
     pub const MAIN_FUNCTION : &str = "_specs__UserMain";
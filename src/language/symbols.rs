@@ -110,6 +110,14 @@ pub mod operators
         "ref" => REFERENCE,
         "mut" => MUTABLE_REFERENCE,
         "create" => CREATE,
+        "heap-alloc" => HEAP_ALLOCATE,
+        "to-int" => TO_INTEGER,
+        "to-float" => TO_FLOAT,
+        "range" => RANGE,
+        "make-array" => MAKE_ARRAY,
+        "at" => ARRAY_AT,
+        "length" => ARRAY_LENGTH,
+        "print" => PRINT,
     ];
 
     pub const ACCESS_CHAR: char = '.';
@@ -129,7 +137,7 @@ pub mod operators
         match s.as_str()
         {
             // NOT | MINUS | REFERENCE | MUTABLE_REFERENCE | DEREFERENCE | CREATE => true,
-            REFERENCE | MUTABLE_REFERENCE | DEREFERENCE | CREATE => true,
+            REFERENCE | MUTABLE_REFERENCE | DEREFERENCE | CREATE | HEAP_ALLOCATE => true,
             _ => false,
         }
     }
@@ -157,6 +165,14 @@ pub mod keywords
         "then" => THEN,
         "else" => ELSE,
         "when" => WHEN,
+        "while" => WHILE,
+        "for" => FOR,
+        "in" => IN,
+        "return" => RETURN,
+        "break" => BREAK,
+        "continue" => CONTINUE,
+        "array" => ARRAY,
+        "mut-ref" => MUTABLE_REFERENCE_TYPE,
 
         "=>" => ASSOCIATE,
         ":" => ASSOCIATE_TYPE,
@@ -230,6 +246,8 @@ pub mod traits
 {
     symbols![
         "PassByValue" => VALUE,
+        "Trait" => TRAIT,
+        "Enumerated" => ENUMERATED,
         "#Copyable" => COPY,
         "#Numeric" => NUMERIC,
     ];
@@ -0,0 +1,52 @@
+use super::*;
+
+/* -------------------------------------------------------------------------- */
+/*                                 Application                                */
+/* -------------------------------------------------------------------------- */
+
+///
+/// A parameterized type applied to some type arguments (ie. `(Numbered Sum3)`)
+///
+/// - Applications are replaced by instances of a concrete type for each unique set of arguments
+///     (see InstantiateTypes)
+///
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ApplicationType
+{
+    name:      String,
+    arguments: Vec<TypeId>,
+}
+impl ApplicationType
+{
+    pub fn new(name: String, arguments: Vec<Type>) -> Self
+    {
+        return Self {
+            name,
+            arguments: arguments.into_iter().map(TypeId::intern).collect(),
+        };
+    }
+    pub fn from(name: String, arguments: Vec<TypeId>) -> Self
+    {
+        return Self { name, arguments };
+    }
+
+    get!(get_name      -> name : &String);
+    get!(get_arguments -> arguments : &Vec<TypeId>);
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Display                                  */
+/* -------------------------------------------------------------------------- */
+
+impl std::fmt::Display for ApplicationType
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        let _ = write!(f, "({}", self.name);
+        for argument in self.arguments.iter()
+        {
+            let _ = write!(f, " {}", argument);
+        }
+        write!(f, ")")
+    }
+}
@@ -0,0 +1,176 @@
+use super::*;
+
+use super::context::{self, ContextId};
+
+use std::collections::HashMap;
+
+/* -------------------------------------------------------------------------- */
+/*                                   Type Id                                  */
+/* -------------------------------------------------------------------------- */
+
+///
+/// A handle to a type stored in the type arena of some type context
+///
+/// - Every distinct type is stored once per context, so two ids from the same context are the same
+///     type exactly when they are equal
+/// - Primitive types have the same ids in every context, and type variables are kept in their id
+///     rather than stored in the arena
+/// - Ids are plain numbers, so they can be copied freely and shared between threads
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct TypeId
+{
+    context: ContextId,
+    index:   u32,
+}
+impl TypeId
+{
+    ///
+    /// Get the id of a type, adding it to the arena of the current type context if it hasn't been
+    ///     seen before (see TypeContext::enter)
+    ///
+    pub fn intern(t: Type) -> TypeId
+    {
+        if let Some(index) = PRIMITIVES.iter().position(|primitive| *primitive == t)
+        {
+            return primitive_id(index as u32);
+        }
+
+        if let Type::Variable(variable) = t
+        {
+            return TypeId {
+                context: variable.get_context(),
+                index:   VARIABLE_FLAG | variable.get_id() as u32,
+            };
+        }
+
+        context::with_current(|context| {
+            if let Some(id) = context.types.read().unwrap().ids.get(&t)
+            {
+                return *id;
+            }
+
+            context.types.write().unwrap().insert(t, context.id)
+        })
+    }
+
+    ///
+    /// Get a copy of the type this id refers to
+    ///
+    /// - Types refer to the types inside them by id, so copies are cheap
+    ///
+    pub fn get(self) -> Type
+    {
+        if self.context == context::PRIMITIVE_CONTEXT
+        {
+            return PRIMITIVES[self.index as usize].clone();
+        }
+
+        if self.index & VARIABLE_FLAG != 0
+        {
+            let id = (self.index & !VARIABLE_FLAG) as usize;
+            return TypeVariable::from(self.context, id).to_type();
+        }
+
+        context::with_context(self.context, |context| {
+            context.types.read().unwrap().types[self.index as usize].clone()
+        })
+    }
+}
+
+impl Type
+{
+    pub fn intern(self) -> TypeId
+    {
+        TypeId::intern(self)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                    Arena                                   */
+/* -------------------------------------------------------------------------- */
+
+///
+/// Storage for the types made in one type context
+///
+/// - Primitive types and type variables aren't stored, since their ids already say what they are
+///
+pub(super) struct TypeArena
+{
+    types: Vec<Type>,
+    ids:   HashMap<Type, TypeId>,
+}
+impl TypeArena
+{
+    pub(super) fn new() -> Self
+    {
+        return Self {
+            types: Vec::new(),
+            ids:   HashMap::new(),
+        };
+    }
+
+    pub(super) fn len(&self) -> usize
+    {
+        self.types.len()
+    }
+
+    pub(super) fn truncate(&mut self, count: usize)
+    {
+        self.types.truncate(count);
+        self.ids.retain(|_, id| (id.index as usize) < count);
+    }
+
+    fn insert(&mut self, t: Type, context: ContextId) -> TypeId
+    {
+        // note: Another thread may have added the type since it was looked up
+        if let Some(id) = self.ids.get(&t)
+        {
+            return *id;
+        }
+
+        let id = TypeId {
+            context,
+            index: self.types.len() as u32,
+        };
+        self.types.push(t.clone());
+        self.ids.insert(t, id);
+
+        id
+    }
+}
+
+// The primitive types, in the order of their ids
+const PRIMITIVES: [Type; 8] = [
+    Type::Unknown,
+    Type::Void,
+    Type::Never,
+    Type::Integer,
+    Type::Boolean,
+    Type::Float,
+    Type::String,
+    Type::Range,
+];
+
+// Set in the index of ids for type variables, whose index holds the variable's id instead
+const VARIABLE_FLAG: u32 = 1 << 31;
+
+pub(super) const fn primitive_id(index: u32) -> TypeId
+{
+    TypeId {
+        context: context::PRIMITIVE_CONTEXT,
+        index,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Display                                  */
+/* -------------------------------------------------------------------------- */
+
+impl std::fmt::Display for TypeId
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        write!(f, "{}", self.get())
+    }
+}
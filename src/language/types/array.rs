@@ -0,0 +1,60 @@
+use super::*;
+
+///
+/// A contiguous run of elements of the same type
+///
+/// - `(array T n)` arrays have a length known at compile time (ie. from a literal `[a b c]`)
+/// - `(array T)` arrays only know their length at runtime, and any array of T can be used as one
+///
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ArrayType
+{
+    element: TypeId,
+    length:  Option<usize>,
+}
+impl ArrayType
+{
+    pub fn new(element: Type, length: Option<usize>) -> Self
+    {
+        return Self {
+            element: element.intern(),
+            length,
+        };
+    }
+    pub fn from(element: TypeId, length: Option<usize>) -> Self
+    {
+        return Self { element, length };
+    }
+
+    get!(get_element -> element : TypeId);
+    get!(get_length  -> length : Option<usize>);
+
+    pub fn is_dynamic(&self) -> bool
+    {
+        self.length.is_none()
+    }
+
+    ///
+    /// Check if an array of this type can be used where an array of some other type is expected
+    ///
+    /// - Elements must be the same type
+    /// - Arrays of any length can be used where a dynamic array is expected
+    ///
+    pub fn satisfies(&self, expected: &ArrayType) -> bool
+    {
+        let lengths_match = expected.is_dynamic() || self.length == expected.length;
+        lengths_match && self.element == expected.element
+    }
+}
+
+impl std::fmt::Display for ArrayType
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self.length
+        {
+            Some(length) => write!(f, "(array {} {})", self.element, length),
+            None => write!(f, "(array {})", self.element),
+        }
+    }
+}
@@ -1,85 +1,417 @@
+use super::context;
 use super::*;
 
-use std::cell::Ref;
+use crate::language::{symbols, MemberScope, Visibility};
+use std::hash::{Hash, Hasher};
 use traits::TraitSet;
 
 /* -------------------------------------------------------------------------- */
 /*                                  Instance                                  */
 /* -------------------------------------------------------------------------- */
 
-#[derive(Debug)]
+///
+/// The type of an instance of a class (ie. `p` in `let p = (new Point)`)
+///
+/// - Instances are named after their class when parsed, and linked to it once the name is known
+///
+#[derive(Clone, Debug)]
 pub struct InstanceType
 {
-    name:       String,
-    traits:     Indirect<TraitSet>,
-    class_type: OtherType,
+    name:  String,
+    class: Option<ClassType>,
 }
 impl InstanceType
 {
     pub fn new(name: String) -> Self
+    {
+        return Self { name, class: None };
+    }
+
+    ///
+    /// Make an instance type already linked to its class
+    ///
+    pub fn from_class(class: ClassType) -> Self
     {
         return Self {
-            name,
-            traits: traits::common::indirect::empty(),
-            class_type: basic_types::indirect::unknown(),
+            name:  class.get_name(),
+            class: Some(class),
         };
     }
 
-    get!(get_name   -> name : &String);
-    get!(get_traits -> traits.clone() : Indirect<TraitSet>);
+    ///
+    /// Get the name of this instance's class, which follows the class if it's renamed
+    ///
+    pub fn get_name(&self) -> String
+    {
+        match self.class
+        {
+            Some(class) => class.get_name(),
+            None => self.name.clone(),
+        }
+    }
 
-    pub fn set_traits(&mut self, new_traits: Indirect<TraitSet>)
+    pub fn get_traits(&self) -> TraitSet
     {
-        self.traits = new_traits;
+        match self.class
+        {
+            Some(class) => class.get_traits(),
+            None => traits::common::empty(),
+        }
     }
 
-    pub fn get_class(&self) -> Option<Ref<ClassType>>
+    ///
+    /// Check if this instance type has been linked to the class type it was named after
+    ///
+    pub fn is_resolved(&self) -> bool
     {
-        let class_type_ref = self.class_type.borrow();
-        match &*class_type_ref
+        self.class.is_some()
+    }
+
+    get!(get_class -> class : Option<ClassType>);
+
+    ///
+    /// Get the type of the class this instance was linked to, or unknown if it hasn't been linked
+    ///
+    pub fn get_class_type(&self) -> TypeId
+    {
+        match self.class
         {
-            Type::Class(_) =>
-            {
-                let class_ref = Ref::map(class_type_ref, |t| match t
-                {
-                    Type::Class(class) => class,
-                    _ =>
-                    {
-                        unreachable!();
-                    }
-                });
-
-                Some(class_ref)
-            }
-            _ => None,
+            Some(class) => class.to_type().intern(),
+            None => basic_types::interned::unknown(),
         }
     }
+
+    ///
+    /// Check if this is an instance of a variant of some enumerated type (ie. a type that is
+    ///     `is Expression` where Expression is `is Enumerated`)
+    ///
+    pub fn is_variant_of(&self, enumerated: &InstanceType) -> bool
+    {
+        let is_enumerated = enumerated
+            .get_traits()
+            .has(&String::from(symbols::traits::ENUMERATED));
+
+        is_enumerated && self.get_traits().has(&enumerated.get_name())
+    }
 }
 
+///
+/// Instances are the same type if they are linked to the same class, no matter what name they
+///     refer to it by
+///
+/// - Instances that haven't been linked yet can only be compared by name
+///
 impl PartialEq for InstanceType
 {
     fn eq(&self, other: &Self) -> bool
     {
-        false
+        match (self.class, other.class)
+        {
+            (Some(class_self), Some(class_other)) => class_self == class_other,
+            (None, None) => self.name == other.name,
+            _ => false,
+        }
     }
 }
+impl Eq for InstanceType {}
 
+impl Hash for InstanceType
+{
+    fn hash<H: Hasher>(&self, state: &mut H)
+    {
+        match self.class
+        {
+            Some(class) => class.hash(state),
+            None => self.name.hash(state),
+        }
+    }
+}
 
 /* -------------------------------------------------------------------------- */
 /*                                    Class                                   */
 /* -------------------------------------------------------------------------- */
 
-#[derive(Debug)]
-pub struct ClassType {}
+///
+/// The type of a type definition (ie. `Point` in `(new Point)`)
+///
+/// - Classes are nominal, so two classes are only the same type if they come from the same
+///     definition, even if they have the same name and members
+/// - Class types are handles to an entry in the class table, so renaming a class or filling in
+///     its members is seen through every instance of it
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ClassType
+{
+    id: ClassId,
+}
+impl ClassType
+{
+    pub fn new(name: String, members: Vec<MemberType>, methods: Vec<MethodType>) -> Self
+    {
+        let data = ClassData {
+            name,
+            members,
+            methods,
+            traits: traits::common::empty(),
+            variant_of: None,
+            revision: 0,
+        };
+
+        let id = context::with_current(|context| {
+            let mut classes = context.classes.write().unwrap();
+            let id = ClassId {
+                context: context.id,
+                id:      classes.len(),
+            };
+            classes.push(data);
+            id
+        });
+
+        return Self { id };
+    }
+
+    get!(get_id -> id : ClassId);
+
+    pub fn get_name(&self) -> String
+    {
+        self.read(|class| class.name.clone())
+    }
+    pub fn get_members(&self) -> Vec<MemberType>
+    {
+        self.read(|class| class.members.clone())
+    }
+    pub fn get_methods(&self) -> Vec<MethodType>
+    {
+        self.read(|class| class.methods.clone())
+    }
+    pub fn get_traits(&self) -> TraitSet
+    {
+        self.read(|class| class.traits.clone())
+    }
+    pub fn get_variant_of(&self) -> Option<TypeId>
+    {
+        self.read(|class| class.variant_of)
+    }
+
+    pub fn set_name(&self, name: String)
+    {
+        self.write(|class| class.name = name);
+    }
+    pub fn set_traits(&self, traits: TraitSet)
+    {
+        self.write(|class| class.traits = traits);
+    }
+
+    ///
+    /// Mark this as a variant of an enumerated type, once its data has been moved into the
+    ///     enumerated type (see ExplicateEnumerations)
+    ///
+    pub fn set_variant_of(&self, enumerated: TypeId)
+    {
+        self.write(|class| class.variant_of = Some(enumerated));
+    }
+
+    ///
+    /// Find a data member by name and scope
+    ///
+    pub fn get_member(&self, name: &String, scope: MemberScope) -> Option<MemberType>
+    {
+        self.read(|class| {
+            class
+                .members
+                .iter()
+                .find(|member| member.get_name() == name && member.get_scope() == scope)
+                .cloned()
+        })
+    }
+
+    ///
+    /// Find a method by name (static and instance methods share a namespace)
+    ///
+    pub fn get_method(&self, name: &String) -> Option<MethodType>
+    {
+        self.read(|class| {
+            class
+                .methods
+                .iter()
+                .find(|method| method.get_name() == name)
+                .cloned()
+        })
+    }
+
+    ///
+    /// Replace the types of all data members and methods with new types made from them
+    ///
+    pub fn map_types<F>(&self, f: &mut F)
+    where
+        F: FnMut(TypeId) -> TypeId,
+    {
+        // note: The table isn't locked while mapping, so the mapping can look up other classes.
+        //       If the types were replaced by someone else in the meantime, they are mapped again
+        //       so that neither change is lost
+        loop
+        {
+            let (mut members, mut methods, revision) = self.read(|class| {
+                (class.members.clone(), class.methods.clone(), class.revision)
+            });
+
+            for member in members.iter_mut()
+            {
+                member.member_type = f(member.member_type);
+            }
+            for method in methods.iter_mut()
+            {
+                method.function_type = f(method.function_type);
+            }
 
-impl PartialEq for ClassType
+            let is_replaced = context::with_context(self.id.context, |context| {
+                let mut classes = context.classes.write().unwrap();
+                let class = &mut classes[self.id.id];
+                if class.revision == revision
+                {
+                    class.members = members;
+                    class.methods = methods;
+                    class.revision += 1;
+                    true
+                }
+                else
+                {
+                    false
+                }
+            });
+
+            if is_replaced
+            {
+                return;
+            }
+        }
+    }
+
+    fn read<T, F>(&self, f: F) -> T
+    where
+        F: FnOnce(&ClassData) -> T,
+    {
+        context::with_context(self.id.context, |context| {
+            f(&context.classes.read().unwrap()[self.id.id])
+        })
+    }
+
+    fn write<F>(&self, f: F)
+    where
+        F: FnOnce(&mut ClassData),
+    {
+        context::with_context(self.id.context, |context| {
+            f(&mut context.classes.write().unwrap()[self.id.id])
+        })
+    }
+}
+
+///
+/// The position of a class in the class table of its type context, which stays the same when the
+///     class is renamed
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ClassId
 {
-    fn eq(&self, other: &Self) -> bool
+    context: context::ContextId,
+    id:      usize,
+}
+impl ClassId
+{
+    get!(get_id -> id : usize);
+}
+
+///
+/// Everything known about a class, shared by all handles to it
+///
+/// - Classes are kept in the class table of the type context they were made in (see TypeContext)
+///
+pub(super) struct ClassData
+{
+    name:    String,
+    members: Vec<MemberType>,
+    methods: Vec<MethodType>,
+    traits:  TraitSet,
+
+    variant_of: Option<TypeId>,
+
+    // How many times the member and method types have been replaced (see map_types)
+    revision: usize,
+}
+
+///
+/// Type information for a data member in a class
+///
+#[derive(Clone, Debug)]
+pub struct MemberType
+{
+    name:             String,
+    member_type:      TypeId,
+    scope:            MemberScope,
+    read_visibility:  Visibility,
+    write_visibility: Visibility,
+}
+impl MemberType
+{
+    pub fn new(
+        name: String,
+        member_type: TypeId,
+        scope: MemberScope,
+        read_visibility: Visibility,
+        write_visibility: Visibility,
+    ) -> Self
     {
-        false
+        return Self {
+            name,
+            member_type,
+            scope,
+            read_visibility,
+            write_visibility,
+        };
     }
+
+    get!(get_name             -> name : &String);
+    get!(get_type             -> member_type : TypeId);
+    get!(get_scope            -> scope : MemberScope);
+    get!(get_read_visibility  -> read_visibility : Visibility);
+    get!(get_write_visibility -> write_visibility : Visibility);
+}
+
+///
+/// Type information for a method in a class
+///
+/// - Instance methods include their `self` argument in their function type
+///
+#[derive(Clone, Debug)]
+pub struct MethodType
+{
+    name:          String,
+    function_type: TypeId,
+    scope:         MemberScope,
+    visibility:    Visibility,
 }
+impl MethodType
+{
+    pub fn new(
+        name: String,
+        function_type: TypeId,
+        scope: MemberScope,
+        visibility: Visibility,
+    ) -> Self
+    {
+        return Self {
+            name,
+            function_type,
+            scope,
+            visibility,
+        };
+    }
 
+    get!(get_name       -> name : &String);
+    get!(get_type       -> function_type : TypeId);
+    get!(get_scope      -> scope : MemberScope);
+    get!(get_visibility -> visibility : Visibility);
+}
 
 /* -------------------------------------------------------------------------- */
 /*                                   Display                                  */
@@ -89,13 +421,13 @@ impl std::fmt::Display for InstanceType
 {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
     {
-        write!(f, "{}", self.name)
+        write!(f, "{}", self.get_name())
     }
 }
 impl std::fmt::Display for ClassType
 {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
     {
-        write!(f, "[ClassType]")
+        write!(f, "(type {})", self.get_name())
     }
 }
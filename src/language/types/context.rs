@@ -0,0 +1,222 @@
+use super::arena::TypeArena;
+use super::class::ClassData;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, RwLock};
+
+/* -------------------------------------------------------------------------- */
+/*                                Type Context                                */
+/* -------------------------------------------------------------------------- */
+
+///
+/// The types and classes made by one compilation
+///
+/// - Types and classes are made in the context entered on the current thread, or a process-wide
+///     default context if none is entered (see enter)
+/// - Type ids and class types remember their context, so they can be read on any thread while the
+///     context is alive
+/// - A context is removed along with everything in it once its last handle is dropped, after which
+///     type ids from it can't be used
+///
+#[derive(Clone)]
+pub struct TypeContext
+{
+    owner: Arc<ContextOwner>,
+}
+impl TypeContext
+{
+    pub fn new() -> Self
+    {
+        let data = Arc::new(ContextData {
+            id:            ContextId(NEXT_CONTEXT.fetch_add(1, Ordering::Relaxed)),
+            types:         RwLock::new(TypeArena::new()),
+            classes:       RwLock::new(Vec::new()),
+            next_variable: AtomicUsize::new(0),
+        });
+
+        CONTEXTS.write().unwrap().insert(data.id, data.clone());
+
+        return Self {
+            owner: Arc::new(ContextOwner { data }),
+        };
+    }
+
+    ///
+    /// Make types and classes in this context on the current thread until the scope is dropped
+    ///
+    /// - Other threads can enter the same context to work on the same compilation
+    ///
+    pub fn enter(&self) -> ContextScope
+    {
+        CURRENT.with(|current| current.borrow_mut().push(self.owner.data.clone()));
+
+        return ContextScope {
+            _context:  self.clone(),
+            _not_send: std::marker::PhantomData,
+        };
+    }
+
+    ///
+    /// Get the number of types and classes in this context, so that everything made after now can
+    ///     be removed again (see restore)
+    ///
+    pub fn checkpoint(&self) -> Checkpoint
+    {
+        let data = &self.owner.data;
+        return Checkpoint {
+            context:     data.id,
+            type_count:  data.types.read().unwrap().len(),
+            class_count: data.classes.read().unwrap().len(),
+        };
+    }
+
+    ///
+    /// Remove every type and class made in this context since a checkpoint
+    ///
+    /// - Restoring a checkpoint invalidates the ids made after it, so nothing made since the
+    ///     checkpoint (ie. nodes, errors) can be used afterwards
+    /// - Checkpoints should only be restored when nothing else is using this context
+    ///
+    pub fn restore(&self, checkpoint: Checkpoint)
+    {
+        let data = &self.owner.data;
+        assert!(
+            checkpoint.context == data.id,
+            "Restored a checkpoint from another type context"
+        );
+
+        data.types.write().unwrap().truncate(checkpoint.type_count);
+        data.classes.write().unwrap().truncate(checkpoint.class_count);
+    }
+}
+
+///
+/// Keeps a type context entered on the current thread (see TypeContext::enter)
+///
+pub struct ContextScope
+{
+    _context:  TypeContext,
+    _not_send: std::marker::PhantomData<*const ()>,
+}
+impl Drop for ContextScope
+{
+    fn drop(&mut self)
+    {
+        CURRENT.with(|current| current.borrow_mut().pop());
+    }
+}
+
+///
+/// The sizes of the type arena and class table of a context at some point
+///
+#[derive(Clone, Copy, Debug)]
+pub struct Checkpoint
+{
+    context:     ContextId,
+    type_count:  usize,
+    class_count: usize,
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                  Internal                                  */
+/* -------------------------------------------------------------------------- */
+
+///
+/// The id of a type context, which is never reused
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ContextId(u32);
+
+// Primitive types don't belong to any context
+pub(super) const PRIMITIVE_CONTEXT: ContextId = ContextId(0);
+
+pub(super) struct ContextData
+{
+    pub(super) id:      ContextId,
+    pub(super) types:   RwLock<TypeArena>,
+    pub(super) classes: RwLock<Vec<ClassData>>,
+
+    next_variable: AtomicUsize,
+}
+impl ContextData
+{
+    pub(super) fn new_variable(&self) -> usize
+    {
+        self.next_variable.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+///
+/// Removes a context once the last handle to it is dropped
+///
+struct ContextOwner
+{
+    data: Arc<ContextData>,
+}
+impl Drop for ContextOwner
+{
+    fn drop(&mut self)
+    {
+        CONTEXTS.write().unwrap().remove(&self.data.id);
+    }
+}
+
+static NEXT_CONTEXT: AtomicU32 = AtomicU32::new(1);
+
+// Every live context, so type ids can be read from any thread
+static CONTEXTS: LazyLock<RwLock<HashMap<ContextId, Arc<ContextData>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+// Used when no context is entered, and never removed
+static DEFAULT: LazyLock<TypeContext> = LazyLock::new(TypeContext::new);
+
+thread_local! {
+    // The contexts entered on this thread, the last of which is current
+    static CURRENT: RefCell<Vec<Arc<ContextData>>> = const { RefCell::new(Vec::new()) };
+}
+
+///
+/// Do something with the context types and classes are made in on this thread
+///
+pub(super) fn with_current<T, F>(f: F) -> T
+where
+    F: FnOnce(&ContextData) -> T,
+{
+    let current = CURRENT.with(|current| current.borrow().last().cloned());
+    match current
+    {
+        Some(data) => f(&data),
+        None => f(&DEFAULT.owner.data),
+    }
+}
+
+///
+/// Do something with the context that some type or class was made in
+///
+pub(super) fn with_context<T, F>(id: ContextId, f: F) -> T
+where
+    F: FnOnce(&ContextData) -> T,
+{
+    // note: Most lookups are for the current context, which doesn't need the shared table
+    let current = CURRENT.with(|current| {
+        current
+            .borrow()
+            .last()
+            .filter(|data| data.id == id)
+            .cloned()
+    });
+
+    let data = match current
+    {
+        Some(data) => data,
+        None => match CONTEXTS.read().unwrap().get(&id)
+        {
+            Some(data) => data.clone(),
+            None => panic!("Used a type from a type context that was dropped"),
+        },
+    };
+
+    f(&data)
+}
@@ -0,0 +1,78 @@
+use super::*;
+
+/* -------------------------------------------------------------------------- */
+/*                                     Tag                                    */
+/* -------------------------------------------------------------------------- */
+
+///
+/// The type of the variant tag of an enumerated type (ie. `__K`), which becomes a C enum
+///
+/// - Each variant of the enumerated type has a named tag value
+///
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TagType
+{
+    name:   String,
+    values: Vec<String>,
+}
+impl TagType
+{
+    pub fn new(name: String, values: Vec<String>) -> Self
+    {
+        return Self { name, values };
+    }
+
+    get!(get_name       -> name : &String);
+    get!(get_name_mut   -> name : &mut String);
+    get!(get_values     -> values : &Vec<String>);
+    get!(get_values_mut -> values : &mut Vec<String>);
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                    Union                                   */
+/* -------------------------------------------------------------------------- */
+
+///
+/// The type of the variant data of an enumerated type (ie. `__V`), which becomes a C union
+///
+/// - Each variant has a field holding an instance of its data type (ie. `Data/Expression/Number`)
+///
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct UnionType
+{
+    fields: Vec<(String, TypeId)>,
+}
+impl UnionType
+{
+    pub fn new(fields: Vec<(String, TypeId)>) -> Self
+    {
+        return Self { fields };
+    }
+
+    get!(get_fields     -> fields : &Vec<(String, TypeId)>);
+    get!(get_fields_mut -> fields : &mut Vec<(String, TypeId)>);
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Display                                  */
+/* -------------------------------------------------------------------------- */
+
+impl std::fmt::Display for TagType
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        write!(f, "{}", self.name)
+    }
+}
+impl std::fmt::Display for UnionType
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        let _ = write!(f, "(union");
+        for (name, field_type) in self.fields.iter()
+        {
+            let _ = write!(f, " [{} {}]", name, field_type);
+        }
+        write!(f, ")")
+    }
+}
@@ -1,44 +1,31 @@
 use super::*;
-use traits::TraitSet;
 
-#[derive(Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct FunctionType
 {
-    arguments:   Vec<OtherType>,
-    return_type: OtherType,
-    traits:      Indirect<TraitSet>,
+    arguments:   Vec<TypeId>,
+    return_type: TypeId,
 }
 impl FunctionType
 {
     pub fn new(arguments: Vec<Type>, return_type: Type) -> Self
     {
         return Self {
-            arguments:   arguments.into_iter().map(OtherType::new).collect(),
-            return_type: OtherType::new(return_type),
-            traits:      traits::common::indirect::empty(),
+            arguments:   arguments.into_iter().map(TypeId::intern).collect(),
+            return_type: return_type.intern(),
         };
     }
 
-    pub fn from(arguments: Vec<OtherType>, return_type: OtherType) -> Self
+    pub fn from(arguments: Vec<TypeId>, return_type: TypeId) -> Self
     {
         return Self {
             arguments,
             return_type,
-            traits: traits::common::indirect::empty(),
         };
     }
 
-    get!(get_arguments   -> arguments : &Vec<OtherType>);
-    get!(get_return_type -> return_type.clone() : OtherType);
-    get!(get_traits      -> traits.clone() : Indirect<TraitSet>);
-}
-
-impl PartialEq for FunctionType
-{
-    fn eq(&self, other: &Self) -> bool
-    {
-        false
-    }
+    get!(get_arguments   -> arguments : &Vec<TypeId>);
+    get!(get_return_type -> return_type : TypeId);
 }
 
 impl std::fmt::Display for FunctionType
@@ -1,37 +1,64 @@
+pub mod application;
+pub mod arena;
+pub mod array;
 pub mod class;
+pub mod context;
+pub mod enumerated;
 pub mod function;
 pub mod primitive;
 pub mod reference;
 pub mod traits;
+pub mod variable;
 
+pub use application::*;
+pub use arena::*;
+pub use array::*;
 pub use class::*;
+pub use context::{Checkpoint, ContextScope, TypeContext};
+pub use enumerated::*;
 pub use function::*;
 pub use primitive::*;
 pub use reference::*;
 pub use traits::*;
+pub use variable::*;
 
 pub use crate::language::ReferenceMode;
 
-use crate::utilities::Indirect;
-
 /* -------------------------------------------------------------------------- */
 /*                                    Type                                    */
 /* -------------------------------------------------------------------------- */
 
-#[derive(Debug)]
+///
+/// The type of a node or definition
+///
+/// - Types refer to the types inside them (ie. the element type of an array) by their id in the
+///     type arena, so types are compared by their structure one level at a time
+///
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum Type
 {
     Unknown,
+    Variable(variable::TypeVariable),
     Void,
+    Never,
 
     Integer,
     Boolean,
     Float,
+    String,
+    Range,
 
     Reference(reference::ReferenceType),
+    Array(array::ArrayType),
     Instance(class::InstanceType),
     Function(function::FunctionType),
     Class(class::ClassType),
+    Bounded(traits::BoundedType),
+
+    Tag(enumerated::TagType),
+    Union(enumerated::UnionType),
+
+    Application(application::ApplicationType),
 }
 impl Type
 {
@@ -60,8 +87,10 @@ impl Type
     {
         match self
         {
-            Type::Integer | Type::Boolean | Type::Float => true,
+            Type::Integer | Type::Boolean | Type::Float | Type::String | Type::Range => true,
+            Type::Array(_) => true,
             Type::Instance(_) => true,
+            Type::Tag(_) | Type::Union(_) => true,
             _ => false,
         }
     }
@@ -71,7 +100,7 @@ impl Type
         {
             t if t.is_value() => true,
 
-            Type::Reference(reference) => match &*reference.get_target().borrow()
+            Type::Reference(reference) => match reference.get_target().get()
             {
                 t if t.is_value() => true,
                 _ => false,
@@ -79,7 +108,7 @@ impl Type
             _ => false,
         }
     }
-    pub fn dereference(&self) -> Option<OtherType>
+    pub fn dereference(&self) -> Option<TypeId>
     {
         match self
         {
@@ -100,32 +129,214 @@ impl Type
             _ => false,
         }
     }
+    pub fn is_never(&self) -> bool
+    {
+        match self
+        {
+            Type::Never => true,
+            _ => false,
+        }
+    }
+    pub fn is_variable(&self) -> bool
+    {
+        match self
+        {
+            Type::Variable(_) => true,
+            _ => false,
+        }
+    }
+
+    ///
+    /// Check if a type is, or refers to, a type variable that hasn't been solved yet
+    ///
+    pub fn has_variables(&self) -> bool
+    {
+        match self
+        {
+            Type::Variable(_) => true,
+            Type::Reference(reference) => reference.get_target().get().has_variables(),
+            Type::Array(array) => array.get_element().get().has_variables(),
+            Type::Function(function) =>
+            {
+                function
+                    .get_arguments()
+                    .iter()
+                    .any(|argument| argument.get().has_variables())
+                    || function.get_return_type().get().has_variables()
+            }
+            _ => false,
+        }
+    }
+
+    ///
+    /// Check if a type is only known by its traits, or is a reference to or function of such a type
+    ///
+    pub fn is_generic(&self) -> bool
+    {
+        match self
+        {
+            Type::Bounded(_) => true,
+            Type::Reference(reference) => reference.get_target().get().is_generic(),
+            Type::Array(array) => array.get_element().get().is_generic(),
+            Type::Function(function) =>
+            {
+                function
+                    .get_arguments()
+                    .iter()
+                    .any(|argument| argument.get().is_generic())
+                    || function.get_return_type().get().is_generic()
+            }
+            _ => false,
+        }
+    }
 
     /* -------------------------------------------------------------------------- */
     /*                                   Traits                                   */
     /* -------------------------------------------------------------------------- */
 
-    pub fn get_traits(&self) -> Indirect<traits::TraitSet>
+    pub fn get_traits(&self) -> traits::TraitSet
     {
         match self
         {
-            Type::Unknown | Type::Void => traits::common::indirect::empty(),
+            Type::Unknown | Type::Variable(_) | Type::Void | Type::Never => traits::common::empty(),
 
-            Type::Integer => traits::common::indirect::integer(),
-            Type::Boolean => traits::common::indirect::boolean(),
-            Type::Float => traits::common::indirect::float(),
+            Type::Integer => traits::common::integer(),
+            Type::Boolean => traits::common::boolean(),
+            Type::Float => traits::common::float(),
+            Type::String => traits::common::string(),
+            Type::Range => traits::common::range(),
 
             Type::Reference(reference) => reference.get_traits(),
+            Type::Array(_) => traits::common::array(),
 
             Type::Instance(instance) => instance.get_traits(),
 
-            Type::Function(function) => function.get_traits(),
-            Type::Class(_) => traits::common::indirect::empty(),
+            Type::Function(_) => traits::common::empty(),
+            Type::Class(class) => class.get_traits(),
+            Type::Bounded(bounded) => bounded.get_traits().clone(),
+
+            Type::Tag(_) | Type::Union(_) => traits::common::empty(),
+
+            Type::Application(_) => traits::common::empty(),
         }
     }
-}
 
-pub type OtherType = Indirect<Type>;
+    ///
+    /// Check if a value of this type can be used where a value of some other type is expected
+    ///
+    /// - Types can always be used where the same type is expected
+    /// - Mutable references can be used where an immutable reference is expected (see
+    ///     satisfies_target)
+    /// - Types can be used where a bounded type is expected if they have all the required traits
+    /// - Bounded types can be used where one of their bounding types is expected
+    /// - Variants of an enumerated type can be used where the enumerated type is expected
+    /// - Arrays can be used where a dynamic array of the same element type is expected
+    /// - Never (the type of jumps like `return`) can be used anywhere, since it never produces a value
+    ///
+    pub fn satisfies(&self, expected: &Type) -> bool
+    {
+        match (self, expected)
+        {
+            (t, expected) if t == expected => true,
+            (Type::Never, _) => true,
+
+            (Type::Reference(reference), Type::Reference(expected_reference)) =>
+            {
+                let is_mode_allowed = match (reference.get_mode(), expected_reference.get_mode())
+                {
+                    (ReferenceMode::Immutable, ReferenceMode::Mutable) => false,
+                    _ => true,
+                };
+                let is_writable = expected_reference.get_mode() == ReferenceMode::Mutable;
+
+                is_mode_allowed
+                    && reference.get_target().get().satisfies_target(
+                        &expected_reference.get_target().get(),
+                        is_writable,
+                    )
+            }
+            (Type::Array(array), Type::Array(expected_array)) => array.satisfies(expected_array),
+            (Type::Instance(instance), Type::Instance(expected_instance)) =>
+            {
+                instance.is_variant_of(expected_instance)
+            }
+            (t, Type::Bounded(bounded)) => bounded.is_satisfied_by(t),
+            (Type::Bounded(bounded), expected) => bounded
+                .get_bounds()
+                .iter()
+                .any(|bound| &bound.get() == expected),
+
+            _ => false,
+        }
+    }
+
+    ///
+    /// Check if a reference to this type can be used where a reference to some other type is
+    ///     expected
+    ///
+    /// - Only the outermost reference mode can be weakened, since a `(mut (mut int))` used as a
+    ///     `(mut (ref int))` could have an immutable reference written through it
+    /// - Targets of mutable references have to be the same type, since any value of the expected
+    ///     type could be written through them (ie. a different variant of an enumerated type)
+    /// - Bounded types stand in for the type they're instantiated with, so they can always be
+    ///     satisfied
+    ///
+    fn satisfies_target(&self, expected: &Type, is_writable: bool) -> bool
+    {
+        match (self, expected)
+        {
+            (t, expected) if t == expected => true,
+            (Type::Reference(_), Type::Reference(_)) => false,
+            (t, Type::Bounded(bounded)) => bounded.is_satisfied_by(t),
+            (t, expected) => !is_writable && t.satisfies(expected),
+        }
+    }
+
+    ///
+    /// Make a copy of this type with each type directly inside it replaced (ie. the arguments and
+    ///     return type of a function type)
+    ///
+    /// - Class types keep their members in the class table, so they are left alone
+    ///
+    pub fn map_inner<F>(&self, f: &mut F) -> Type
+    where
+        F: FnMut(TypeId) -> TypeId,
+    {
+        match self
+        {
+            Type::Reference(reference) =>
+            {
+                ReferenceType::from(reference.get_mode(), f(reference.get_target())).to_type()
+            }
+            Type::Array(array) => ArrayType::from(f(array.get_element()), array.get_length()).to_type(),
+            Type::Function(function) =>
+            {
+                let arguments = function.get_arguments().iter().map(|t| f(*t)).collect();
+                FunctionType::from(arguments, f(function.get_return_type())).to_type()
+            }
+            Type::Bounded(bounded) =>
+            {
+                let bounds = bounded.get_bounds().iter().map(|t| f(*t)).collect();
+                BoundedType::from(bounds, bounded.get_traits().clone()).to_type()
+            }
+            Type::Union(union) =>
+            {
+                let fields = union
+                    .get_fields()
+                    .iter()
+                    .map(|(name, t)| (name.clone(), f(*t)))
+                    .collect();
+                UnionType::new(fields).to_type()
+            }
+            Type::Application(application) =>
+            {
+                let arguments = application.get_arguments().iter().map(|t| f(*t)).collect();
+                ApplicationType::from(application.get_name().clone(), arguments).to_type()
+            }
+            t => t.clone(),
+        }
+    }
+}
 
 /* -------------------------------------------------------------------------- */
 /*                                   Traits                                   */
@@ -142,6 +353,13 @@ impl ToType for reference::ReferenceType
         return Type::Reference(self);
     }
 }
+impl ToType for array::ArrayType
+{
+    fn to_type(self) -> Type
+    {
+        return Type::Array(self);
+    }
+}
 impl ToType for class::InstanceType
 {
     fn to_type(self) -> Type
@@ -163,6 +381,41 @@ impl ToType for class::ClassType
         return Type::Class(self);
     }
 }
+impl ToType for traits::BoundedType
+{
+    fn to_type(self) -> Type
+    {
+        return Type::Bounded(self);
+    }
+}
+impl ToType for enumerated::TagType
+{
+    fn to_type(self) -> Type
+    {
+        return Type::Tag(self);
+    }
+}
+impl ToType for enumerated::UnionType
+{
+    fn to_type(self) -> Type
+    {
+        return Type::Union(self);
+    }
+}
+impl ToType for variable::TypeVariable
+{
+    fn to_type(self) -> Type
+    {
+        return Type::Variable(self);
+    }
+}
+impl ToType for application::ApplicationType
+{
+    fn to_type(self) -> Type
+    {
+        return Type::Application(self);
+    }
+}
 
 /* -------------------------------------------------------------------------- */
 /*                                 Basic Types                                */
@@ -192,67 +445,58 @@ pub mod basic_types
     {
         Type::Float
     }
-    pub mod indirect
+    pub fn string() -> Type
+    {
+        Type::String
+    }
+    pub fn range() -> Type
+    {
+        Type::Range
+    }
+    pub fn never() -> Type
+    {
+        Type::Never
+    }
+
+    ///
+    /// Ids of the primitive types, which are always the first types in the arena
+    ///
+    pub mod interned
     {
+        use super::super::arena::primitive_id;
         use super::*;
-        thread_local! {
-            static UNKNOWN: Indirect<Type> = Indirect::new(Type::Unknown);
-            static VOID: Indirect<Type>    = Indirect::new(Type::Void);
-            static INTEGER: Indirect<Type> = Indirect::new(Type::Integer);
-            static BOOLEAN: Indirect<Type> = Indirect::new(Type::Boolean);
-            static FLOAT: Indirect<Type>   = Indirect::new(Type::Float);
+
+        pub fn unknown() -> TypeId
+        {
+            return primitive_id(0);
         }
-        pub fn unknown() -> Indirect<Type>
+        pub fn void() -> TypeId
         {
-            return UNKNOWN.with(|t| t.clone());
+            return primitive_id(1);
         }
-        pub fn void() -> Indirect<Type>
+        pub fn never() -> TypeId
         {
-            return VOID.with(|t| t.clone());
+            return primitive_id(2);
         }
-        pub fn integer() -> Indirect<Type>
+        pub fn integer() -> TypeId
         {
-            return INTEGER.with(|t| t.clone());
+            return primitive_id(3);
         }
-        pub fn boolean() -> Indirect<Type>
+        pub fn boolean() -> TypeId
         {
-            return BOOLEAN.with(|t| t.clone());
+            return primitive_id(4);
         }
-        pub fn float() -> Indirect<Type>
+        pub fn float() -> TypeId
         {
-            return FLOAT.with(|t| t.clone());
+            return primitive_id(5);
         }
-    }
-}
-
-/* -------------------------------------------------------------------------- */
-/*                                 Comparison                                 */
-/* -------------------------------------------------------------------------- */
-
-impl PartialEq for Type
-{
-    fn eq(&self, other: &Type) -> bool
-    {
-        match (self, other)
+        pub fn string() -> TypeId
         {
-            (Type::Unknown, Type::Unknown) => true,
-            (Type::Void, Type::Void) => true,
-            
-            (Type::Integer, Type::Integer) => true,
-            (Type::Boolean, Type::Boolean) => true,
-            (Type::Float, Type::Float) => true,
-
-            (Type::Reference(ref_self), Type::Reference(ref_other)) => ref_self == ref_other,
-            (Type::Instance(instance_self), Type::Instance(instance_other)) =>
-            {
-                instance_self == instance_other
-            }
-            (Type::Function(function_self), Type::Function(function_other)) =>
-            {
-                function_self == function_other
-            }
-            (Type::Class(class_self), Type::Class(class_other)) => class_self == class_other,
-            _ => false,
+            return primitive_id(6);
+        }
+        pub fn range() -> TypeId
+        {
+            return primitive_id(7);
         }
     }
 }
@@ -268,17 +512,28 @@ impl std::fmt::Display for Type
         match self
         {
             Type::Unknown => write!(f, "unknown"),
+            Type::Variable(variable) => write!(f, "{}", variable),
             Type::Void => write!(f, "void"),
+            Type::Never => write!(f, "never"),
 
             Type::Integer => write!(f, "int"),
             Type::Boolean => write!(f, "bool"),
             Type::Float => write!(f, "float"),
+            Type::String => write!(f, "string"),
+            Type::Range => write!(f, "range"),
 
             Type::Reference(reference) => write!(f, "{}", reference),
+            Type::Array(array) => write!(f, "{}", array),
             Type::Instance(instance) => write!(f, "{}", instance),
 
             Type::Function(function) => write!(f, "{}", function),
             Type::Class(class) => write!(f, "{}", class),
+            Type::Bounded(bounded) => write!(f, "{}", bounded),
+
+            Type::Tag(tag) => write!(f, "{}", tag),
+            Type::Union(union) => write!(f, "{}", union),
+
+            Type::Application(application) => write!(f, "{}", application),
         }
     }
 }
@@ -1,10 +1,10 @@
 use super::*;
 
-#[derive(Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct ReferenceType
 {
     mode:   ReferenceMode,
-    target: OtherType,
+    target: TypeId,
 }
 impl ReferenceType
 {
@@ -12,38 +12,21 @@ impl ReferenceType
     {
         return Self {
             mode,
-            target: OtherType::new(target),
+            target: target.intern(),
         };
     }
-    pub fn from(mode: ReferenceMode, target: OtherType) -> Self
+    pub fn from(mode: ReferenceMode, target: TypeId) -> Self
     {
         return Self { mode, target };
     }
 
     get!(get_mode   -> mode : ReferenceMode);
-    get!(get_target -> target.clone() : OtherType);
+    get!(get_target -> target : TypeId);
 
     // note: Reference types share the same traits as their targets
-    pub fn get_traits(&self) -> Indirect<traits::TraitSet>
+    pub fn get_traits(&self) -> traits::TraitSet
     {
-        self.target.borrow().get_traits()
-    }
-}
-
-impl PartialEq for ReferenceType
-{
-    fn eq(&self, other: &Self) -> bool
-    {
-        match self.mode == other.mode
-        {
-            true => (),
-            false => return false,
-        }
-
-        let self_target = self.target.borrow();
-        let other_target = other.target.borrow();
-
-        &*self_target == &*other_target
+        self.target.get().get_traits()
     }
 }
 
@@ -1,7 +1,9 @@
-use super::Indirect;
-use std::collections::HashMap;
+use super::*;
+use crate::language::symbols;
+use crate::language::MemberScope;
+use std::collections::BTreeSet;
 
-#[derive(Hash, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub struct Trait
 {
     name: String,
@@ -12,31 +14,127 @@ impl Trait
     {
         return Self { name };
     }
+
+    get!(get_name -> name : &String);
 }
 
+// note: Traits are kept in name order, so sets with the same traits are equal and hash the same
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct TraitSet
 {
-    set: HashMap<Trait, ()>,
+    set: BTreeSet<Trait>,
 }
 impl TraitSet
 {
     pub fn empty() -> Self
     {
         return Self {
-            set: HashMap::new(),
+            set: BTreeSet::new(),
         };
     }
     pub fn new(traits: Vec<Trait>) -> Self
     {
         return Self {
-            set: traits.into_iter().map(|t| (t, ())).collect(),
+            set: traits.into_iter().collect(),
         };
     }
 
     pub fn has(&self, name: &String) -> bool
     {
         let t = Trait::new(name.clone());
-        return self.set.contains_key(&t);
+        return self.set.contains(&t);
+    }
+
+    ///
+    /// Check if this set has every trait in some other set
+    ///
+    pub fn has_all(&self, other: &TraitSet) -> bool
+    {
+        other.set.iter().all(|t| self.set.contains(t))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Bounded                                  */
+/* -------------------------------------------------------------------------- */
+
+///
+/// A type that is only known to have some traits (ie. an argument declared `is Summable`)
+///
+/// - Bounds that name a type are linked to its class, so members of the trait type can be
+///     accessed through the bounded type
+///
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BoundedType
+{
+    bounds: Vec<TypeId>,
+    traits: TraitSet,
+}
+impl BoundedType
+{
+    pub fn new(names: Vec<String>) -> Self
+    {
+        let traits = TraitSet::new(names.iter().cloned().map(Trait::new).collect());
+        let bounds = names
+            .into_iter()
+            .map(|name| InstanceType::new(name).to_type().intern())
+            .collect();
+
+        return Self { bounds, traits };
+    }
+    pub fn from(bounds: Vec<TypeId>, traits: TraitSet) -> Self
+    {
+        return Self { bounds, traits };
+    }
+
+    get!(get_bounds -> bounds : &Vec<TypeId>);
+    get!(get_traits -> traits : &TraitSet);
+
+    ///
+    /// Find the first bound whose class has an instance member or method with the given name
+    ///
+    pub fn get_bound_with_member(&self, name: &String) -> Option<TypeId>
+    {
+        self.bounds
+            .iter()
+            .find(|bound| match bound.get()
+            {
+                Type::Instance(instance) => match instance.get_class()
+                {
+                    Some(class) =>
+                    {
+                        class.get_member(name, MemberScope::Instance).is_some()
+                            || class.get_method(name).is_some()
+                    }
+                    None => false,
+                },
+                _ => false,
+            })
+            .cloned()
+    }
+
+    ///
+    /// Check if a type has all the traits required by this bound
+    ///
+    /// - Instances satisfy a bound that names their own type
+    ///
+    pub fn is_satisfied_by(&self, t: &Type) -> bool
+    {
+        let required = &self.traits;
+
+        match t
+        {
+            Type::Instance(instance) =>
+            {
+                let available = instance.get_traits();
+
+                required.set.iter().all(|required_trait| {
+                    *required_trait.get_name() == instance.get_name()
+                        || available.has(required_trait.get_name())
+                })
+            }
+            t => t.get_traits().has_all(required),
+        }
     }
 }
 
@@ -48,32 +146,49 @@ pub mod common
     {
         return TraitSet::empty();
     }
-    pub mod indirect
+    pub fn integer() -> TraitSet
     {
-        use super::*;
-
-        thread_local! {
-            static EMPTY: Indirect<TraitSet> = Indirect::new(TraitSet::empty());
-            static INTEGER: Indirect<TraitSet> = Indirect::new(TraitSet::new(vec![]));
-            static BOOLEAN: Indirect<TraitSet> = Indirect::new(TraitSet::new(vec![]));
-            static FLOAT: Indirect<TraitSet> = Indirect::new(TraitSet::new(vec![]));
-        }
-        pub fn empty() -> Indirect<TraitSet>
-        {
-            return EMPTY.with(|t| t.clone());
-        }
-        pub fn integer() -> Indirect<TraitSet>
-        {
-            return INTEGER.with(|t| t.clone());
-        }
-        pub fn boolean() -> Indirect<TraitSet>
-        {
-            return BOOLEAN.with(|t| t.clone());
-        }
-        pub fn float() -> Indirect<TraitSet>
-        {
-            return FLOAT.with(|t| t.clone());
-        }
+        return TraitSet::new(vec![
+            Trait::new(String::from(symbols::traits::VALUE)),
+            Trait::new(String::from(symbols::traits::COPY)),
+            Trait::new(String::from(symbols::traits::NUMERIC)),
+        ]);
+    }
+    pub fn boolean() -> TraitSet
+    {
+        return TraitSet::new(vec![
+            Trait::new(String::from(symbols::traits::VALUE)),
+            Trait::new(String::from(symbols::traits::COPY)),
+        ]);
+    }
+    pub fn float() -> TraitSet
+    {
+        return TraitSet::new(vec![
+            Trait::new(String::from(symbols::traits::VALUE)),
+            Trait::new(String::from(symbols::traits::COPY)),
+            Trait::new(String::from(symbols::traits::NUMERIC)),
+        ]);
+    }
+    pub fn string() -> TraitSet
+    {
+        return TraitSet::new(vec![
+            Trait::new(String::from(symbols::traits::VALUE)),
+            Trait::new(String::from(symbols::traits::COPY)),
+        ]);
+    }
+    pub fn range() -> TraitSet
+    {
+        return TraitSet::new(vec![
+            Trait::new(String::from(symbols::traits::VALUE)),
+            Trait::new(String::from(symbols::traits::COPY)),
+        ]);
+    }
+    pub fn array() -> TraitSet
+    {
+        return TraitSet::new(vec![
+            Trait::new(String::from(symbols::traits::VALUE)),
+            Trait::new(String::from(symbols::traits::COPY)),
+        ]);
     }
 }
 
@@ -88,6 +203,18 @@ impl std::fmt::Display for Trait
         write!(f, "[trait {}]", self.name)
     }
 }
+impl std::fmt::Display for BoundedType
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        let _ = write!(f, "(is");
+        for bound in self.bounds.iter()
+        {
+            let _ = write!(f, " {}", bound);
+        }
+        write!(f, ")")
+    }
+}
 impl std::fmt::Debug for TraitSet
 {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
@@ -0,0 +1,42 @@
+use super::context::{self, ContextId};
+
+///
+/// A placeholder for a type that hasn't been inferred yet
+///
+/// - Every variable has a unique id in its type context, so all the places it appears can be
+///     filled in once it's solved
+/// - Variables are solved while inferring types (see Unifier), so none are left after Infer
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct TypeVariable
+{
+    context: ContextId,
+    id:      usize,
+}
+impl TypeVariable
+{
+    ///
+    /// Make a new variable in the current type context (see TypeContext::enter)
+    ///
+    pub fn new() -> Self
+    {
+        let (context, id) = context::with_current(|context| (context.id, context.new_variable()));
+        return Self { context, id };
+    }
+
+    pub(super) fn from(context: ContextId, id: usize) -> Self
+    {
+        return Self { context, id };
+    }
+
+    get!(get_context -> context : ContextId);
+    get!(get_id      -> id : usize);
+}
+
+impl std::fmt::Display for TypeVariable
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        write!(f, "?{}", self.id)
+    }
+}
@@ -0,0 +1,314 @@
+#![feature(get_mut_unchecked)]
+#![allow(dead_code)]
+
+#[macro_use]
+mod macros;
+pub(crate) mod utilities;
+
+pub(crate) mod compiler;
+pub(crate) mod driver;
+pub(crate) mod errors;
+pub(crate) mod interpreter;
+pub(crate) mod language;
+pub(crate) mod parser;
+pub(crate) mod repl;
+pub(crate) mod source;
+
+// note: Only the compiler's stages are exposed, everything else is internal
+pub use compiler::Compiler;
+pub use errors::compile_error::Error as CompileError;
+pub use errors::parse_error::Error as ParseError;
+pub use errors::s_expression_error::Error as SExpressionError;
+pub use errors::ResultLog;
+pub use language::node::{CNode, Node};
+pub use language::s_expression::SExpression;
+pub use language::types::{Checkpoint, ContextScope, TypeContext};
+pub use parser::Parser;
+pub use source::Source;
+
+use errors::*;
+
+///
+/// Include for the runtime header, which all generated C programs start with
+///
+pub const C_HEADER: &str = "#include \"specs_runtime.h\"";
+
+/* -------------------------------------------------------------------------- */
+/*                                   Options                                  */
+/* -------------------------------------------------------------------------- */
+
+///
+/// A point in the compiler to stop at and output the result of
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Stage
+{
+    SExpression,
+    Preprocessed,
+    Ast,
+    Typed,
+    Flat,
+    C,
+}
+
+///
+/// Options for `compile`
+///
+/// - By default, programs are compiled all the way to C without printing the result of each pass
+///
+#[derive(Clone, Debug)]
+pub struct CompileOptions
+{
+    pub stage:        Stage,
+    pub print_passes: bool,
+}
+
+impl Default for CompileOptions
+{
+    fn default() -> Self
+    {
+        CompileOptions {
+            stage:        Stage::C,
+            print_passes: false,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Output                                   */
+/* -------------------------------------------------------------------------- */
+
+///
+/// The result of running the compiler up to some stage
+///
+/// - `SExpression` holds the result of the `SExpression` and `Preprocessed` stages
+/// - `Node` holds the result of the `Ast`, `Typed` and `Flat` stages
+///
+pub enum Output
+{
+    SExpression(SExpression),
+    Node(Node),
+    C(CNode),
+}
+
+///
+/// An error or warning from any stage of the compiler
+///
+/// - Each stage's errors are an enum of their own, so embedders can match on the kind of error
+///
+pub enum Diagnostic
+{
+    SExpression(SExpressionError),
+    Parse(ParseError),
+    Compile(CompileError),
+}
+
+///
+/// Everything produced by `compile`
+///
+/// - There is only an output if there were no errors
+/// - Types in the output and diagnostics are kept in `types`, so it (or a clone of it) has to be
+///     kept alive while they're used
+///
+pub struct CompileOutput
+{
+    pub output:   Option<Output>,
+    pub errors:   Vec<Diagnostic>,
+    pub warnings: Vec<Diagnostic>,
+    pub types:    TypeContext,
+}
+
+impl CompileOutput
+{
+    pub fn is_ok(&self) -> bool
+    {
+        self.errors.is_empty()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Compile                                  */
+/* -------------------------------------------------------------------------- */
+
+///
+/// Compile some source text up to the stage given in the options
+///
+/// - Each stage is also available on its own, through `Parser` and `Compiler`
+/// - Each compilation makes its types in a new type context, which is removed once the output is
+///     dropped
+///
+pub fn compile(source: String, options: &CompileOptions) -> CompileOutput
+{
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    let types = TypeContext::new();
+    let scope = types.enter();
+    let output = compile_stages(source, options, &mut errors, &mut warnings);
+    drop(scope);
+
+    CompileOutput {
+        output,
+        errors,
+        warnings,
+        types,
+    }
+}
+
+fn compile_stages(
+    source: String,
+    options: &CompileOptions,
+    errors: &mut Vec<Diagnostic>,
+    warnings: &mut Vec<Diagnostic>,
+) -> Option<Output>
+{
+    let parser = Parser::new();
+    let mut compiler = Compiler::new();
+    compiler.set_print_passes(options.print_passes);
+
+    // Keep all errors and warnings, only continuing to the next stage if there are no errors
+    macro_rules! collect {
+        ($result:expr, $diagnostic:path) => {
+            match $result
+            {
+                ResultLog::Ok(result) => result,
+                ResultLog::Warn(result, new_warnings) =>
+                {
+                    warnings.extend(new_warnings.into_iter().map($diagnostic));
+                    result
+                }
+                ResultLog::Error(new_errors, new_warnings) =>
+                {
+                    errors.extend(new_errors.into_iter().map($diagnostic));
+                    warnings.extend(new_warnings.into_iter().map($diagnostic));
+                    return None;
+                }
+            }
+        };
+    }
+
+    let mut s_expression = collect!(parser.make_s_expression(source), Diagnostic::SExpression);
+    if options.stage == Stage::SExpression
+    {
+        return Some(Output::SExpression(s_expression));
+    }
+
+    parser.preprocess(&mut s_expression);
+    if options.stage == Stage::Preprocessed
+    {
+        return Some(Output::SExpression(s_expression));
+    }
+
+    let node = collect!(parser.make_node(s_expression), Diagnostic::Parse);
+    if options.stage == Stage::Ast
+    {
+        return Some(Output::Node(node));
+    }
+
+    let node = collect!(compiler.check(node), Diagnostic::Compile);
+    if options.stage == Stage::Typed
+    {
+        return Some(Output::Node(node));
+    }
+
+    let node = collect!(compiler.flatten(node), Diagnostic::Compile);
+    if options.stage == Stage::Flat
+    {
+        return Some(Output::Node(node));
+    }
+
+    let cnode = collect!(compiler.convert_c(node), Diagnostic::Compile);
+    Some(Output::C(cnode))
+}
+
+///
+/// Run the `specs` command-line tool with the process's arguments (used by the binary, and not
+///     part of the embedding API)
+///
+#[doc(hidden)]
+pub fn run_command_line()
+{
+    driver::run();
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Display                                  */
+/* -------------------------------------------------------------------------- */
+
+impl std::fmt::Display for Output
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            Output::SExpression(s_expression) => write!(f, "{}", s_expression),
+            Output::Node(node) => write!(f, "{}", node),
+            Output::C(cnode) => write!(f, "{}\n\n{}", C_HEADER, cnode),
+        }
+    }
+}
+
+impl Diagnostic
+{
+    ///
+    /// Get the part of the program this diagnostic is about, if it has one
+    ///
+    pub fn get_source(&self) -> Option<&Source>
+    {
+        match self
+        {
+            Diagnostic::SExpression(error) => error.get_source(),
+            Diagnostic::Parse(error) => error.get_source(),
+            Diagnostic::Compile(error) => error.get_source(),
+        }
+    }
+
+    ///
+    /// Get a longer description of this diagnostic, if it has one
+    ///
+    pub fn get_description(&self) -> Option<&str>
+    {
+        match self
+        {
+            Diagnostic::SExpression(error) => error.get_description(),
+            Diagnostic::Parse(error) => error.get_description(),
+            Diagnostic::Compile(error) => error.get_description(),
+        }
+    }
+}
+
+impl ErrorTrait for Diagnostic
+{
+    fn get_source(&self) -> Option<&Source>
+    {
+        Diagnostic::get_source(self)
+    }
+
+    fn get_description(&self) -> Option<&str>
+    {
+        Diagnostic::get_description(self)
+    }
+
+    fn show(&self) -> bool
+    {
+        match self
+        {
+            Diagnostic::SExpression(error) => error.show(),
+            Diagnostic::Parse(error) => error.show(),
+            Diagnostic::Compile(error) => error.show(),
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        match self
+        {
+            Diagnostic::SExpression(error) => write!(f, "{}", error),
+            Diagnostic::Parse(error) => write!(f, "{}", error),
+            Diagnostic::Compile(error) => write!(f, "{}", error),
+        }
+    }
+}
@@ -5,6 +5,8 @@ mod preprocess;
 mod parse_node;
 mod parse_atomic;
 mod parse_function;
+mod parse_class;
+mod parse_type;
 
 use crate::language::s_expression::*;
 
@@ -23,7 +25,9 @@ impl Parser
         preprocess::make_type_groups::apply(source);
 
         preprocess::make_operator_groups::apply(source);
+        preprocess::make_jump_groups::apply(source);
         preprocess::make_conditional_groups::apply(source);
+        preprocess::make_loop_groups::apply(source);
         preprocess::make_when_groups::apply(source);
         preprocess::make_assign_groups::apply(source);
 
@@ -9,6 +9,31 @@ pub fn integer(symbol: &String, source: &Source) -> Option<Integer>
     }
 }
 
+///
+/// Parse a float literal symbol (ie. `1.5`, `-2e-3`), which needs a decimal point or an exponent to
+///     be told apart from an integer
+///
+pub fn float(symbol: &String, source: &Source) -> Option<Result<Float, Error>>
+{
+    let digits = symbol.strip_prefix('-').unwrap_or(symbol);
+
+    let is_number = digits.starts_with(|c: char| c.is_ascii_digit())
+        && digits.chars().all(|c| c.is_ascii_digit() || ".eE+-".contains(c));
+    let is_float = digits.contains(&['.', 'e', 'E'][..]);
+
+    if !is_number || !is_float
+    {
+        return None;
+    }
+
+    match symbol.parse::<f64>()
+    {
+        // Literals too large for a float would otherwise become infinity
+        Ok(value) if value.is_finite() => Some(Ok(Float::new(value, source.clone()))),
+        _ => Some(Err(Error::BadFloat(symbol.clone(), source.clone()))),
+    }
+}
+
 pub fn boolean(symbol: &String, source: &Source) -> Option<Boolean>
 {
     match symbol.as_str()
@@ -19,6 +44,82 @@ pub fn boolean(symbol: &String, source: &Source) -> Option<Boolean>
     }
 }
 
+///
+/// Parse a string literal symbol (ie. `"Hello\n"`), replacing escape sequences with the characters
+///     they stand for
+///
+pub fn string(symbol: &String, source: &Source) -> Option<Result<Text, Error>>
+{
+    let delimiter = delimiters::STRING;
+    if symbol.len() < 2 * delimiter.len()
+        || !symbol.starts_with(delimiter)
+        || !symbol.ends_with(delimiter)
+    {
+        return None;
+    }
+
+    let contents = &symbol[delimiter.len()..symbol.len() - delimiter.len()];
+
+    let mut value = String::with_capacity(contents.len());
+    let mut chars = contents.chars();
+
+    while let Some(c) = chars.next()
+    {
+        if c != '\\'
+        {
+            value.push(c);
+            continue;
+        }
+
+        let escaped = match chars.next()
+        {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('0') => '\0',
+            Some('\\') => '\\',
+            Some('"') => '"',
+            Some(c) =>
+            {
+                return Some(Err(Error::BadEscapeSequence(
+                    format!("\\{}", c),
+                    source.clone(),
+                )))
+            }
+            None =>
+            {
+                return Some(Err(Error::BadEscapeSequence(
+                    String::from("\\"),
+                    source.clone(),
+                )))
+            }
+        };
+        value.push(escaped);
+    }
+
+    Some(Ok(Text::new(value, source.clone())))
+}
+
+///
+/// Parse a jump keyword on its own (ie. `break`), which has no value
+///
+pub fn jump(symbol: &String, source: &Source) -> Option<Jump>
+{
+    let mode = jump_mode(symbol)?;
+    Some(Jump::new(mode, Node::nothing(source.clone()), source.clone()))
+}
+
+pub fn jump_mode(symbol: &String) -> Option<JumpMode>
+{
+    match symbol.as_str()
+    {
+        keywords::RETURN => Some(JumpMode::Return),
+        keywords::BREAK => Some(JumpMode::Break),
+        keywords::CONTINUE => Some(JumpMode::Continue),
+        _ => None,
+    }
+}
+
 pub fn primitive_operator(symbol: &String, source: &Source) -> Option<PrimitiveOperator>
 {
     let operator = match symbol.as_str()
@@ -41,6 +142,17 @@ pub fn primitive_operator(symbol: &String, source: &Source) -> Option<PrimitiveO
         operators::XOR => primitive::Operator::ExclusiveOr,
 
         operators::CREATE => primitive::Operator::Create,
+        operators::HEAP_ALLOCATE => primitive::Operator::HeapAllocate,
+
+        operators::TO_INTEGER => primitive::Operator::ToInteger,
+        operators::TO_FLOAT => primitive::Operator::ToFloat,
+
+        operators::RANGE => primitive::Operator::Range,
+        operators::MAKE_ARRAY => primitive::Operator::MakeArray,
+        operators::ARRAY_AT => primitive::Operator::ArrayAt,
+        operators::ARRAY_LENGTH => primitive::Operator::ArrayLength,
+
+        operators::PRINT => primitive::Operator::Print,
 
         _ =>
         {
@@ -53,7 +165,12 @@ pub fn primitive_operator(symbol: &String, source: &Source) -> Option<PrimitiveO
 
 pub fn variable(symbol: String, source: Source) -> ResultLog<Variable, Error>
 {
-    if keywords::contains(&symbol) || primitive_data_types::contains(&symbol)
+    if symbol == keywords::SELF
+    {
+        // self is a keyword, but refers to the instance argument of a method
+        ResultLog::Ok(Variable::new(symbol, source))
+    }
+    else if keywords::contains(&symbol) || primitive_data_types::contains(&symbol)
     {
         ResultLog::new_error(Error::UnexpectedKeyword(symbol, source))
     }
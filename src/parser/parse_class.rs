@@ -0,0 +1,535 @@
+use super::parse_function;
+use super::parse_node::*;
+use super::parse_type::make_type;
+use crate::utilities::IntoN;
+
+use crate::language::node::Parameter;
+use crate::language::types::Type;
+use crate::language::{MemberScope, Visibility};
+
+///
+/// Data member information gathered from a `data` block, before member types are parsed
+///
+pub type PartialMember = (String, MemberScope, Visibility, Visibility, Source);
+
+///
+/// Method information gathered from a `public`, `private`, or trait implementation block, before
+///     method bodies are parsed (including the name of the trait being implemented, if any)
+///
+pub type PartialMethod = (MemberScope, Visibility, Option<String>);
+
+pub fn definition(
+    elements: Vec<SExpression>,
+    source: &Source,
+) -> ResultLog<(NodeKind, PartialNodeData, Vec<ParseNode>), Error>
+{
+    use SExpression::*;
+
+    let mut iter = elements.into_iter();
+    iter.next(); // skip the type keyword
+
+    // Get the type name, parameters, and body
+    //  type Name {...}
+    //  type Name [T] [U is Trait] ... {...}
+
+    let layout_error = || {
+        Error::BadTypeDefinition(
+            String::from("Unexpected type definition layout, expected type Name [T]... {...}"),
+            source.clone(),
+        )
+    };
+
+    let name = match iter.next()
+    {
+        Some(Symbol(name, _)) => name,
+        _ => return ResultLog::new_error(layout_error()),
+    };
+
+    let mut warnings = Vec::new();
+    let mut errors = Vec::new();
+
+    let mut parameters = Vec::new();
+    let body_elements = loop
+    {
+        match iter.next()
+        {
+            Some(List(BracketType::Square, parameter_elements, parameter_source)) =>
+            {
+                match parse_parameter(parameter_elements, &parameter_source)
+                {
+                    Ok(parameter) => parameters.push(parameter),
+                    Err(error) => errors.push(error),
+                }
+            }
+            Some(List(BracketType::Curly, body_elements, _)) if iter.next().is_none() =>
+            {
+                break body_elements;
+            }
+            _ => return ResultLog::new_error(layout_error()),
+        }
+    };
+
+    let mut members = Vec::new();
+    let mut member_types = Vec::new();
+
+    let mut methods = Vec::new();
+    let mut method_nodes = Vec::new();
+
+    let mut traits = Vec::new();
+
+    // Accessors can come before the data block, so keep track of them and apply them to
+    //  members once the whole definition has been read
+
+    let mut public_read = Vec::new();
+    let mut public_write = Vec::new();
+
+    for element in body_elements
+    {
+        match element
+        {
+            // <...> groups are created by the preprocessor
+            List(BracketType::None, group_elements, group_source) =>
+            {
+                let mut group_iter = group_elements.into_iter();
+                match (group_iter.next(), group_iter.next(), group_iter.next())
+                {
+                    // data {...}
+                    (
+                        Some(Symbol(x, _)),
+                        Some(List(BracketType::Curly, block_elements, _)),
+                        None,
+                    ) if x == keywords::TYPE_DATA =>
+                    {
+                        for member_element in block_elements
+                        {
+                            match parse_member(member_element)
+                            {
+                                Ok((member, member_type)) =>
+                                {
+                                    members.push(member);
+                                    member_types.push(member_type);
+                                }
+                                Err(error) => errors.push(error),
+                            }
+                        }
+                    }
+                    // public {...}
+                    // private {...}
+                    (
+                        Some(Symbol(x, _)),
+                        Some(List(BracketType::Curly, block_elements, _)),
+                        None,
+                    ) if x == keywords::PUBLIC || x == keywords::PRIVATE =>
+                    {
+                        let visibility = match x == keywords::PUBLIC
+                        {
+                            true => Visibility::Public,
+                            false => Visibility::Private,
+                        };
+
+                        for block_element in block_elements
+                        {
+                            match block_element
+                            {
+                                // (fn ...)
+                                List(BracketType::Round, method_elements, method_source)
+                                    if starts_with(&method_elements, keywords::FUNCTION) =>
+                                {
+                                    match parse_method(
+                                        method_elements,
+                                        visibility,
+                                        None,
+                                        &method_source,
+                                    )
+                                    {
+                                        ResultLog::Ok((method, node)) =>
+                                        {
+                                            methods.push(method);
+                                            method_nodes.push(node);
+                                        }
+                                        ResultLog::Warn((method, node), mut new_warnings) =>
+                                        {
+                                            warnings.append(&mut new_warnings);
+                                            methods.push(method);
+                                            method_nodes.push(node);
+                                        }
+                                        ResultLog::Error(mut new_errors, mut new_warnings) =>
+                                        {
+                                            errors.append(&mut new_errors);
+                                            warnings.append(&mut new_warnings);
+                                        }
+                                    }
+                                }
+                                // (read ...)
+                                // (read-write ...)
+                                List(BracketType::Round, accessor_elements, accessor_source)
+                                    if starts_with(&accessor_elements, keywords::READ)
+                                        || starts_with(&accessor_elements, keywords::WRITE) =>
+                                {
+                                    let is_write = starts_with(&accessor_elements, keywords::WRITE);
+
+                                    match parse_accessor(accessor_elements, &accessor_source)
+                                    {
+                                        Ok(_) if visibility == Visibility::Private =>
+                                        {
+                                            warnings.push(Error::BadTypeDefinition(
+                                                String::from("Accessors in private blocks have no effect"),
+                                                accessor_source,
+                                            ));
+                                        }
+                                        Ok(accessor) if is_write => public_write.push(accessor),
+                                        Ok(accessor) => public_read.push(accessor),
+                                        Err(error) => errors.push(error),
+                                    }
+                                }
+                                block_element =>
+                                {
+                                    errors.push(Error::BadTypeDefinition(
+                                        format!(
+                                            "Unexpected element in {} block: {}",
+                                            x, block_element
+                                        ),
+                                        block_element.get_source(),
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                    // is Trait
+                    (Some(Symbol(x, _)), Some(Symbol(trait_name, _)), None)
+                        if x == keywords::IS =>
+                    {
+                        traits.push(trait_name);
+                    }
+                    // is Trait {...}
+                    (
+                        Some(Symbol(x, _)),
+                        Some(Symbol(trait_name, _)),
+                        Some(List(BracketType::Curly, block_elements, _)),
+                    ) if x == keywords::IS && group_iter.next().is_none() =>
+                    {
+                        // Methods in an implementation block replace the trait's methods, and
+                        //  take on their visibility once the trait is resolved
+
+                        for block_element in block_elements
+                        {
+                            match block_element
+                            {
+                                // (fn ...)
+                                List(BracketType::Round, method_elements, method_source)
+                                    if starts_with(&method_elements, keywords::FUNCTION) =>
+                                {
+                                    match parse_method(
+                                        method_elements,
+                                        Visibility::Public,
+                                        Some(trait_name.clone()),
+                                        &method_source,
+                                    )
+                                    {
+                                        ResultLog::Ok((method, node)) =>
+                                        {
+                                            methods.push(method);
+                                            method_nodes.push(node);
+                                        }
+                                        ResultLog::Warn((method, node), mut new_warnings) =>
+                                        {
+                                            warnings.append(&mut new_warnings);
+                                            methods.push(method);
+                                            method_nodes.push(node);
+                                        }
+                                        ResultLog::Error(mut new_errors, mut new_warnings) =>
+                                        {
+                                            errors.append(&mut new_errors);
+                                            warnings.append(&mut new_warnings);
+                                        }
+                                    }
+                                }
+                                block_element =>
+                                {
+                                    errors.push(Error::BadTypeDefinition(
+                                        format!(
+                                            "Unexpected element in {} implementation block: {}",
+                                            trait_name, block_element
+                                        ),
+                                        block_element.get_source(),
+                                    ));
+                                }
+                            }
+                        }
+
+                        traits.push(trait_name);
+                    }
+                    _ =>
+                    {
+                        errors.push(Error::BadTypeDefinition(
+                            String::from("Unexpected group in type definition"),
+                            group_source,
+                        ));
+                    }
+                }
+            }
+            element =>
+            {
+                errors.push(Error::BadTypeDefinition(
+                    format!("Unexpected element in type definition: {}", element),
+                    element.get_source(),
+                ));
+            }
+        }
+    }
+
+    // Apply accessors to members
+
+    let accessors = public_read
+        .into_iter()
+        .map(|accessor| (accessor, false))
+        .chain(public_write.into_iter().map(|accessor| (accessor, true)));
+
+    for ((accessor_name, scope, accessor_source), is_write) in accessors
+    {
+        match members
+            .iter_mut()
+            .find(|(name, member_scope, ..)| *name == accessor_name && *member_scope == scope)
+        {
+            Some((_, _, read_visibility, write_visibility, _)) =>
+            {
+                *read_visibility = Visibility::Public;
+                if is_write
+                {
+                    *write_visibility = Visibility::Public;
+                }
+            }
+            None =>
+            {
+                errors.push(Error::BadTypeDefinition(
+                    format!("Accessor for unknown data member: {}", accessor_name),
+                    accessor_source,
+                ));
+            }
+        }
+    }
+
+    ResultLog::maybe_error(
+        (
+            NodeKind::Class,
+            PartialNodeData::Class(name, parameters, members, methods, traits, member_types),
+            method_nodes,
+        ),
+        warnings,
+        errors,
+    )
+}
+
+///
+/// Create a partial alias node from a type alias definition
+///
+/// - `type Name = T` names some other type
+/// - `type Name = is T` names a bounded type
+///
+pub fn alias(
+    elements: Vec<SExpression>,
+    source: &Source,
+) -> ResultLog<(NodeKind, PartialNodeData, Vec<ParseNode>), Error>
+{
+    use SExpression::*;
+
+    let mut iter = elements.into_iter();
+    iter.next(); // skip the type keyword
+
+    let (name, type_expression) = match (iter.next(), iter.next(), iter.next(), iter.next())
+    {
+        // type Name = T
+        (Some(Symbol(name, _)), Some(Symbol(x, _)), Some(t), None)
+            if x == operators::ASSIGN_BINDING =>
+        {
+            (name, t)
+        }
+        // type Name = is T
+        (Some(Symbol(name, _)), Some(Symbol(x1, _)), Some(Symbol(x2, is_source)), Some(t))
+            if x1 == operators::ASSIGN_BINDING && x2 == keywords::IS && iter.next().is_none() =>
+        {
+            let bounded = List(BracketType::None, vec![Symbol(x2, is_source), t], source.clone());
+            (name, bounded)
+        }
+        _ =>
+        {
+            let error = Error::BadTypeDefinition(
+                String::from("Unexpected type alias layout, expected type Name = T"),
+                source.clone(),
+            );
+            return ResultLog::new_error(error);
+        }
+    };
+
+    ResultLog::Ok((
+        NodeKind::Alias,
+        PartialNodeData::Alias(name, vec![ParseNode::UnparsedType(type_expression)]),
+        Vec::new(),
+    ))
+}
+
+///
+/// Parse a type parameter `[T]`, `[T <is Trait>]`, or `[T <is {Trait1 Trait2 ...}>]`
+///
+fn parse_parameter(elements: Vec<SExpression>, source: &Source) -> Result<Parameter, Error>
+{
+    use SExpression::*;
+
+    let mut iter = elements.into_iter();
+    match (iter.next(), iter.next(), iter.next())
+    {
+        (Some(Symbol(name, _)), None, None) => Ok(Parameter::new(name, Type::Unknown)),
+        (Some(Symbol(name, _)), Some(bound @ List(BracketType::None, _, _)), None) =>
+        {
+            Ok(Parameter::new(name, make_type(bound)?))
+        }
+        _ => Err(Error::BadTypeDefinition(
+            String::from("Unexpected type parameter, expected [T] or [T is Trait]"),
+            source.clone(),
+        )),
+    }
+}
+
+fn starts_with(elements: &[SExpression], keyword: &str) -> bool
+{
+    match elements.first()
+    {
+        Some(SExpression::Symbol(x, _)) => x == keyword,
+        _ => false,
+    }
+}
+
+///
+/// Parse either `name` (static) or `(self . name)` (instance)
+///
+fn parse_scoped_name(s_expression: SExpression) -> Result<(String, MemberScope), Error>
+{
+    use SExpression::*;
+
+    match s_expression
+    {
+        Symbol(name, _) => Ok((name, MemberScope::Static)),
+        List(BracketType::Round, elements, source) => match elements.as_slice()
+        {
+            // (self . name)
+            [Symbol(x1, _), Symbol(x2, _), Symbol(name, _)]
+                if x1 == keywords::SELF && x2 == operators::ACCESS =>
+            {
+                Ok((name.clone(), MemberScope::Instance))
+            }
+            _ => Err(Error::BadTypeDefinition(
+                format!(
+                    "Unexpected member name: {}, expected name or self.name",
+                    List(BracketType::Round, elements, source.clone())
+                ),
+                source,
+            )),
+        },
+        s_expression => Err(Error::BadTypeDefinition(
+            format!(
+                "Unexpected member name: {}, expected name or self.name",
+                s_expression
+            ),
+            s_expression.get_source(),
+        )),
+    }
+}
+
+///
+/// Parse a data member `[name Type]` or `[(self . name) Type]`
+///
+fn parse_member(s_expression: SExpression) -> Result<(PartialMember, ParseNode), Error>
+{
+    use SExpression::*;
+
+    match s_expression
+    {
+        List(BracketType::Square, elements, source) if elements.len() == 2 =>
+        {
+            let (name_expression, type_expression) = elements.into_2();
+            let (name, scope) = parse_scoped_name(name_expression)?;
+
+            let member = (
+                name,
+                scope,
+                Visibility::Private,
+                Visibility::Private,
+                source,
+            );
+            Ok((member, ParseNode::UnparsedType(type_expression)))
+        }
+        s_expression => Err(Error::BadTypeDefinition(
+            format!("Unexpected data member: {}, expected [name Type]", s_expression),
+            s_expression.get_source(),
+        )),
+    }
+}
+
+///
+/// Parse an accessor `(read name)` or `(read-write name)`
+///
+fn parse_accessor(
+    elements: Vec<SExpression>,
+    source: &Source,
+) -> Result<(String, MemberScope, Source), Error>
+{
+    if elements.len() != 2
+    {
+        return Err(Error::BadTypeDefinition(
+            String::from("Unexpected accessor layout, expected (read name) or (read-write name)"),
+            source.clone(),
+        ));
+    }
+
+    let (_, name_expression) = elements.into_2();
+    let (name, scope) = parse_scoped_name(name_expression)?;
+
+    Ok((name, scope, source.clone()))
+}
+
+///
+/// Parse a method definition, creating a partial function node
+///
+fn parse_method(
+    elements: Vec<SExpression>,
+    visibility: Visibility,
+    implements: Option<String>,
+    source: &Source,
+) -> ResultLog<(PartialMethod, ParseNode), Error>
+{
+    match parse_function::definition(elements, parse_function::Mode::Any, source)
+    {
+        ResultLog::Ok((kind, data, children)) => ResultLog::Ok(make_method(
+            kind,
+            data,
+            children,
+            visibility,
+            implements,
+            source,
+        )),
+        ResultLog::Warn((kind, data, children), warnings) => ResultLog::Warn(
+            make_method(kind, data, children, visibility, implements, source),
+            warnings,
+        ),
+        ResultLog::Error(errors, warnings) => ResultLog::Error(errors, warnings),
+    }
+}
+
+fn make_method(
+    kind: NodeKind,
+    data: PartialNodeData,
+    children: Vec<ParseNode>,
+    visibility: Visibility,
+    implements: Option<String>,
+    source: &Source,
+) -> (PartialMethod, ParseNode)
+{
+    let scope = match &data
+    {
+        PartialNodeData::Function(_, true, _, _) => MemberScope::Instance,
+        _ => MemberScope::Static,
+    };
+
+    let node = ParseNode::PartialNode(kind, data, children, source.clone());
+    ((scope, visibility, implements), node)
+}
@@ -20,6 +20,48 @@ pub fn definition(
     let mut iter = elements.into_iter();
     iter.next(); // skip the fn keyword
 
+    // Anonymous functions go straight to their arguments, return type, or body
+    //  fn <...> ...
+    //  fn -> Type ...
+    //  fn {...}
+
+    let is_anonymous = match iter.as_slice().first()
+    {
+        Some(List(BracketType::None, _, _)) | Some(List(BracketType::Curly, _, _)) => true,
+        Some(Symbol(x, _)) => x == keywords::RETURNS,
+        _ => false,
+    };
+
+    if is_anonymous
+    {
+        if mode != Mode::StaticOnly
+        {
+            let error = Error::BadFunctionName(
+                String::from("Methods must have a name"),
+                source.clone(),
+            );
+            return ResultLog::new_error(error);
+        }
+
+        return match parse_signature(iter, source)
+        {
+            ResultLog::Ok((argument_names, types, body)) => ResultLog::Ok((
+                NodeKind::Closure,
+                PartialNodeData::Function(String::new(), false, argument_names, types),
+                vec![ParseNode::UnparsedNode(body)],
+            )),
+            ResultLog::Warn((argument_names, types, body), warnings) => ResultLog::Warn(
+                (
+                    NodeKind::Closure,
+                    PartialNodeData::Function(String::new(), false, argument_names, types),
+                    vec![ParseNode::UnparsedNode(body)],
+                ),
+                warnings,
+            ),
+            ResultLog::Error(errors, warnings) => ResultLog::Error(errors, warnings),
+        };
+    }
+
     // Get the function name and check if it's an instance method
     let (name, is_instance_method) = match iter.next()
     {
@@ -66,6 +108,36 @@ pub fn definition(
         }
     };
 
+    match parse_signature(iter, source)
+    {
+        ResultLog::Ok((argument_names, types, body)) => ResultLog::Ok((
+            NodeKind::Function,
+            PartialNodeData::Function(name, is_instance_method, argument_names, types),
+            vec![ParseNode::UnparsedNode(body)],
+        )),
+        ResultLog::Warn((argument_names, types, body), warnings) => ResultLog::Warn(
+            (
+                NodeKind::Function,
+                PartialNodeData::Function(name, is_instance_method, argument_names, types),
+                vec![ParseNode::UnparsedNode(body)],
+            ),
+            warnings,
+        ),
+        ResultLog::Error(errors, warnings) => ResultLog::Error(errors, warnings),
+    }
+}
+
+///
+/// Get the argument names, types (return type first), and body of a function from the elements
+///     following its name
+///
+fn parse_signature(
+    mut iter: std::vec::IntoIter<SExpression>,
+    source: &Source,
+) -> ResultLog<(Vec<String>, Vec<ParseNode>, SExpression), Error>
+{
+    use SExpression::*;
+
     // Get the function arguments, return type, and body expressions
     let (maybe_arguments, maybe_return_type, body) =
         match (iter.next(), iter.next(), iter.next(), iter.next())
@@ -143,12 +215,14 @@ pub fn definition(
             }
         };
 
-    // If a return type was given, create a new child ParseItem, otherwise use a complete void type
-    //  note: void type isn't added to child_items, since it doesn't need to onto the parse queue
+    // If a return type was given, create a new child ParseItem, otherwise use a type variable so the
+    //  return type is inferred from the body (see Infer)
+    //  note: the type variable isn't added to child_items, since it doesn't need to onto the parse
+    //      queue
     let return_type = match maybe_return_type
     {
         Some(s_expression) => ParseNode::UnparsedType(s_expression),
-        None => ParseNode::ParsedType(Type::Void),
+        None => ParseNode::ParsedType(TypeVariable::new().to_type()),
     };
 
     let mut warnings = Vec::new();
@@ -188,15 +262,7 @@ pub fn definition(
         None => (),
     };
 
-    ResultLog::maybe_error(
-        (
-            NodeKind::Function,
-            PartialNodeData::Function(name, is_instance_method, argument_names, types),
-            vec![ParseNode::UnparsedNode(body)],
-        ),
-        warnings,
-        errors,
-    )
+    ResultLog::maybe_error((argument_names, types, body), warnings, errors)
 }
 
 fn parse_argument(
@@ -220,6 +286,27 @@ fn parse_argument(
                 ResultLog::new_error(error)
             }
         },
+        // [name ref is T] => [name (ref is T)]
+        List(BracketType::Square, elements, source) if elements.len() > 2 =>
+        {
+            let mut iter = elements.into_iter();
+            match iter.next()
+            {
+                Some(Symbol(name, _)) =>
+                {
+                    let argument_type = List(BracketType::Round, iter.collect(), source);
+                    ResultLog::Ok((name, ParseNode::UnparsedType(argument_type)))
+                }
+                _ =>
+                {
+                    let error = Error::BadFunctionArgument(
+                        format!("Expected an argument name"),
+                        function_source,
+                    );
+                    ResultLog::new_error(error)
+                }
+            }
+        }
         s_expression =>
         {
             let error = Error::BadFunctionArgument(format!("{}", s_expression), function_source);
@@ -56,7 +56,6 @@ pub enum ParseNode
 
     ParsedType(Type),
     UnparsedType(SExpression),
-    PartialType(Vec<ParseNode>, Source),
 }
 
 impl ParseNode
@@ -71,7 +70,6 @@ impl ParseNode
 
             ParseNode::ParsedType(_) => Source::empty(),
             ParseNode::UnparsedType(s_expression) => s_expression.get_source(),
-            ParseNode::PartialType(_, source) => source.clone(),
         }
     }
 }
@@ -83,7 +81,7 @@ impl Recur<ParseNode> for ParseNode
         match self
         {
             ParseNode::PartialNode(
-                NodeKind::Function,
+                NodeKind::Function | NodeKind::Closure,
                 PartialNodeData::Function(_, _, _, types),
                 children,
                 _,
@@ -92,6 +90,21 @@ impl Recur<ParseNode> for ParseNode
                 // Make sure type annotations are added as child nodes
                 children.iter().chain(types.iter()).collect()
             }
+            ParseNode::PartialNode(
+                NodeKind::Class,
+                PartialNodeData::Class(_, _, _, _, _, member_types),
+                children,
+                _,
+            ) =>
+            {
+                // Make sure data member types are added as child nodes
+                children.iter().chain(member_types.iter()).collect()
+            }
+            ParseNode::PartialNode(NodeKind::Alias, PartialNodeData::Alias(_, types), _, _) =>
+            {
+                // Make sure the aliased type is added as a child node
+                types.iter().collect()
+            }
             ParseNode::PartialNode(_, _, children, _) => children.iter().collect(),
             _ => Vec::new(),
         }
@@ -101,11 +114,21 @@ impl Recur<ParseNode> for ParseNode
         match self
         {
             ParseNode::PartialNode(
-                NodeKind::Function,
+                NodeKind::Function | NodeKind::Closure,
                 PartialNodeData::Function(_, _, _, types),
                 children,
                 _,
             ) => children.iter_mut().chain(types.iter_mut()).collect(),
+            ParseNode::PartialNode(
+                NodeKind::Class,
+                PartialNodeData::Class(_, _, _, _, _, member_types),
+                children,
+                _,
+            ) => children.iter_mut().chain(member_types.iter_mut()).collect(),
+            ParseNode::PartialNode(NodeKind::Alias, PartialNodeData::Alias(_, types), _, _) =>
+            {
+                types.iter_mut().collect()
+            }
             ParseNode::PartialNode(_, _, children, _) => children.iter_mut().collect(),
             _ => Vec::new(),
         }
@@ -121,9 +144,20 @@ pub enum PartialNodeData
     None,
     Access(String),
     Binding(String),
+    ForLoop(String),
+    Jump(JumpMode),
     Sequence(SequenceMode),
     Reference(ReferenceMode),
     Function(String, bool, Vec<String>, Vec<ParseNode>),
+    Class(
+        String,
+        Vec<Parameter>,
+        Vec<parse_class::PartialMember>,
+        Vec<parse_class::PartialMethod>,
+        Vec<String>,
+        Vec<ParseNode>,
+    ),
+    Alias(String, Vec<ParseNode>),
 }
 
 impl Default for PartialNodeData
@@ -206,10 +240,8 @@ impl RecurTransform<ParseNode, ParseState, Error> for ParseTransform
                     ResultLog::Error(errors, warnings) => ResultLog::Error(errors, warnings),
                 }
             }
-            ParseNode::PartialNode(..) | ParseNode::PartialType(..) =>
-            {
-                ResultLog::new_error(Error::Internal(format!("Unexpected enter: {:?}", node)))
-            }
+            // Partial nodes can be created ahead of time by their parent (ie. methods in a class)
+            ParseNode::PartialNode(..) => ResultLog::Ok(()),
         }
     }
 
@@ -277,7 +309,6 @@ impl RecurTransform<ParseNode, ParseState, Error> for ParseTransform
                 }
             }
             ParseNode::ParsedType(..) => ResultLog::Ok(()),
-            ParseNode::PartialType(..) => unimplemented!(), // Currently, we're only handling types that have no child ParseNodes
 
             ParseNode::UnparsedNode(..) | ParseNode::UnparsedType(..) =>
             {
@@ -288,7 +319,9 @@ impl RecurTransform<ParseNode, ParseState, Error> for ParseTransform
 }
 
 use super::parse_atomic;
+use super::parse_class;
 use super::parse_function;
+use super::parse_type;
 
 ///
 /// Inspect the structure of a potential Node and create a partial or completed ParseNode
@@ -319,11 +352,28 @@ fn make_partial_node(s_expression: SExpression) -> ResultLog<ParseNode, Error>
                 Some(integer) => return ResultLog::Ok(parsed(integer)),
                 None => (),
             }
+            match parse_atomic::float(&symbol, &source)
+            {
+                Some(Ok(float)) => return ResultLog::Ok(parsed(float)),
+                Some(Err(error)) => return ResultLog::new_error(error),
+                None => (),
+            }
             match parse_atomic::boolean(&symbol, &source)
             {
                 Some(boolean) => return ResultLog::Ok(parsed(boolean)),
                 None => (),
             }
+            match parse_atomic::string(&symbol, &source)
+            {
+                Some(Ok(text)) => return ResultLog::Ok(parsed(text)),
+                Some(Err(error)) => return ResultLog::new_error(error),
+                None => (),
+            }
+            match parse_atomic::jump(&symbol, &source)
+            {
+                Some(jump) => return ResultLog::Ok(parsed(jump)),
+                None => (),
+            }
             match parse_atomic::primitive_operator(&symbol, &source)
             {
                 Some(operator) => return ResultLog::Ok(parsed(operator)),
@@ -439,6 +489,57 @@ fn make_partial_node(s_expression: SExpression) -> ResultLog<ParseNode, Error>
                         vec![unparsed(a), unparsed(b), unparsed(c)],
                     )
                 }
+                // While
+                //  (while a {...})
+                [Symbol(x, _), a, b] if x == keywords::WHILE => (
+                    NodeKind::WhileLoop,
+                    PartialNodeData::None,
+                    vec![unparsed(a), unparsed(b)],
+                ),
+                // For
+                //  (for x in a {...})
+                [Symbol(x1, _), Symbol(name, _), Symbol(x2, _), a, b]
+                    if x1 == keywords::FOR && x2 == keywords::IN =>
+                {
+                    (
+                        NodeKind::WhileLoop,
+                        PartialNodeData::ForLoop(std::mem::take(name)),
+                        vec![unparsed(a), unparsed(b)],
+                    )
+                }
+                // Jump
+                //  (break)
+                //  (return)
+                [Symbol(x, _)] if parse_atomic::jump_mode(x).is_some() => (
+                    NodeKind::Jump,
+                    PartialNodeData::Jump(parse_atomic::jump_mode(x).unwrap()),
+                    Vec::new(),
+                ),
+                // Return with a value
+                //  (return a)
+                [Symbol(x, _), a] if x == keywords::RETURN => (
+                    NodeKind::Jump,
+                    PartialNodeData::Jump(JumpMode::Return),
+                    vec![unparsed(a)],
+                ),
+                // Return with an ungrouped value
+                //  (return f a b)
+                [Symbol(x, _), ..] if x == keywords::RETURN =>
+                {
+                    let value = List(BracketType::Round, elements.split_off(1), source.clone());
+                    (
+                        NodeKind::Jump,
+                        PartialNodeData::Jump(JumpMode::Return),
+                        vec![ParseNode::UnparsedNode(value)],
+                    )
+                }
+                // Only returns have a value
+                //  (break a)
+                [Symbol(x, _), ..] if x == keywords::BREAK || x == keywords::CONTINUE =>
+                {
+                    let description = format!("'{}' can't have a value", x);
+                    return ResultLog::new_error(Error::UnknownExpression(description, source));
+                }
                 // (...)
                 s_expressions =>
                 {
@@ -447,7 +548,7 @@ fn make_partial_node(s_expression: SExpression) -> ResultLog<ParseNode, Error>
                         // Function
                         //  (fn ...)
                         Some(Symbol(x, _))
-                            if x == keywords::FUNCTION && s_expressions.len() >= 3 =>
+                            if x == keywords::FUNCTION && s_expressions.len() >= 2 =>
                         {
                             match parse_function::definition(
                                 elements,
@@ -468,12 +569,43 @@ fn make_partial_node(s_expression: SExpression) -> ResultLog<ParseNode, Error>
                                 }
                             }
                         }
+                        // Type Alias
+                        //  (type Name = ...)
+                        Some(Symbol(x, _)) if x == keywords::TYPE && is_alias(s_expressions) =>
+                        {
+                            match parse_class::alias(elements, &source)
+                            {
+                                ResultLog::Ok(parts) => parts,
+                                ResultLog::Warn(parts, mut new_warnings) =>
+                                {
+                                    warnings.append(&mut new_warnings);
+                                    parts
+                                }
+                                ResultLog::Error(errors, mut new_warnings) =>
+                                {
+                                    warnings.append(&mut new_warnings);
+                                    return ResultLog::Error(errors, warnings);
+                                }
+                            }
+                        }
                         // Class
                         //  (type ...)
                         Some(Symbol(x, _)) if x == keywords::TYPE && s_expressions.len() >= 3 =>
                         {
-                            let error = Error::Internal(format!("Classes not yet implemented"));
-                            return ResultLog::new_error(error);
+                            match parse_class::definition(elements, &source)
+                            {
+                                ResultLog::Ok(parts) => parts,
+                                ResultLog::Warn(parts, mut new_warnings) =>
+                                {
+                                    warnings.append(&mut new_warnings);
+                                    parts
+                                }
+                                ResultLog::Error(errors, mut new_warnings) =>
+                                {
+                                    warnings.append(&mut new_warnings);
+                                    return ResultLog::Error(errors, warnings);
+                                }
+                            }
                         }
                         // Call
                         //  (...)
@@ -516,10 +648,17 @@ fn make_partial_node(s_expression: SExpression) -> ResultLog<ParseNode, Error>
         }
         List(BracketType::Square, elements, source) =>
         {
-            // [...] lists
+            // [...] lists are array literals
 
-            ResultLog::new_error(Error::UnknownExpression(
-                format!("{}", &List(BracketType::Square, elements, source.clone())),
+            let operator = PrimitiveOperator::new(primitive::Operator::ArrayLiteral, source.clone());
+
+            let mut children = vec![parsed(operator)];
+            children.extend(elements.into_iter().map(ParseNode::UnparsedNode));
+
+            ResultLog::Ok(ParseNode::PartialNode(
+                NodeKind::Call,
+                PartialNodeData::None,
+                children,
                 source,
             ))
         }
@@ -536,6 +675,21 @@ fn make_partial_node(s_expression: SExpression) -> ResultLog<ParseNode, Error>
     }
 }
 
+///
+/// Check if a `(type ...)` list defines an alias (ie. `(type Name = T)`)
+///
+fn is_alias(s_expressions: &[SExpression]) -> bool
+{
+    match s_expressions
+    {
+        [_, SExpression::Symbol(_, _), SExpression::Symbol(x, _), ..] =>
+        {
+            x == operators::ASSIGN_BINDING
+        }
+        _ => false,
+    }
+}
+
 ///
 /// Take the data from a partial node and try to create a completed Node
 ///
@@ -596,8 +750,32 @@ fn make_complete_node(
             let (a, b, c) = children.into_3();
             Conditional::new(a, b, c, source.clone()).to_node()
         }
+        (NodeKind::WhileLoop, PartialNodeData::None, 2) =>
+        {
+            let (a, b) = children.into_2();
+            WhileLoop::new(a, b, source).to_node()
+        }
+        (NodeKind::WhileLoop, PartialNodeData::ForLoop(name), 2) =>
+        {
+            let (a, b) = children.into_2();
+            make_for_loop(name, a, b, source)
+        }
+        (NodeKind::Jump, PartialNodeData::Jump(mode), 0) =>
+        {
+            Jump::new(mode, Node::nothing(source.clone()), source).to_node()
+        }
+        (NodeKind::Jump, PartialNodeData::Jump(JumpMode::Return), 1) =>
+        {
+            let a = children.into_1();
+            Jump::new(JumpMode::Return, a, source).to_node()
+        }
 
-        (NodeKind::Function, PartialNodeData::Function(name, false, argument_names, types), 1) =>
+        // note: instance methods get their self argument once the class they belong to is complete
+        (
+            kind @ (NodeKind::Function | NodeKind::Closure),
+            PartialNodeData::Function(name, _, argument_names, types),
+            1,
+        ) =>
         {
             let mut types_iter = types.into_iter();
             // Make sure we have a fully-parsed return type
@@ -645,7 +823,93 @@ fn make_complete_node(
             }
 
             let body = children.into_1();
-            Function::new(name, arguments, return_type, body, source).to_node()
+            match kind
+            {
+                NodeKind::Closure => Closure::new(arguments, return_type, body, source).to_node(),
+                _ => Function::new(name, arguments, return_type, body, source).to_node(),
+            }
+        }
+
+        (
+            NodeKind::Class,
+            PartialNodeData::Class(
+                name,
+                parameters,
+                partial_members,
+                partial_methods,
+                traits,
+                member_types,
+            ),
+            n,
+        ) if n == partial_methods.len() =>
+        {
+            // Make sure we have fully-parsed types for all data members
+
+            let mut members = Vec::with_capacity(partial_members.len());
+
+            for ((name, scope, read_visibility, write_visibility, member_source), type_node) in
+                partial_members.into_iter().zip(member_types)
+            {
+                match type_node
+                {
+                    ParseNode::ParsedType(t) => members.push(Member::new(
+                        name,
+                        t,
+                        scope,
+                        read_visibility,
+                        write_visibility,
+                        member_source,
+                    )),
+                    node =>
+                    {
+                        return ResultLog::new_error(Error::Internal(format!(
+                            "Failed to parse data member type: {:?}",
+                            node
+                        )));
+                    }
+                }
+            }
+
+            // Pair each method function with its scope and visibility
+
+            let mut methods = Vec::with_capacity(partial_methods.len());
+
+            for ((scope, visibility, implements), child) in partial_methods.into_iter().zip(children)
+            {
+                match child
+                {
+                    Node::Function(function) =>
+                    {
+                        methods.push(Method::new(function, scope, visibility, implements))
+                    }
+                    node =>
+                    {
+                        return ResultLog::new_error(Error::Internal(format!(
+                            "Expected method function, found: {}",
+                            node
+                        )));
+                    }
+                }
+            }
+
+            Class::new(name, parameters, members, methods, traits, source).to_node()
+        }
+
+        (NodeKind::Alias, PartialNodeData::Alias(name, types), 0) =>
+        {
+            // Make sure we have a fully-parsed aliased type
+
+            match types.into_1()
+            {
+                ParseNode::ParsedType(t) => Alias::new(name, t, source).to_node(),
+                node =>
+                {
+                    return ResultLog::new_error(Error::Internal(format!(
+                        "Failed to parse aliased type: {:?}",
+                        node
+                    )));
+                }
+            }
         }
 
         (kind, mode, len) =>
@@ -661,32 +925,72 @@ fn make_complete_node(
 }
 
 ///
-/// Inspect the structure of a potential Type and create a partial or completed ParseNode
+/// Create a while loop over the elements of a range or array from `for x in I {...}`
 ///
-fn make_partial_type(s_expression: SExpression) -> ResultLog<ParseNode, Error>
+/// - The iterator counts through the range, or the indices of the array, and the iterable is
+///     kept so that each element can be taken from it
+///
+/// ex.
+///
+///     {
+///         let _iterable_x = I
+///         let _iterator_x = (iterator _iterable_x)
+///         while (iterator-has-next (ref _iterator_x))
+///         {
+///             let x = (iterator-next (mut _iterator_x) _iterable_x)
+///             {...}
+///         }
+///     }
+///
+fn make_for_loop(name: String, iterable: Node, body: Node, source: Source) -> Node
 {
-    use SExpression::*;
+    let iterable_name = format!("_iterable_{}", name);
+    let iterator_name = format!("_iterator_{}", name);
 
-    match s_expression
-    {
-        Symbol(symbol, _) =>
-        {
-            let t = match symbol.as_str()
-            {
-                primitive_data_types::INTEGER => Type::Integer,
-                primitive_data_types::BOOLEAN => Type::Boolean,
-                primitive_data_types::FLOAT => Type::Float,
-                primitive_data_types::VOID => Type::Void,
+    let variable = |name: &String| Variable::new(name.clone(), source.clone()).to_node();
+    let iterator_reference = |mode: ReferenceMode| {
+        Reference::new(mode, variable(&iterator_name), source.clone()).to_node()
+    };
+    let iterator_operator = |operator: primitive::Operator, operands: Vec<Node>| {
+        let operator_node = PrimitiveOperator::new(operator, source.clone()).to_node();
+        Call::new(operator_node, operands, source.clone()).to_node()
+    };
 
-                // Any non-primitive types identified with a symbol are instances
-                s => InstanceType::new(String::from(s)).to_type(),
-            };
+    let condition = iterator_operator(
+        primitive::Operator::IteratorHasNext,
+        vec![iterator_reference(ReferenceMode::Immutable)],
+    );
+    let next = iterator_operator(
+        primitive::Operator::IteratorNext,
+        vec![iterator_reference(ReferenceMode::Mutable), variable(&iterable_name)],
+    );
+    let bind_element = Binding::new(name, next, source.clone()).to_node();
+
+    let loop_body = Sequence::new(SequenceMode::Scope, vec![bind_element, body], source.clone());
+    let while_loop = WhileLoop::new(condition, loop_body.to_node(), source.clone());
+
+    let make_iterator = iterator_operator(
+        primitive::Operator::MakeIterator,
+        vec![variable(&iterable_name)],
+    );
+    let bind_iterable = Binding::new(iterable_name, iterable, source.clone()).to_node();
+    let bind_iterator = Binding::new(iterator_name, make_iterator, source.clone()).to_node();
+    Sequence::new(
+        SequenceMode::Scope,
+        vec![bind_iterable, bind_iterator, while_loop.to_node()],
+        source,
+    )
+    .to_node()
+}
 
-            ResultLog::Ok(ParseNode::ParsedType(t))
-        }
-        s_expression => ResultLog::new_error(Error::UnknownExpression(
-            format!("Unknown type expression: {}", s_expression),
-            s_expression.get_source(),
-        )),
+///
+/// Inspect the structure of a potential Type and create a partial or completed ParseNode
+///
+fn make_partial_type(s_expression: SExpression) -> ResultLog<ParseNode, Error>
+{
+    match parse_type::make_type(s_expression)
+    {
+        Ok(t) => ResultLog::Ok(ParseNode::ParsedType(t)),
+        Err(error) => ResultLog::new_error(error),
     }
 }
@@ -45,6 +45,7 @@ enum ParseNode
     Unparsed(BracketType, Source),
     PartialList(BracketType, Vec<ParseNode>, Source),
     Comment(Source),
+    Failed(Source),
 }
 
 impl Recur<ParseNode> for ParseNode
@@ -116,7 +117,14 @@ impl RecurTransform<ParseNode, ParseState, Error> for ParseTransform
 
                         ResultLog::Warn((), warnings)
                     }
-                    ResultLog::Error(errors, warnings) => ResultLog::Error(errors, warnings),
+                    ResultLog::Error(errors, warnings) =>
+                    {
+                        // Scan errors are already reported, so the node isn't expanded any further
+
+                        *node = ParseNode::Failed(source.clone());
+
+                        ResultLog::Error(errors, warnings)
+                    }
                 }
             }
 
@@ -160,9 +168,10 @@ impl RecurTransform<ParseNode, ParseState, Error> for ParseTransform
 
                             new_children.push(s_expression);
                         }
-                        ParseNode::Comment(..) =>
+                        ParseNode::Comment(..) | ParseNode::Failed(..) =>
                         {
-                            // Discard child comment nodes
+                            // Discard child comment nodes, and nodes whose errors were already
+                            //  reported when they failed to expand
                         }
                         child =>
                         {
@@ -197,8 +206,9 @@ fn scan_text(source: &Source) -> ResultLog<Vec<ParseNode>, Error>
 {
     enum Group
     {
-        List(char, usize, usize, usize, usize),
+        List(char, usize, usize, usize, usize, Quote, usize),
         Symbol(usize, usize, usize),
+        String(usize, usize, usize, Quote),
         LineComment(usize, usize, usize),
     }
 
@@ -211,12 +221,14 @@ fn scan_text(source: &Source) -> ResultLog<Vec<ParseNode>, Error>
 
     let text = source.get_text();
 
-    for (i, c) in text.chars().enumerate()
+    // note: Positions are byte offsets into the text, so they can be used to slice it
+    for (i, c) in text.char_indices()
     {
         // Check if the current characters ends the current group
 
         let mut end_current_group = false;
         let mut inside_line_comment = false;
+        let mut inside_string = false;
 
         if c == '\n'
         {
@@ -226,11 +238,24 @@ fn scan_text(source: &Source) -> ResultLog<Vec<ParseNode>, Error>
         match group_stack.last_mut()
         {
             None => (),
-            Some(Group::List(open, open_count, _first, last, _start_line)) =>
+            Some(Group::List(open, open_count, _first, last, _start_line, quote, quote_start)) =>
             {
                 // We're checking for open/close characters to end this list
+                //  (ignoring any inside of string literals)
+
+                if *quote == Quote::Outside && is_string_delimiter(c)
+                {
+                    *quote_start = i;
+                }
+
+                *quote = quote.next(c);
+                inside_string = *quote != Quote::Outside;
 
-                if c == *open
+                if inside_string
+                {
+                    // Brackets in strings don't change the list
+                }
+                else if c == *open
                 {
                     *open_count += 1;
                 }
@@ -259,6 +284,21 @@ fn scan_text(source: &Source) -> ResultLog<Vec<ParseNode>, Error>
                     end_current_group = true;
                 }
             }
+            Some(Group::String(_first, last, _start_line, quote)) =>
+            {
+                // We're checking for an unescaped quote to end this string
+
+                inside_string = true;
+                *quote = quote.next(c);
+
+                if *quote == Quote::Outside
+                {
+                    // The current string ends at this character, which may take more than one byte
+
+                    *last = i + c.len_utf8() - 1;
+                    end_current_group = true;
+                }
+            }
             Some(Group::LineComment(_first, last, _start_line)) =>
             {
                 // We're checking for a line ending to end this comment
@@ -267,9 +307,9 @@ fn scan_text(source: &Source) -> ResultLog<Vec<ParseNode>, Error>
 
                 if is_line_comment_end(c)
                 {
-                    // The current line comment ends at the symbol before this one
+                    // The current line comment ends before this character
 
-                    *last = i - 1;
+                    *last = i;
                     end_current_group = true;
                 }
             }
@@ -282,7 +322,7 @@ fn scan_text(source: &Source) -> ResultLog<Vec<ParseNode>, Error>
         {
             match group_stack.pop().expect("Unexpected some group")
             {
-                Group::List(open, _, first, last, start_line) =>
+                Group::List(open, _, first, last, start_line, _, _) =>
                 {
                     let bracket = match open
                     {
@@ -310,6 +350,20 @@ fn scan_text(source: &Source) -> ResultLog<Vec<ParseNode>, Error>
                     // A character that ends a symbol could start a list, comment, etc.
                     check_starts_group = true;
                 }
+                Group::String(first, last, start_line, _) =>
+                {
+                    // Strings are kept as symbols with their quotes and escapes until they're
+                    //  parsed into nodes
+
+                    let symbol = text[first..last + 1].to_owned();
+
+                    let new_source = source.get_range(start_line, line, first, last + 1);
+                    let new_node = ParseNode::Parsed(SExpression::Symbol(symbol, new_source));
+                    result.push(new_node);
+
+                    // The end of a string is part of that string
+                    check_starts_group = false;
+                }
                 Group::LineComment(first, last, start_line) =>
                 {
                     let new_source = source.get_range(start_line, line, first, last);
@@ -328,7 +382,14 @@ fn scan_text(source: &Source) -> ResultLog<Vec<ParseNode>, Error>
             {
                 // Start a list group if we hit an open character
 
-                let new_group = Group::List(c, 1, i, i, line);
+                let new_group = Group::List(c, 1, i, i, line, Quote::Outside, i);
+                group_stack.push(new_group);
+            }
+            else if is_string_delimiter(c) && group_stack.is_empty()
+            {
+                // Start a string group if we hit a quote
+
+                let new_group = Group::String(i, i, line, Quote::Inside);
                 group_stack.push(new_group);
             }
             else if is_symbol_char(c) && group_stack.is_empty()
@@ -342,7 +403,7 @@ fn scan_text(source: &Source) -> ResultLog<Vec<ParseNode>, Error>
 
         // Always check for line comments starting (if we aren't already in one)
 
-        if !inside_line_comment && is_line_comment_start(c)
+        if !inside_line_comment && !inside_string && is_line_comment_start(c)
         {
             let new_group = Group::LineComment(i, i, line);
             group_stack.push(new_group);
@@ -355,7 +416,18 @@ fn scan_text(source: &Source) -> ResultLog<Vec<ParseNode>, Error>
     {
         match group
         {
-            Group::List(open, _, first, _, start_line) =>
+            Group::List(_, _, _, _, _, Quote::Inside, quote_start)
+            | Group::List(_, _, _, _, _, Quote::Escaped, quote_start) =>
+            {
+                // The list can't be closed because it ends inside of a string literal
+
+                let quote_line = line - text[quote_start..].matches('\n').count();
+                let new_source = source.get_range(quote_line, line, quote_start, text.len());
+                let error = Error::UnclosedString(new_source);
+
+                errors.push(error);
+            }
+            Group::List(open, _, first, _, start_line, _, _) =>
             {
                 let mut open_string = String::new();
                 open_string.push(open);
@@ -376,6 +448,14 @@ fn scan_text(source: &Source) -> ResultLog<Vec<ParseNode>, Error>
                 result.push(new_node);
             }
 
+            Group::String(first, _, start_line, _) =>
+            {
+                let new_source = source.get_range(start_line, line, first, text.len());
+                let error = Error::UnclosedString(new_source);
+
+                errors.push(error);
+            }
+
             Group::LineComment(..) =>
             {
                 // Don't do anything with line comments
@@ -383,7 +463,39 @@ fn scan_text(source: &Source) -> ResultLog<Vec<ParseNode>, Error>
         }
     }
 
-    ResultLog::maybe_error(result, errors, warnings)
+    ResultLog::maybe_error(result, warnings, errors)
+}
+
+///
+/// Where a character is relative to a string literal
+///
+#[derive(Clone, Copy, PartialEq)]
+enum Quote
+{
+    Outside,
+    Inside,
+    Escaped,
+}
+
+impl Quote
+{
+    ///
+    /// Get the state after some character
+    ///
+    fn next(self, c: char) -> Quote
+    {
+        match self
+        {
+            Quote::Outside if is_string_delimiter(c) => Quote::Inside,
+            Quote::Outside => Quote::Outside,
+
+            Quote::Inside if is_string_delimiter(c) => Quote::Outside,
+            Quote::Inside if c == '\\' => Quote::Escaped,
+            Quote::Inside => Quote::Inside,
+
+            Quote::Escaped => Quote::Inside,
+        }
+    }
 }
 
 // Text scanning helper functions
@@ -444,9 +556,18 @@ fn is_line_comment_end(c: char) -> bool
     }
 }
 
+fn is_string_delimiter(c: char) -> bool
+{
+    symbols::delimiters::STRING.starts_with(c)
+}
+
 fn is_symbol_char(c: char) -> bool
 {
-    if is_whitespace(c) || is_bracket_close(c) || is_bracket_open(c) || is_line_comment_start(c)
+    if is_whitespace(c)
+        || is_bracket_close(c)
+        || is_bracket_open(c)
+        || is_line_comment_start(c)
+        || is_string_delimiter(c)
     {
         false
     }
@@ -0,0 +1,404 @@
+use super::parse_node::*;
+
+///
+/// Create a Type from a type expression
+///
+/// - `T` is a primitive type or an instance of a named type
+///     - `string` and `tag` are both the string type
+/// - `(ref T)` and `(mut T)` (or `(mut-ref T)`) are references to T
+///     - `(ref Name T1 T2 ...)` is a reference to the application `(Name T1 T2 ...)`
+/// - `(T1 T2 ... -> Tr)` is a function taking T1, T2, ... and returning Tr
+/// - `is T` and `is {T1 T2 ...}` are any type with the given traits
+/// - `(array T n)` is an array of n elements of type T, and `(array T)` is an array of any length
+/// - `(Name T1 T2 ...)` is a parameterized type applied to some type arguments
+///
+pub fn make_type(s_expression: SExpression) -> Result<Type, Error>
+{
+    use SExpression::*;
+
+    match s_expression
+    {
+        Symbol(symbol, source) => make_named_type(symbol, source),
+        List(BracketType::Round, elements, source) | List(BracketType::None, elements, source) =>
+        {
+            match elements.as_slice()
+            {
+                [] => Err(Error::BadType(
+                    String::from("Empty type expression"),
+                    source,
+                )),
+
+                // (ref T)
+                // (mut T)
+                // (mut-ref T)
+                [Symbol(x, _), ..]
+                    if x == operators::REFERENCE
+                        || x == operators::MUTABLE_REFERENCE
+                        || x == keywords::MUTABLE_REFERENCE_TYPE =>
+                {
+                    make_reference_type(elements, source)
+                }
+
+                // is T
+                // is {T1 T2 ...}
+                [Symbol(x, _), ..] if x == keywords::IS => make_bounded_type(elements, source),
+
+                // (array T n)
+                // (array T)
+                [Symbol(x, _), ..] if x == keywords::ARRAY => make_array_type(elements, source),
+
+                // (T)
+                [_] => make_type(elements.into_iter().next().unwrap()),
+
+                // (T1 T2 ... -> Tr)
+                _ if elements.iter().any(is_returns_symbol) => make_function_type(elements, source),
+
+                // (Name T1 T2 ...)
+                _ => make_application_type(elements, source),
+            }
+        }
+        List(..) => Err(Error::BadType(
+            format!(
+                "Unexpected brackets in type expression, use () to group types: {}",
+                s_expression
+            ),
+            s_expression.get_source(),
+        )),
+        Empty(source) => Err(Error::BadType(
+            String::from("Empty type expression"),
+            source,
+        )),
+    }
+}
+
+///
+/// Create a primitive type or an instance of a named type from a symbol
+///
+fn make_named_type(symbol: String, source: Source) -> Result<Type, Error>
+{
+    let t = match symbol.as_str()
+    {
+        primitive_data_types::INTEGER => Type::Integer,
+        primitive_data_types::BOOLEAN => Type::Boolean,
+        primitive_data_types::FLOAT => Type::Float,
+        primitive_data_types::LONGSTRING => Type::String,
+        // note: Tags are short strings, which use the same runtime string as long strings
+        primitive_data_types::SHORTSTRING => Type::String,
+        primitive_data_types::VOID => Type::Void,
+
+        s if keywords::contains(&symbol) || operators::contains(&symbol) =>
+        {
+            return Err(Error::BadType(
+                format!("Expected a type, found the keyword or operator '{}'", s),
+                source,
+            ));
+        }
+        s if !is_type_name(s) =>
+        {
+            return Err(Error::BadType(
+                format!("Expected a type, found '{}'", s),
+                source,
+            ));
+        }
+
+        // Any non-primitive types identified with a symbol are instances
+        s => InstanceType::new(String::from(s)).to_type(),
+    };
+
+    Ok(t)
+}
+
+///
+/// Create a reference type from `(ref T)`, `(mut T)` or `(mut-ref T)`
+///
+fn make_reference_type(elements: Vec<SExpression>, source: Source) -> Result<Type, Error>
+{
+    let mut iter = elements.into_iter();
+
+    let mode = match iter.next()
+    {
+        Some(SExpression::Symbol(x, _)) if x == operators::REFERENCE => ReferenceMode::Immutable,
+        _ => ReferenceMode::Mutable,
+    };
+
+    // Anything after the reference operator is the target type
+
+    let target_elements: Vec<SExpression> = iter.collect();
+    let target = match target_elements.len()
+    {
+        0 =>
+        {
+            return Err(Error::BadType(
+                String::from("Expected a type to reference after 'ref', 'mut' or 'mut-ref'"),
+                source,
+            ));
+        }
+        1 => target_elements.into_iter().next().unwrap(),
+        _ => SExpression::List(BracketType::Round, target_elements, source),
+    };
+
+    Ok(make_type(target)?.into_reference(mode))
+}
+
+///
+/// Create a bounded type from `is T` or `is {T1 T2 ...}`
+///
+fn make_bounded_type(elements: Vec<SExpression>, source: Source) -> Result<Type, Error>
+{
+    use SExpression::*;
+
+    let mut iter = elements.into_iter();
+    iter.next(); // skip the is keyword
+
+    let bounds = match (iter.next(), iter.next())
+    {
+        (Some(Symbol(name, name_source)), None) => vec![(name, name_source)],
+        (Some(List(BracketType::Curly, names, names_source)), None) =>
+        {
+            if names.is_empty()
+            {
+                return Err(Error::BadType(
+                    String::from("Expected at least one trait in 'is {...}'"),
+                    names_source,
+                ));
+            }
+
+            let mut bounds = Vec::with_capacity(names.len());
+            for name in names
+            {
+                match name
+                {
+                    Symbol(name, name_source) => bounds.push((name, name_source)),
+                    name =>
+                    {
+                        return Err(Error::BadType(
+                            format!("Expected a trait name, found: {}", name),
+                            name.get_source(),
+                        ));
+                    }
+                }
+            }
+            bounds
+        }
+        (None, _) =>
+        {
+            return Err(Error::BadType(
+                String::from("Expected a trait name or {...} after 'is'"),
+                source,
+            ));
+        }
+        (Some(bound), None) =>
+        {
+            return Err(Error::BadType(
+                format!(
+                    "Expected a trait name or {{...}} after 'is', found: {}",
+                    bound
+                ),
+                bound.get_source(),
+            ));
+        }
+        (Some(_), Some(extra)) =>
+        {
+            return Err(Error::BadType(
+                format!(
+                    "Unexpected '{}' after trait bound, use 'is {{T1 T2 ...}}' for multiple traits",
+                    extra
+                ),
+                extra.get_source(),
+            ));
+        }
+    };
+
+    let mut names = Vec::with_capacity(bounds.len());
+    for (name, name_source) in bounds
+    {
+        if !is_type_name(&name) || keywords::contains(&name) || operators::contains(&name)
+        {
+            return Err(Error::BadType(
+                format!("Expected a trait name, found '{}'", name),
+                name_source,
+            ));
+        }
+        names.push(name);
+    }
+
+    Ok(BoundedType::new(names).to_type())
+}
+
+///
+/// Create an array type from `(array T n)` or `(array T)`
+///
+fn make_array_type(elements: Vec<SExpression>, source: Source) -> Result<Type, Error>
+{
+    use SExpression::*;
+
+    let mut iter = elements.into_iter();
+    iter.next(); // skip the array keyword
+
+    let element_type = match iter.next()
+    {
+        Some(element) => make_type(element)?,
+        None =>
+        {
+            return Err(Error::BadType(
+                String::from("Expected an element type after 'array'"),
+                source,
+            ));
+        }
+    };
+
+    let length = match (iter.next(), iter.next())
+    {
+        (None, _) => None,
+        (Some(Symbol(length, length_source)), None) => match length.parse::<usize>()
+        {
+            Ok(length) => Some(length),
+            Err(_) =>
+            {
+                return Err(Error::BadType(
+                    format!("Expected a non-negative array length, found '{}'", length),
+                    length_source,
+                ));
+            }
+        },
+        (Some(length), None) =>
+        {
+            return Err(Error::BadType(
+                format!("Expected a non-negative array length, found: {}", length),
+                length.get_source(),
+            ));
+        }
+        (Some(_), Some(extra)) =>
+        {
+            return Err(Error::BadType(
+                format!("Unexpected '{}' after array length", extra),
+                extra.get_source(),
+            ));
+        }
+    };
+
+    Ok(ArrayType::new(element_type, length).to_type())
+}
+
+///
+/// Create a function type from `(T1 T2 ... -> Tr)`
+///
+fn make_function_type(elements: Vec<SExpression>, source: Source) -> Result<Type, Error>
+{
+    let arrow_index = elements.iter().position(is_returns_symbol).unwrap();
+
+    if let Some(extra) = elements[arrow_index + 1..]
+        .iter()
+        .find(|e| is_returns_symbol(e))
+    {
+        return Err(Error::BadType(
+            String::from("Function types can only have one '->', use () to group function types"),
+            extra.get_source(),
+        ));
+    }
+
+    let mut iter = elements.into_iter();
+
+    let mut argument_types = Vec::with_capacity(arrow_index);
+    for argument in iter.by_ref().take(arrow_index)
+    {
+        argument_types.push(make_type(argument)?);
+    }
+
+    iter.next(); // skip the arrow
+
+    let return_type = match (iter.next(), iter.next())
+    {
+        (Some(return_type), None) => make_type(return_type)?,
+        (None, _) =>
+        {
+            return Err(Error::BadType(
+                String::from("Expected a return type after '->'"),
+                source,
+            ));
+        }
+        (Some(_), Some(extra)) =>
+        {
+            return Err(Error::BadType(
+                format!(
+                    "Unexpected '{}' after return type, use () to group the return type",
+                    extra
+                ),
+                extra.get_source(),
+            ));
+        }
+    };
+
+    Ok(FunctionType::new(argument_types, return_type).to_type())
+}
+
+///
+/// Create an application of a parameterized type from `(Name T1 T2 ...)`
+///
+fn make_application_type(elements: Vec<SExpression>, source: Source) -> Result<Type, Error>
+{
+    use SExpression::*;
+
+    let mut iter = elements.into_iter();
+
+    let name = match iter.next()
+    {
+        Some(Symbol(name, name_source)) => match make_named_type(name, name_source.clone())?
+        {
+            Type::Instance(instance) => instance.get_name().clone(),
+            t =>
+            {
+                return Err(Error::BadType(
+                    format!("The primitive type '{}' doesn't take type arguments", t),
+                    name_source,
+                ));
+            }
+        },
+        Some(other) =>
+        {
+            return Err(Error::BadType(
+                format!(
+                    "Expected the name of a parameterized type, found: {}",
+                    other
+                ),
+                other.get_source(),
+            ));
+        }
+        None =>
+        {
+            return Err(Error::BadType(
+                String::from("Empty type expression"),
+                source,
+            ))
+        }
+    };
+
+    let mut arguments = Vec::new();
+    for argument in iter
+    {
+        arguments.push(make_type(argument)?);
+    }
+
+    Ok(ApplicationType::new(name, arguments).to_type())
+}
+
+fn is_returns_symbol(s_expression: &SExpression) -> bool
+{
+    match s_expression
+    {
+        SExpression::Symbol(x, _) => x == keywords::RETURNS,
+        _ => false,
+    }
+}
+
+///
+/// Check if a symbol could name a type (ie. it isn't a literal)
+///
+fn is_type_name(symbol: &str) -> bool
+{
+    match symbol.chars().next()
+    {
+        Some(c) => !c.is_ascii_digit() && c != '-' && !symbol.starts_with(delimiters::STRING),
+        None => false,
+    }
+}
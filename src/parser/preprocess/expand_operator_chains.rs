@@ -27,6 +27,11 @@ pub fn apply(expression: &mut SExpression)
 
 fn expand_chain(operator: &str, symbol: &String, source: &Source) -> Option<SExpression>
 {
+    if is_literal(symbol)
+    {
+        return None;
+    }
+
     if symbol.contains(operator)
     {
         let mut tokens = symbol.split(operator);
@@ -81,3 +86,14 @@ fn expand_chain(operator: &str, symbol: &String, source: &Source) -> Option<SExp
         return None;
     }
 }
+
+///
+/// Check if a symbol is a literal that can contain operator characters (ie. `"a.b"` or `-1.5`)
+///
+fn is_literal(symbol: &String) -> bool
+{
+    let digits = symbol.strip_prefix('-').unwrap_or(symbol);
+
+    symbol.starts_with(symbols::delimiters::STRING)
+        || digits.starts_with(|c: char| c.is_ascii_digit())
+}
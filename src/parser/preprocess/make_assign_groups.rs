@@ -43,19 +43,20 @@ fn group_bindings(source_bracket_type: BracketType, elements: &mut Vec<SExpressi
 
 fn group_assigns(source_bracket_type: BracketType, elements: &mut Vec<SExpression>)
 {
-    fn filter(slice: &[SExpression]) -> bool
+    // Arrows in round brackets are part of a function type (ie. `(int int -> bool)`), rather than a
+    //  reverse assignment
+    let is_type_list = source_bracket_type == BracketType::Round;
+
+    let filter = |slice: &[SExpression]| match slice
     {
-        match slice
+        [_lhs, SExpression::Symbol(assign_keyword, _), _rhs]
+            if assign_keyword == symbols::operators::ASSIGN
+                || (assign_keyword == symbols::operators::ASSIGN_REVERSE && !is_type_list) =>
         {
-            [_lhs, SExpression::Symbol(assign_keyword, _), _rhs]
-                if assign_keyword == symbols::operators::ASSIGN
-                    || assign_keyword == symbols::operators::ASSIGN_REVERSE =>
-            {
-                true
-            }
-            _ => false,
+            true
         }
-    }
+        _ => false,
+    };
     fn exclude_filter(elements: &[SExpression], index: usize) -> bool
     {
         // Make sure we aren't grouping something that's actually part of a let binding
@@ -23,11 +23,14 @@ pub fn apply(expression: &mut SExpression)
 }
 
 // A : B => [A B]
+// A : is B => [A <is B>]
 // note: [A : B] => [A : B]
 fn convert_inline_associations(source_bracket_type: BracketType, elements: &mut Vec<SExpression>)
 {
     let same_bracket = source_bracket_type == BracketType::Square;
 
+    group_associated_traits(elements);
+
     // A : B
     const SLICE_SIZE: usize = 3;
 
@@ -105,4 +108,41 @@ fn convert_inline_associations(source_bracket_type: BracketType, elements: &mut
             utilities::remove_empty(elements, empty_count);
         }
     }
-}
\ No newline at end of file
+}
+// A : is B => A : <is B>
+fn group_associated_traits(elements: &mut Vec<SExpression>)
+{
+    // : is B
+    const SLICE_SIZE: usize = 3;
+
+    if elements.len() < SLICE_SIZE
+    {
+        return;
+    }
+
+    let mut empty_count = 0;
+    let mut i = 0;
+
+    while i + SLICE_SIZE <= elements.len()
+    {
+        match &elements[i..i + SLICE_SIZE]
+        {
+            [SExpression::Symbol(associate_keyword, _), SExpression::Symbol(is_keyword, _), _]
+                if associate_keyword == symbols::keywords::ASSOCIATE_TYPE
+                    && is_keyword == symbols::keywords::IS =>
+            {
+                //  (... : is b ...) => (... : <> <is b> ...)
+                utilities::group(2, BracketType::None, &mut elements[i + 1..i + SLICE_SIZE]);
+
+                empty_count += 1;
+                i += SLICE_SIZE;
+            }
+            _ => i += 1,
+        }
+    }
+
+    if empty_count > 0
+    {
+        utilities::remove_empty(elements, empty_count);
+    }
+}
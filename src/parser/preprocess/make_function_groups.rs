@@ -52,8 +52,15 @@ fn group_arguments(elements: &mut Vec<SExpression>)
         let remaining_elements = element_count - i;
 
         // Get the offset that arguments could start, if any
+        //  - after [fn] for anonymous functions
         //  - after [fn _ -> _] or [fn _]
-        let argument_start = if remaining_elements >= NAME_RETURN_SLICE_SIZE
+        let argument_start = if remaining_elements >= NAME_SLICE_SIZE
+            && is_anonymous_with_arguments(&elements.as_slice()[i..i + NAME_SLICE_SIZE])
+        {
+            // Arguments start right after the keyword
+            1
+        }
+        else if remaining_elements >= NAME_RETURN_SLICE_SIZE
         {
             let name_slice = &elements.as_slice()[i..i + NAME_RETURN_SLICE_SIZE];
             match name_slice
@@ -146,6 +153,20 @@ fn group_arguments(elements: &mut Vec<SExpression>)
     }
 }
 
+// Check for the start of an anonymous function with arguments
+//  fn [...]
+fn is_anonymous_with_arguments(slice: &[SExpression]) -> bool
+{
+    match slice
+    {
+        [SExpression::Symbol(x, _), SExpression::List(BracketType::Square, _, _)] =>
+        {
+            x == symbols::keywords::FUNCTION
+        }
+        _ => false,
+    }
+}
+
 fn group_functions(source_bracket_type: BracketType, elements: &mut Vec<SExpression>)
 {
     fn filter_return_with_arguments(slice: &[SExpression]) -> bool
@@ -213,6 +234,49 @@ fn group_functions(source_bracket_type: BracketType, elements: &mut Vec<SExpress
             _ => false,
         }
     }
+    fn filter_anonymous_no_return(slice: &[SExpression]) -> bool
+    {
+        use SExpression::*;
+        match slice
+        {
+            // fn {...}
+            [Symbol(function_keyword, _), List(BracketType::Curly, _, _)]
+                if function_keyword == symbols::keywords::FUNCTION =>
+            {
+                true
+            }
+            _ => false,
+        }
+    }
+    fn filter_anonymous_return(slice: &[SExpression]) -> bool
+    {
+        use SExpression::*;
+        match slice
+        {
+            // fn -> _ {...}
+            [Symbol(function_keyword, _), Symbol(arrow_keyword, _), _, List(BracketType::Curly, _, _)]
+                if function_keyword == symbols::keywords::FUNCTION
+                    && arrow_keyword == symbols::keywords::RETURNS =>
+            {
+                true
+            }
+            _ => false,
+        }
+    }
+    utilities::make_groups(
+        2,
+        filter_anonymous_no_return,
+        source_bracket_type,
+        BracketType::Round,
+        elements,
+    );
+    utilities::make_groups(
+        4,
+        filter_anonymous_return,
+        source_bracket_type,
+        BracketType::Round,
+        elements,
+    );
     utilities::make_groups(
         3,
         filter_no_return_no_arguments,
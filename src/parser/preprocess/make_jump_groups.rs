@@ -0,0 +1,51 @@
+use super::*;
+use crate::language::symbols;
+
+pub fn apply(expression: &mut SExpression)
+{
+    match expression
+    {
+        SExpression::List(source_bracket_type, elements, _) =>
+        {
+            // Make groups in this list
+            group_returns(*source_bracket_type, elements);
+
+            // Then traverse child lists
+            for element in elements.iter_mut()
+            {
+                apply(element);
+            }
+        }
+        _ =>
+        {}
+    }
+}
+
+// return A => (return A)
+//  note: A must start on the same line, otherwise the return has no value
+//      ex. return
+//          (print "unreachable")
+fn group_returns(source_bracket_type: BracketType, elements: &mut Vec<SExpression>)
+{
+    fn filter(slice: &[SExpression]) -> bool
+    {
+        use SExpression::*;
+        match slice
+        {
+            [Symbol(return_keyword, return_source), value]
+                if return_keyword == symbols::keywords::RETURN =>
+            {
+                let is_keyword = match value
+                {
+                    Symbol(symbol, _) => symbols::keywords::contains(symbol),
+                    _ => false,
+                };
+
+                !is_keyword && value.get_source().get_start_line() == return_source.get_start_line()
+            }
+            _ => false,
+        }
+    }
+
+    utilities::make_groups(2, filter, source_bracket_type, BracketType::Round, elements);
+}
@@ -0,0 +1,63 @@
+use super::*;
+use crate::language::symbols;
+
+pub fn apply(expression: &mut SExpression)
+{
+    match expression
+    {
+        SExpression::List(source_bracket_type, elements, _) =>
+        {
+            // Make groups in this list
+            group_while(*source_bracket_type, elements);
+            group_for(*source_bracket_type, elements);
+
+            // Then traverse child lists
+            for element in elements.iter_mut()
+            {
+                apply(element);
+            }
+        }
+        _ =>
+        {}
+    }
+}
+
+// while A {...} => (while A {...})
+fn group_while(source_bracket_type: BracketType, elements: &mut Vec<SExpression>)
+{
+    fn filter(slice: &[SExpression]) -> bool
+    {
+        use SExpression::*;
+        match slice
+        {
+            [Symbol(while_keyword, _), _condition, List(BracketType::Curly, _, _)]
+                if while_keyword == symbols::keywords::WHILE =>
+            {
+                true
+            }
+            _ => false,
+        }
+    }
+
+    utilities::make_groups(3, filter, source_bracket_type, BracketType::Round, elements);
+}
+
+// for x in A {...} => (for x in A {...})
+fn group_for(source_bracket_type: BracketType, elements: &mut Vec<SExpression>)
+{
+    fn filter(slice: &[SExpression]) -> bool
+    {
+        use SExpression::*;
+        match slice
+        {
+            [Symbol(for_keyword, _), Symbol(_name, _), Symbol(in_keyword, _), _iterator, List(BracketType::Curly, _, _)]
+                if for_keyword == symbols::keywords::FOR && in_keyword == symbols::keywords::IN =>
+            {
+                true
+            }
+            _ => false,
+        }
+    }
+
+    utilities::make_groups(5, filter, source_bracket_type, BracketType::Round, elements);
+}
@@ -25,6 +25,8 @@ pub fn apply(expression: &mut SExpression)
 
 fn group_types(source_bracket_type: BracketType, elements: &mut Vec<SExpression>)
 {
+    group_parameterized_types(source_bracket_type, elements);
+
     fn filter(slice: &[SExpression]) -> bool
     {
         use SExpression::*;
@@ -42,6 +44,85 @@ fn group_types(source_bracket_type: BracketType, elements: &mut Vec<SExpression>
 
     utilities::make_groups(3, filter, source_bracket_type, BracketType::Round, elements);
 }
+// type Type [T] ... { ... } => (type Type [T] ... { ... })
+// type Type = T => (type Type = T)
+// type Type = is T => (type Type = is T)
+fn group_parameterized_types(source_bracket_type: BracketType, elements: &mut Vec<SExpression>)
+{
+    use SExpression::*;
+
+    // Get the size of the type definition starting at some index, if it has parameters or is an alias
+    fn get_group_size(elements: &[SExpression]) -> Option<usize>
+    {
+        match elements
+        {
+            [Symbol(type_keyword, _), Symbol(_type_name, _), Symbol(equals, _), Symbol(is_keyword, _), _, ..]
+                if type_keyword == symbols::keywords::TYPE
+                    && equals == symbols::operators::ASSIGN_BINDING
+                    && is_keyword == symbols::keywords::IS =>
+            {
+                Some(5)
+            }
+            [Symbol(type_keyword, _), Symbol(_type_name, _), Symbol(equals, _), _, ..]
+                if type_keyword == symbols::keywords::TYPE
+                    && equals == symbols::operators::ASSIGN_BINDING =>
+            {
+                Some(4)
+            }
+            [Symbol(type_keyword, _), Symbol(_type_name, _), rest @ ..]
+                if type_keyword == symbols::keywords::TYPE =>
+            {
+                let parameter_count = rest
+                    .iter()
+                    .take_while(|element| match element
+                    {
+                        List(BracketType::Square, _, _) => true,
+                        _ => false,
+                    })
+                    .count();
+
+                match rest.get(parameter_count)
+                {
+                    Some(List(BracketType::Curly, _, _)) if parameter_count > 0 =>
+                    {
+                        Some(parameter_count + 3)
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    let mut empty_count = 0;
+    let mut i = 0;
+
+    while i < elements.len()
+    {
+        match get_group_size(&elements[i..])
+        {
+            // The whole list is already a single group
+            Some(size) if size == elements.len() && source_bracket_type == BracketType::Round =>
+            {
+                break;
+            }
+            Some(size) =>
+            {
+                utilities::group_front(size, BracketType::Round, &mut elements[i..i + size]);
+
+                empty_count += size - 1;
+                i += size;
+            }
+            None => i += 1,
+        }
+    }
+
+    if empty_count > 0
+    {
+        utilities::remove_empty(elements, empty_count);
+    }
+}
+
 fn group_regions(source_bracket_type: BracketType, elements: &mut Vec<SExpression>)
 {
     fn filter_region(slice: &[SExpression]) -> bool
@@ -3,7 +3,9 @@ pub mod make_function_groups;
 pub mod make_type_groups;
 
 pub mod make_operator_groups;
+pub mod make_jump_groups;
 pub mod make_conditional_groups;
+pub mod make_loop_groups;
 pub mod make_assign_groups;
 pub mod make_when_groups;
 
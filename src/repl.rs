@@ -0,0 +1,238 @@
+use crate::compiler::Compiler;
+use crate::errors::*;
+use crate::interpreter::Interpreter;
+use crate::language::node::*;
+use crate::parser::Parser;
+
+use std::io::Write;
+
+const PROMPT: &str = "> ";
+const CONTINUE_PROMPT: &str = ". ";
+
+///
+/// An interactive session that reads, checks and evaluates inputs from stdin
+///
+/// - Inputs continue onto the next line until all of their brackets are closed
+/// - Each input is checked along with all previous inputs, so their bindings and definitions can
+///     be used, but only the nodes from the new input are evaluated
+/// - Line numbers in errors count the lines of all previous inputs that were kept
+///
+pub struct Repl
+{
+    parser:      Parser,
+    compiler:    Compiler,
+    interpreter: Interpreter,
+    history:     String,
+    types:       TypeContext,
+}
+
+impl Repl
+{
+    pub fn new() -> Repl
+    {
+        let mut compiler = Compiler::new();
+        compiler.set_allow_any_result(true);
+
+        Repl {
+            parser: Parser::new(),
+            compiler,
+            interpreter: Interpreter::new(),
+            history: String::new(),
+            types: TypeContext::new(),
+        }
+    }
+
+    ///
+    /// Read and evaluate inputs until stdin is closed
+    ///
+    pub fn run(&mut self)
+    {
+        // note: Types from every input are made in the session's own context
+        let _scope = self.types.enter();
+
+        let stdin = std::io::stdin();
+        let mut input = String::new();
+
+        loop
+        {
+            match input.is_empty()
+            {
+                true => print!("{}", PROMPT),
+                false => print!("{}", CONTINUE_PROMPT),
+            }
+            let _ = std::io::stdout().flush();
+
+            let mut line = String::new();
+            match stdin.read_line(&mut line)
+            {
+                Ok(0) | Err(_) => break,
+                Ok(_) => input.push_str(&line),
+            }
+
+            if input.trim().is_empty()
+            {
+                input.clear();
+                continue;
+            }
+            if self.is_incomplete(&input)
+            {
+                continue;
+            }
+
+            let mut complete_input = std::mem::take(&mut input);
+            if !complete_input.ends_with('\n')
+            {
+                complete_input.push('\n');
+            }
+
+            // note: Each input is checked again along with the next one, and the interpreter
+            //       doesn't look at types, so the types made for it can be removed afterwards
+            let checkpoint = self.types.checkpoint();
+            self.evaluate(complete_input);
+            self.types.restore(checkpoint);
+        }
+
+        println!();
+    }
+
+    ///
+    /// Check if an input has brackets that haven't been closed yet
+    ///
+    fn is_incomplete(&self, input: &String) -> bool
+    {
+        use crate::errors::s_expression_error::Error;
+
+        match self.parser.make_s_expression(input.clone())
+        {
+            ResultLog::Error(errors, _) => errors.iter().any(|error| match error
+            {
+                Error::UnclosedBracket(..) => true,
+                _ => false,
+            }),
+            _ => false,
+        }
+    }
+
+    ///
+    /// Check an input along with all previous inputs, then evaluate its nodes and print the
+    ///     resulting values
+    ///
+    /// - Inputs are only kept in the history if they are evaluated without errors
+    ///
+    fn evaluate(&mut self, input: String)
+    {
+        let start_line = self.history.matches('\n').count();
+        let text = format!("{}{}", self.history, input);
+
+        let root = match self.check(text.clone(), start_line)
+        {
+            Some(root) => root,
+            None => return,
+        };
+
+        let new_nodes = match root
+        {
+            Node::Sequence(sequence) => sequence
+                .get_nodes()
+                .iter()
+                .filter(|node| node.get_source().get_start_line() >= start_line)
+                .cloned()
+                .collect(),
+            node => vec![node],
+        };
+        let sequence = Sequence::new(SequenceMode::Transparent, new_nodes, Source::empty());
+
+        let result = match self.interpreter.evaluate(&sequence.clone().to_node())
+        {
+            ResultLog::Ok(result) | ResultLog::Warn(result, _) => result,
+            ResultLog::Error(errors, _) =>
+            {
+                print_errors(&errors);
+                return;
+            }
+        };
+
+        self.history = text;
+
+        // Print all new bindings and definitions, then the result of the input
+
+        for node in sequence.get_nodes()
+        {
+            match node
+            {
+                Node::Binding(binding) =>
+                {
+                    if let Some(value) = self.interpreter.lookup(binding.get_name())
+                    {
+                        println!(
+                            "{} = {} : {}",
+                            binding.get_name(),
+                            value,
+                            binding.get_binding().get_type().get()
+                        );
+                    }
+                }
+                Node::Function(function) =>
+                {
+                    println!("{} : {}", function.get_name(), function.get_type().get());
+                }
+                _ => (),
+            }
+        }
+
+        if let Some(result_node) = sequence.get_result_node()
+        {
+            if !result_node.is_type(&Type::Void)
+            {
+                println!("{} : {}", result, result_node.get_type().get());
+            }
+        }
+    }
+
+    ///
+    /// Parse and check some text, printing any errors or new warnings
+    ///
+    fn check(&self, text: String, start_line: usize) -> Option<Node>
+    {
+        let mut s_expression = report(self.parser.make_s_expression(text), start_line)?;
+        self.parser.preprocess(&mut s_expression);
+
+        let node = report(self.parser.make_node(s_expression), start_line)?;
+        report(self.compiler.check(node), start_line)
+    }
+}
+
+///
+/// Print the errors and warnings from some result, skipping any warnings from previous inputs
+///
+fn report<TResult, TError: ErrorTrait>(
+    result: ResultLog<TResult, TError>,
+    start_line: usize,
+) -> Option<TResult>
+{
+    let is_new = |warning: &TError| match warning.get_source()
+    {
+        Some(source) => source.get_start_line() >= start_line,
+        None => true,
+    };
+
+    match result
+    {
+        ResultLog::Ok(result) => Some(result),
+        ResultLog::Warn(result, warnings) =>
+        {
+            let new_warnings = warnings.into_iter().filter(is_new).collect();
+            print_warnings(&new_warnings);
+
+            Some(result)
+        }
+        ResultLog::Error(errors, warnings) =>
+        {
+            let new_warnings = warnings.into_iter().filter(is_new).collect();
+            print_warnings(&new_warnings);
+            print_errors(&errors);
+
+            None
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Source
@@ -7,8 +7,8 @@ pub struct Source
     end_line:   usize,
     start:      usize,
     end:        usize,
-    all_text:   Rc<String>,
-    all_lines:  Rc<Vec<String>>,
+    all_text:   Arc<String>,
+    all_lines:  Arc<Vec<String>>,
 }
 
 impl Source
@@ -26,8 +26,8 @@ impl Source
             end_line,
             start,
             end,
-            all_text: Rc::new(all_text.clone()),
-            all_lines: Rc::new(all_text.split('\n').map(String::from).collect()),
+            all_text: Arc::new(all_text.clone()),
+            all_lines: Arc::new(all_text.split('\n').map(String::from).collect()),
         }
     }
 
@@ -49,8 +49,8 @@ impl Source
         let mut min_start = usize::MAX;
         let mut max_end = 0;
 
-        let mut all_text = Rc::new(String::new());
-        let mut all_lines = Rc::new(Vec::new());
+        let mut all_text = Arc::new(String::new());
+        let mut all_lines = Arc::new(Vec::new());
 
         let mut all_are_empty = true;
 
@@ -118,8 +118,8 @@ impl Source
             end_line:   end_line + self.start_line,
             start:      start + self.start,
             end:        end + self.start,
-            all_text:   Rc::clone(&self.all_text),
-            all_lines:  Rc::clone(&self.all_lines),
+            all_text:   Arc::clone(&self.all_text),
+            all_lines:  Arc::clone(&self.all_lines),
         }
     }
 
@@ -130,8 +130,8 @@ impl Source
             end_line:   0,
             start:      0,
             end:        0,
-            all_text:   Rc::new(String::new()),
-            all_lines:  Rc::new(Vec::new()),
+            all_text:   Arc::new(String::new()),
+            all_lines:  Arc::new(Vec::new()),
         }
     }
 
@@ -28,6 +28,14 @@ impl<T> Indirect<T>
         return Rc::as_ref(&self.reference).borrow_mut();
     }
 
+    ///
+    /// Check if two Indirects refer to the same value
+    ///
+    pub fn is_same(&self, other: &Indirect<T>) -> bool
+    {
+        Rc::ptr_eq(&self.reference, &other.reference)
+    }
+
     pub fn unwrap(self) -> T
     {
         match Rc::try_unwrap(self.reference)
@@ -5,6 +5,25 @@ typedef int bool;
 #define true 1
 #define false 0
 
+typedef struct
+{
+    void* apply;
+    void* environment;
+} _specs__Closure;
+
+typedef struct
+{
+    long length;
+    const char* data;
+} _specs__String;
+
+typedef struct
+{
+    int n;
+    int end;
+    int step;
+} _Specs_NumericIterator;
+
 int __SpecsMain__();
 
 int main( int argc, char** argv )
@@ -16,4 +35,47 @@ int main( int argc, char** argv )
 void* _specs__Allocate(size_t size)
 {
     return malloc(size);
-}
\ No newline at end of file
+}
+
+void _specs__Print(_specs__String string)
+{
+    fwrite(string.data, 1, string.length, stdout);
+}
+
+_Specs_NumericIterator _Specs_NumericIterator__New( int start, int end, int step )
+{
+    _Specs_NumericIterator new_iterator = { start, end, step };
+    return new_iterator;
+}
+
+int _Specs_NumericIterator__Next( _Specs_NumericIterator* iterator )
+{
+    int result = iterator->n;
+    iterator->n += iterator->step;
+    return result;
+}
+
+bool _Specs_NumericIterator__HasNext( _Specs_NumericIterator* iterator )
+{
+    return iterator->n < iterator->end;
+}
+
+void _specs__CheckBounds(long index, long length)
+{
+#ifndef SPECS_NO_BOUNDS_CHECKS
+    if (index < 0 || index >= length)
+    {
+        fprintf(stderr, "index %ld out of bounds for array of length %ld\n", index, length);
+        exit(1);
+    }
+#endif
+}
+
+void _specs__CheckLength(long length)
+{
+    if (length < 0)
+    {
+        fprintf(stderr, "can't make an array of negative length %ld\n", length);
+        exit(1);
+    }
+}
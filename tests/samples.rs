@@ -0,0 +1,180 @@
+//!
+//! Run the sample programs in test/in and compare their output to test/out
+//!
+//! - A sample with `test/out/<name>.out` is built to C, compiled with `$CC` (or `cc`) and run, and
+//!     is also evaluated by the interpreter, unless it is listed in `COMPILED_ONLY`
+//!     - Both must print exactly the expected output
+//! - A sample with `test/out/<name>.err` is checked, and must fail with exactly the expected errors
+//! - Samples without either file aren't run (ie. notes and samples written in older syntax)
+//!
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+///
+/// Samples that are only compared through C
+///
+/// - Type definitions can't be interpreted yet
+///
+const COMPILED_ONLY: &[&str] = &[
+    "enums",
+    "parameterized",
+    "structs",
+    "traits",
+    "types",
+];
+
+#[test]
+fn samples_match_expected_output()
+{
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("test");
+    let build_directory = Path::new(env!("CARGO_TARGET_TMPDIR")).join("samples");
+    fs::create_dir_all(&build_directory).unwrap();
+
+    let mut samples: Vec<PathBuf> = fs::read_dir(root.join("in"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "sp"))
+        .collect();
+    samples.sort();
+
+    let mut failures = Vec::new();
+    let mut count = 0;
+
+    for sample in samples
+    {
+        let name = sample.file_stem().unwrap().to_string_lossy().into_owned();
+        let expected_output = root.join("out").join(format!("{}.out", name));
+        let expected_errors = root.join("out").join(format!("{}.err", name));
+
+        if expected_output.exists()
+        {
+            let expected = fs::read_to_string(&expected_output).unwrap();
+            count += 1;
+
+            let compiled = run_compiled(&sample, &root.join("out"), &build_directory, &name);
+            if let Some(failure) = compare(&name, "compiled C", &expected, compiled)
+            {
+                failures.push(failure);
+            }
+
+            if !COMPILED_ONLY.contains(&name.as_str())
+            {
+                let interpreted = run_specs(&["run", sample.to_str().unwrap()]);
+                if let Some(failure) = compare(&name, "interpreter", &expected, interpreted)
+                {
+                    failures.push(failure);
+                }
+            }
+        }
+        else if expected_errors.exists()
+        {
+            let expected = fs::read_to_string(&expected_errors).unwrap();
+            count += 1;
+
+            let checked = run_specs(&["check", "--quiet", sample.to_str().unwrap()]);
+            let failure = match checked
+            {
+                Ok(output) => Some(format!(
+                    "{}: expected errors, but check succeeded\n{}",
+                    name, output
+                )),
+                Err(output) if output != expected => Some(format!(
+                    "{}: unexpected errors\n--- expected\n{}\n--- found\n{}",
+                    name, expected, output
+                )),
+                Err(_) => None,
+            };
+            failures.extend(failure);
+        }
+    }
+
+    assert!(count > 0, "No samples have expected output");
+    assert!(failures.is_empty(), "\n{}", failures.join("\n\n"));
+}
+
+///
+/// Build a sample to C, compile it and run it, giving its output
+///
+fn run_compiled(
+    sample: &Path,
+    runtime_directory: &Path,
+    build_directory: &Path,
+    name: &str,
+) -> Result<String, String>
+{
+    let c_path = build_directory.join(format!("{}.c", name));
+    let exe_path = build_directory.join(name);
+
+    run_specs(&[
+        "build",
+        "--quiet",
+        sample.to_str().unwrap(),
+        "-o",
+        c_path.to_str().unwrap(),
+    ])?;
+
+    let cc = std::env::var("CC").unwrap_or_else(|_| String::from("cc"));
+    let compiled = Command::new(&cc)
+        .arg("-w")
+        .arg("-I")
+        .arg(runtime_directory)
+        .arg(&c_path)
+        .arg("-o")
+        .arg(&exe_path)
+        .output()
+        .unwrap_or_else(|error| panic!("Failed to run C compiler '{}': {}", cc, error));
+    get_stdout(compiled)?;
+
+    get_stdout(Command::new(&exe_path).output().unwrap())
+}
+
+///
+/// Run the specs command-line tool, giving its output
+///
+fn run_specs(args: &[&str]) -> Result<String, String>
+{
+    get_stdout(
+        Command::new(env!("CARGO_BIN_EXE_specs"))
+            .args(args)
+            .output()
+            .unwrap(),
+    )
+}
+
+fn get_stdout(output: Output) -> Result<String, String>
+{
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+
+    if output.status.success()
+    {
+        Ok(stdout)
+    }
+    else
+    {
+        Err(format!(
+            "{}{}",
+            stdout,
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    }
+}
+
+fn compare(
+    name: &str,
+    runner: &str,
+    expected: &str,
+    found: Result<String, String>,
+) -> Option<String>
+{
+    match found
+    {
+        Ok(found) if found == expected => None,
+        Ok(found) => Some(format!(
+            "{} ({}): unexpected output\n--- expected\n{}\n--- found\n{}",
+            name, runner, expected, found
+        )),
+        Err(error) => Some(format!("{} ({}): failed\n{}", name, runner, error)),
+    }
+}